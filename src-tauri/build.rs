@@ -1,3 +1,5 @@
 fn main() {
+    // The terminal-only build has no Tauri app to configure.
+    #[cfg(feature = "gui")]
     tauri_build::build()
 }
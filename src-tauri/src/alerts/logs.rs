@@ -0,0 +1,397 @@
+use super::{enabled_by_default, AlertRecord, AlertStatus};
+use crate::{
+    error::{AppError, AppResult},
+    models::log_entry::LogEntry,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use ts_rs::TS;
+
+/// Matching lines sent along with an alert, the latest ones.
+pub const MAX_ALERT_ENTRIES: usize = 20;
+
+/// Alerts when at least `min_matches` tailed lines within `window_secs`
+/// match `level` and `pattern`, e.g. more than 50 `ERROR` lines a minute or
+/// any line matching `OutOfMemory`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct LogAlertRule {
+    /// Assigned when the rule is first saved.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Level the line must have (e.g. `ERROR`, case-insensitive); any level
+    /// when absent.
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Regular expression searched in the line; any line when absent.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default = "default_min_matches")]
+    pub min_matches: usize,
+    #[serde(default = "default_window_secs")]
+    #[ts(type = "number")]
+    pub window_secs: u64,
+    /// After firing, the rule does not fire again for this long; the lines
+    /// it would have fired for are only counted.
+    #[serde(default = "default_cooldown_secs")]
+    #[ts(type = "number")]
+    pub cooldown_secs: u64,
+    /// Desktop notification when the alert fires.
+    #[serde(default = "enabled_by_default")]
+    pub notify: bool,
+}
+
+fn default_min_matches() -> usize {
+    1
+}
+
+fn default_window_secs() -> u64 {
+    60
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+impl LogAlertRule {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.min_matches == 0 {
+            return Err(AppError::InvalidArgument(
+                "at least one matching line is required".to_string(),
+            ));
+        }
+        if self.window_secs == 0 {
+            return Err(AppError::InvalidArgument(
+                "the window must be at least one second".to_string(),
+            ));
+        }
+        if let Some(pattern) = &self.pattern {
+            Regex::new(pattern).map_err(|e| {
+                AppError::InvalidArgument(format!("invalid pattern {}: {}", pattern, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// What the rule counts, e.g. `ERROR lines matching "OutOfMemory"`.
+    fn describe(&self) -> String {
+        let lines = match &self.level {
+            Some(level) => format!("{} lines", level.to_uppercase()),
+            None => "lines".to_string(),
+        };
+        match &self.pattern {
+            Some(pattern) => format!("{} matching \"{}\"", lines, pattern),
+            None => lines,
+        }
+    }
+}
+
+/// What the engine remembers about one rule between batches.
+#[derive(Default)]
+struct RuleTracker {
+    /// `pattern` compiled, with the pattern it was compiled from.
+    regex: Option<(String, Regex)>,
+    /// Matches within the window per batch, with when the batch was seen.
+    hits: VecDeque<(i64, usize)>,
+    /// The sum of `hits`.
+    hit_count: usize,
+    /// The latest matching lines within the window.
+    entries: VecDeque<(i64, LogEntry)>,
+    fired_at: Option<i64>,
+    /// Matches that reached the threshold during the cooldown.
+    suppressed: usize,
+}
+
+impl RuleTracker {
+    fn matches(&mut self, rule: &LogAlertRule, entry: &LogEntry) -> bool {
+        if let Some(level) = &rule.level {
+            if !entry.level.eq_ignore_ascii_case(level) {
+                return false;
+            }
+        }
+        let Some(pattern) = &rule.pattern else {
+            return true;
+        };
+        if self.regex.as_ref().map_or(true, |(p, _)| p != pattern) {
+            match Regex::new(pattern) {
+                Ok(regex) => self.regex = Some((pattern.clone(), regex)),
+                Err(e) => {
+                    log::warn!("Log alert {} has an invalid pattern: {}", rule.name, e);
+                    return false;
+                }
+            }
+        }
+        self.regex
+            .as_ref()
+            .is_some_and(|(_, regex)| regex.is_match(&entry.message))
+    }
+}
+
+/// Evaluates [`LogAlertRule`]s over the log lines as they are tailed. A rule
+/// fires with the matching lines as context, then stays quiet for its
+/// cooldown.
+///
+/// ```
+/// use app_lib::{
+///     alerts::logs::{LogAlertEngine, LogAlertRule},
+///     models::log_entry::LogEntry,
+/// };
+///
+/// let rules = [LogAlertRule {
+///     id: "oom".into(),
+///     name: "Out of memory".into(),
+///     enabled: true,
+///     level: None,
+///     pattern: Some("OutOfMemory".into()),
+///     min_matches: 1,
+///     window_secs: 60,
+///     cooldown_secs: 300,
+///     notify: false,
+/// }];
+/// let line = |message: &str| LogEntry {
+///     timestamp: "2024-01-01 12:00:00".into(),
+///     level: "ERROR".into(),
+///     message: message.into(),
+///     stream: None,
+/// };
+///
+/// let mut engine = LogAlertEngine::default();
+/// assert!(engine.evaluate(&rules, 0, &[line("GC took 2 s")]).is_empty());
+/// let fired = engine.evaluate(&rules, 1_000, &[line("java.lang.OutOfMemoryError")]);
+/// assert_eq!(fired.len(), 1);
+/// assert_eq!(fired[0].entries[0].message, "java.lang.OutOfMemoryError");
+///
+/// // Within the cooldown: only counted.
+/// assert!(engine.evaluate(&rules, 2_000, &[line("OutOfMemoryError again")]).is_empty());
+/// let fired = engine.evaluate(&rules, 302_000, &[line("OutOfMemoryError")]);
+/// assert_eq!(fired.len(), 1);
+/// assert!(fired[0].message.contains("1 more during the cooldown"));
+/// ```
+#[derive(Default)]
+pub struct LogAlertEngine {
+    trackers: HashMap<String, RuleTracker>,
+}
+
+impl LogAlertEngine {
+    /// Checks `entries`, tailed at `now_ms`, and returns the alerts that
+    /// fired. Disabled rules are skipped and forget their matches.
+    pub fn evaluate(
+        &mut self,
+        rules: &[LogAlertRule],
+        now_ms: i64,
+        entries: &[LogEntry],
+    ) -> Vec<AlertRecord> {
+        self.trackers
+            .retain(|id, _| rules.iter().any(|r| r.enabled && &r.id == id));
+        let mut records = Vec::new();
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let tracker = self.trackers.entry(rule.id.clone()).or_default();
+            let mut matched = 0;
+            for entry in entries {
+                if tracker.matches(rule, entry) {
+                    matched += 1;
+                    tracker.entries.push_back((now_ms, entry.clone()));
+                    if tracker.entries.len() > MAX_ALERT_ENTRIES {
+                        tracker.entries.pop_front();
+                    }
+                }
+            }
+
+            if matched > 0 {
+                tracker.hits.push_back((now_ms, matched));
+                tracker.hit_count += matched;
+            }
+            let window_start = now_ms - rule.window_secs as i64 * 1000;
+            while let Some(&(at, count)) = tracker.hits.front() {
+                if at > window_start {
+                    break;
+                }
+                tracker.hits.pop_front();
+                tracker.hit_count -= count;
+            }
+            while tracker
+                .entries
+                .front()
+                .is_some_and(|(at, _)| *at <= window_start)
+            {
+                tracker.entries.pop_front();
+            }
+            if matched == 0 || tracker.hit_count < rule.min_matches {
+                continue;
+            }
+
+            let cooling = tracker
+                .fired_at
+                .is_some_and(|at| now_ms - at < rule.cooldown_secs as i64 * 1000);
+            if cooling {
+                tracker.suppressed += matched;
+                continue;
+            }
+
+            let mut message = format!(
+                "{} {} in the last {} s",
+                tracker.hit_count,
+                rule.describe(),
+                rule.window_secs
+            );
+            if tracker.suppressed > 0 {
+                message.push_str(&format!(
+                    ", {} more during the cooldown",
+                    tracker.suppressed
+                ));
+            }
+            records.push(AlertRecord {
+                rule_id: rule.id.clone(),
+                rule_name: rule.name.clone(),
+                status: AlertStatus::Firing,
+                message,
+                value: Some(tracker.hit_count as f64),
+                at_ms: now_ms,
+                entries: tracker.entries.drain(..).map(|(_, e)| e).collect(),
+            });
+            tracker.fired_at = Some(now_ms);
+            tracker.suppressed = 0;
+        }
+        records
+    }
+
+    /// Forgets the matches of a rule, e.g. after it was edited.
+    pub fn reset(&mut self, rule_id: &str) {
+        self.trackers.remove(rule_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(min_matches: usize, window_secs: u64, cooldown_secs: u64) -> LogAlertRule {
+        LogAlertRule {
+            id: "rule".into(),
+            name: "rule".into(),
+            enabled: true,
+            level: Some("error".into()),
+            pattern: None,
+            min_matches,
+            window_secs,
+            cooldown_secs,
+            notify: false,
+        }
+    }
+
+    fn line(level: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: "2024-01-01 12:00:00".into(),
+            level: level.into(),
+            message: message.into(),
+            stream: None,
+        }
+    }
+
+    fn error() -> LogEntry {
+        line("ERROR", "failed")
+    }
+
+    #[test]
+    fn cooldown_ends_exactly_after_its_duration() {
+        let rules = [rule(1, 60, 10)];
+        let mut engine = LogAlertEngine::default();
+        assert_eq!(engine.evaluate(&rules, 0, &[error()]).len(), 1);
+        assert!(engine
+            .evaluate(&rules, 9_999, &[error(), error()])
+            .is_empty());
+
+        let fired = engine.evaluate(&rules, 10_000, &[error()]);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].message.contains("2 more during the cooldown"));
+        assert_eq!(fired[0].value, Some(4.0));
+    }
+
+    #[test]
+    fn matches_leave_the_window_exactly_after_its_duration() {
+        let rules = [rule(2, 10, 0)];
+        let mut engine = LogAlertEngine::default();
+        assert!(engine.evaluate(&rules, 0, &[error()]).is_empty());
+        assert!(engine.evaluate(&rules, 10_000, &[error()]).is_empty());
+        assert_eq!(engine.evaluate(&rules, 19_999, &[error()]).len(), 1);
+    }
+
+    #[test]
+    fn batches_without_a_match_do_not_fire() {
+        let rules = [rule(1, 60, 0)];
+        let mut engine = LogAlertEngine::default();
+        assert_eq!(engine.evaluate(&rules, 0, &[error()]).len(), 1);
+        assert!(engine
+            .evaluate(&rules, 1_000, &[line("INFO", "ok")])
+            .is_empty());
+    }
+
+    #[test]
+    fn disabling_a_rule_forgets_its_matches_and_cooldown() {
+        let mut rules = [rule(2, 60, 300)];
+        let mut engine = LogAlertEngine::default();
+        assert!(engine.evaluate(&rules, 0, &[error()]).is_empty());
+
+        rules[0].enabled = false;
+        assert!(engine
+            .evaluate(&rules, 1_000, &[error(), error()])
+            .is_empty());
+        rules[0].enabled = true;
+        assert!(engine.evaluate(&rules, 2_000, &[error()]).is_empty());
+        assert_eq!(engine.evaluate(&rules, 3_000, &[error()]).len(), 1);
+
+        engine.reset("rule");
+        assert!(engine.evaluate(&rules, 4_000, &[error()]).is_empty());
+        assert_eq!(engine.evaluate(&rules, 5_000, &[error()]).len(), 1);
+    }
+
+    #[test]
+    fn level_and_pattern_must_both_match() {
+        let mut rules = [rule(1, 60, 0)];
+        rules[0].pattern = Some("disk (full|error)".into());
+        let mut engine = LogAlertEngine::default();
+        assert!(engine
+            .evaluate(&rules, 0, &[line("WARNING", "disk full")])
+            .is_empty());
+        assert!(engine
+            .evaluate(&rules, 1_000, &[line("ERROR", "net down")])
+            .is_empty());
+        assert_eq!(
+            engine
+                .evaluate(&rules, 2_000, &[line("error", "disk full")])
+                .len(),
+            1
+        );
+
+        rules[0].pattern = Some("(".into());
+        assert!(engine
+            .evaluate(&rules, 3_000, &[line("ERROR", "(")])
+            .is_empty());
+    }
+
+    #[test]
+    fn only_the_latest_lines_are_attached() {
+        let rules = [rule(1, 60, 0)];
+        let lines: Vec<LogEntry> = (0..MAX_ALERT_ENTRIES + 5)
+            .map(|i| line("ERROR", &i.to_string()))
+            .collect();
+        let fired = LogAlertEngine::default().evaluate(&rules, 0, &lines);
+        assert_eq!(fired[0].entries.len(), MAX_ALERT_ENTRIES);
+        assert_eq!(fired[0].entries[0].message, "5");
+    }
+
+    #[test]
+    fn rules_are_validated() {
+        assert!(rule(1, 1, 0).validate().is_ok());
+        assert!(rule(0, 60, 0).validate().is_err());
+        assert!(rule(1, 0, 0).validate().is_err());
+        let mut bad = rule(1, 60, 0);
+        bad.pattern = Some("[".into());
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,489 @@
+use super::{enabled_by_default, AlertRecord, AlertStatus};
+use crate::{
+    error::{AppError, AppResult},
+    models::metrics::MetricsSample,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use ts_rs::TS;
+
+/// Alerts when `metric` is past `threshold` for `for_secs`.
+///
+/// `metric` is any series name of [`MetricsSample::named_values`] (e.g.
+/// `cpu.usage`, `cpu.temp`, `gpu.temp`, `sensor[...]`) or one of the derived
+/// `memory.used_percent`, `disk.free[<mount>]` and
+/// `disk.free_percent[<mount>]`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct MetricAlertRule {
+    /// Assigned when the rule is first saved.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub metric: String,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// Where a firing alert resolves again, for hysteresis. Defaults to
+    /// `threshold`.
+    #[serde(default)]
+    pub clear_threshold: Option<f64>,
+    /// How long the threshold must be crossed before the alert fires.
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub for_secs: u64,
+    /// Desktop notification when the alert fires and resolves.
+    #[serde(default = "enabled_by_default")]
+    pub notify: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    fn crossed(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::Below => value < threshold,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Comparison::Above => "above",
+            Comparison::Below => "below",
+        }
+    }
+}
+
+impl MetricAlertRule {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.metric.trim().is_empty() {
+            return Err(AppError::InvalidArgument(
+                "a metric is required".to_string(),
+            ));
+        }
+        if !self.threshold.is_finite() {
+            return Err(AppError::InvalidArgument(
+                "threshold must be a number".to_string(),
+            ));
+        }
+        if let Some(clear) = self.clear_threshold {
+            // Clearing must not need a value that still crosses the threshold.
+            if !clear.is_finite() || self.comparison.crossed(clear, self.threshold) {
+                return Err(AppError::InvalidArgument(format!(
+                    "clear threshold {} must not be {} the threshold {}",
+                    clear,
+                    self.comparison.as_str(),
+                    self.threshold
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn clear_threshold(&self) -> f64 {
+        self.clear_threshold.unwrap_or(self.threshold)
+    }
+}
+
+/// A rule that is currently pending or firing.
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct ActiveAlert {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub status: AlertStatus,
+    /// When the threshold was first crossed.
+    #[ts(type = "number")]
+    pub since_ms: i64,
+    pub value: f64,
+}
+
+/// Tracks [`MetricAlertRule`]s over successive samples and reports every
+/// state change: pending when the threshold is crossed, firing once it
+/// stayed crossed for the rule's duration, resolved when a firing alert's
+/// value is back past the clear threshold or its rule is disabled, removed
+/// or edited. A pending alert whose value recovers is dropped silently.
+///
+/// ```
+/// use app_lib::{
+///     alerts::{
+///         metric::{Comparison, MetricAlertEngine, MetricAlertRule},
+///         AlertStatus,
+///     },
+///     models::metrics::MetricsSample,
+/// };
+///
+/// let rules = [MetricAlertRule {
+///     id: "cpu".into(),
+///     name: "CPU busy".into(),
+///     enabled: true,
+///     metric: "cpu.usage".into(),
+///     comparison: Comparison::Above,
+///     threshold: 90.0,
+///     clear_threshold: Some(80.0),
+///     for_secs: 10,
+///     notify: false,
+/// }];
+/// let sample = |seconds: i64, cpu_usage| MetricsSample {
+///     timestamp_ms: seconds * 1000,
+///     cpu_usage,
+///     ..MetricsSample::default()
+/// };
+/// let mut engine = MetricAlertEngine::default();
+/// let mut statuses = |s| -> Vec<AlertStatus> {
+///     engine.evaluate(&rules, &s).into_iter().map(|r| r.status).collect()
+/// };
+///
+/// assert_eq!(statuses(sample(0, 95.0)), [AlertStatus::Pending]);
+/// assert_eq!(statuses(sample(10, 93.0)), [AlertStatus::Firing]);
+/// // Below the threshold but not the clear threshold: still firing.
+/// assert!(statuses(sample(11, 85.0)).is_empty());
+/// assert_eq!(statuses(sample(12, 75.0)), [AlertStatus::Resolved]);
+///
+/// // A firing alert whose rule goes away resolves rather than vanishing.
+/// assert_eq!(statuses(sample(13, 99.0)), [AlertStatus::Pending]);
+/// assert_eq!(statuses(sample(23, 99.0)), [AlertStatus::Firing]);
+/// let resolved = engine.evaluate(&[], &sample(24, 99.0));
+/// assert_eq!(resolved[0].status, AlertStatus::Resolved);
+/// assert_eq!(resolved[0].message, "rule removed");
+/// ```
+#[derive(Default)]
+pub struct MetricAlertEngine {
+    active: HashMap<String, ActiveAlert>,
+}
+
+impl MetricAlertEngine {
+    pub fn evaluate(
+        &mut self,
+        rules: &[MetricAlertRule],
+        sample: &MetricsSample,
+    ) -> Vec<AlertRecord> {
+        let now_ms = sample.timestamp_ms;
+        let mut records = Vec::new();
+        let gone: Vec<String> = self
+            .active
+            .keys()
+            .filter(|id| !rules.iter().any(|r| r.enabled && &r.id == *id))
+            .cloned()
+            .collect();
+        for id in gone {
+            let reason = if rules.iter().any(|r| r.id == id) {
+                "rule disabled"
+            } else {
+                "rule removed"
+            };
+            records.extend(self.reset(&id, now_ms, reason));
+        }
+        let values = sample.named_values();
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            // No data (e.g. no GPU) changes nothing.
+            let Some(value) = metric_value(sample, &values, &rule.metric) else {
+                continue;
+            };
+            let crossed = rule.comparison.crossed(value, rule.threshold);
+            let record = |status, message: String| AlertRecord {
+                rule_id: rule.id.clone(),
+                rule_name: rule.name.clone(),
+                status,
+                message,
+                value: Some(value),
+                at_ms: now_ms,
+                entries: Vec::new(),
+            };
+            let breach = || {
+                format!(
+                    "{} is {:.1}, {} {}",
+                    rule.metric,
+                    value,
+                    rule.comparison.as_str(),
+                    rule.threshold
+                )
+            };
+
+            match self.active.get_mut(&rule.id) {
+                None if crossed => {
+                    let status = if rule.for_secs == 0 {
+                        AlertStatus::Firing
+                    } else {
+                        AlertStatus::Pending
+                    };
+                    self.active.insert(
+                        rule.id.clone(),
+                        ActiveAlert {
+                            rule_id: rule.id.clone(),
+                            rule_name: rule.name.clone(),
+                            status,
+                            since_ms: now_ms,
+                            value,
+                        },
+                    );
+                    records.push(record(status, breach()));
+                }
+                None => {}
+                Some(alert) if alert.status == AlertStatus::Pending => {
+                    if !crossed {
+                        self.active.remove(&rule.id);
+                    } else if now_ms - alert.since_ms >= rule.for_secs as i64 * 1000 {
+                        alert.status = AlertStatus::Firing;
+                        alert.value = value;
+                        records.push(record(
+                            AlertStatus::Firing,
+                            format!("{} for {} s", breach(), rule.for_secs),
+                        ));
+                    } else {
+                        alert.value = value;
+                    }
+                }
+                Some(alert) => {
+                    if rule.comparison.crossed(value, rule.clear_threshold()) {
+                        alert.value = value;
+                    } else {
+                        self.active.remove(&rule.id);
+                        records.push(record(
+                            AlertStatus::Resolved,
+                            format!("{} is back to {:.1}", rule.metric, value),
+                        ));
+                    }
+                }
+            }
+        }
+        records
+    }
+
+    /// Pending and firing alerts, oldest first.
+    pub fn active(&self) -> Vec<ActiveAlert> {
+        let mut active: Vec<ActiveAlert> = self.active.values().cloned().collect();
+        active.sort_by_key(|a| a.since_ms);
+        active
+    }
+
+    /// Forgets the state of a rule, e.g. after it was edited. A firing alert
+    /// is resolved with `reason` as the message; a pending one is dropped.
+    pub fn reset(&mut self, rule_id: &str, now_ms: i64, reason: &str) -> Option<AlertRecord> {
+        let alert = self.active.remove(rule_id)?;
+        (alert.status == AlertStatus::Firing).then(|| AlertRecord {
+            rule_id: alert.rule_id,
+            rule_name: alert.rule_name,
+            status: AlertStatus::Resolved,
+            message: reason.to_string(),
+            value: Some(alert.value),
+            at_ms: now_ms,
+            entries: Vec::new(),
+        })
+    }
+}
+
+/// The value of `metric` in `sample`, with `values` its named values.
+pub fn metric_value(
+    sample: &MetricsSample,
+    values: &BTreeMap<String, f64>,
+    metric: &str,
+) -> Option<f64> {
+    if metric == "memory.used_percent" {
+        return (sample.memory_total > 0)
+            .then(|| sample.memory_used as f64 / sample.memory_total as f64 * 100.0);
+    }
+    let mount = |prefix: &str| {
+        let mount_point = metric.strip_prefix(prefix)?.strip_suffix(']')?;
+        sample.mounts.iter().find(|m| m.mount_point == mount_point)
+    };
+    if let Some(mount) = mount("disk.free[") {
+        return Some(mount.total_bytes.saturating_sub(mount.used_bytes) as f64);
+    }
+    if let Some(mount) = mount("disk.free_percent[") {
+        return (mount.total_bytes > 0).then(|| {
+            mount.total_bytes.saturating_sub(mount.used_bytes) as f64 / mount.total_bytes as f64
+                * 100.0
+        });
+    }
+    values.get(metric).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::metrics::MountSample;
+
+    fn rule(comparison: Comparison, threshold: f64, for_secs: u64) -> MetricAlertRule {
+        MetricAlertRule {
+            id: "rule".into(),
+            name: "rule".into(),
+            enabled: true,
+            metric: "cpu.usage".into(),
+            comparison,
+            threshold,
+            clear_threshold: None,
+            for_secs,
+            notify: false,
+        }
+    }
+
+    fn sample(ms: i64, cpu_usage: f32) -> MetricsSample {
+        MetricsSample {
+            timestamp_ms: ms,
+            cpu_usage,
+            ..MetricsSample::default()
+        }
+    }
+
+    fn statuses(
+        engine: &mut MetricAlertEngine,
+        rules: &[MetricAlertRule],
+        sample: MetricsSample,
+    ) -> Vec<AlertStatus> {
+        engine
+            .evaluate(rules, &sample)
+            .into_iter()
+            .map(|r| r.status)
+            .collect()
+    }
+
+    #[test]
+    fn fires_exactly_when_the_duration_has_passed() {
+        let rules = [rule(Comparison::Above, 50.0, 5)];
+        let mut engine = MetricAlertEngine::default();
+        assert_eq!(
+            statuses(&mut engine, &rules, sample(0, 60.0)),
+            [AlertStatus::Pending]
+        );
+        assert!(statuses(&mut engine, &rules, sample(4_999, 60.0)).is_empty());
+        assert_eq!(
+            statuses(&mut engine, &rules, sample(5_000, 60.0)),
+            [AlertStatus::Firing]
+        );
+        assert_eq!(engine.active()[0].status, AlertStatus::Firing);
+    }
+
+    #[test]
+    fn zero_duration_fires_at_once_and_threshold_itself_does_not_cross() {
+        let rules = [rule(Comparison::Below, 10.0, 0)];
+        let mut engine = MetricAlertEngine::default();
+        assert!(statuses(&mut engine, &rules, sample(0, 10.0)).is_empty());
+        assert_eq!(
+            statuses(&mut engine, &rules, sample(1_000, 5.0)),
+            [AlertStatus::Firing]
+        );
+        assert_eq!(
+            statuses(&mut engine, &rules, sample(2_000, 10.0)),
+            [AlertStatus::Resolved]
+        );
+    }
+
+    #[test]
+    fn pending_alert_that_recovers_is_dropped_silently() {
+        let rules = [rule(Comparison::Above, 50.0, 5)];
+        let mut engine = MetricAlertEngine::default();
+        statuses(&mut engine, &rules, sample(0, 60.0));
+        assert!(statuses(&mut engine, &rules, sample(1_000, 40.0)).is_empty());
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn disabling_a_firing_rule_resolves_it() {
+        let mut rules = [rule(Comparison::Above, 50.0, 0)];
+        let mut engine = MetricAlertEngine::default();
+        statuses(&mut engine, &rules, sample(0, 60.0));
+
+        rules[0].enabled = false;
+        let records = engine.evaluate(&rules, &sample(1_000, 60.0));
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, AlertStatus::Resolved);
+        assert_eq!(records[0].message, "rule disabled");
+        assert_eq!(records[0].at_ms, 1_000);
+        assert!(engine.active().is_empty());
+
+        rules[0].enabled = true;
+        assert_eq!(
+            statuses(&mut engine, &rules, sample(2_000, 60.0)),
+            [AlertStatus::Firing]
+        );
+    }
+
+    #[test]
+    fn disabling_or_deleting_a_pending_rule_is_silent() {
+        let mut rules = vec![rule(Comparison::Above, 50.0, 5)];
+        let mut engine = MetricAlertEngine::default();
+        statuses(&mut engine, &rules, sample(0, 60.0));
+        rules[0].enabled = false;
+        assert!(statuses(&mut engine, &rules, sample(1_000, 60.0)).is_empty());
+
+        rules[0].enabled = true;
+        statuses(&mut engine, &rules, sample(2_000, 60.0));
+        rules.clear();
+        assert!(statuses(&mut engine, &rules, sample(3_000, 60.0)).is_empty());
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn reset_resolves_only_a_firing_alert() {
+        let rules = [rule(Comparison::Above, 50.0, 5)];
+        let mut engine = MetricAlertEngine::default();
+        statuses(&mut engine, &rules, sample(0, 60.0));
+        assert!(engine.reset("rule", 1_000, "rule edited").is_none());
+        assert!(engine.active().is_empty());
+
+        statuses(&mut engine, &rules, sample(2_000, 60.0));
+        statuses(&mut engine, &rules, sample(7_000, 60.0));
+        let record = engine
+            .reset("rule", 8_000, "rule edited")
+            .expect("firing alert");
+        assert_eq!(record.status, AlertStatus::Resolved);
+        assert_eq!(record.message, "rule edited");
+        assert_eq!(record.value, Some(60.0));
+        assert!(engine.reset("rule", 9_000, "rule edited").is_none());
+    }
+
+    #[test]
+    fn missing_metric_changes_nothing() {
+        let mut rules = [rule(Comparison::Above, 50.0, 0)];
+        rules[0].metric = "gpu.temp".into();
+        let mut engine = MetricAlertEngine::default();
+        assert!(statuses(&mut engine, &rules, sample(0, 60.0)).is_empty());
+    }
+
+    #[test]
+    fn derived_metrics_are_computed() {
+        let sample = MetricsSample {
+            memory_used: 3,
+            memory_total: 4,
+            mounts: vec![MountSample {
+                mount_point: "/data".into(),
+                used_bytes: 750,
+                total_bytes: 1000,
+            }],
+            ..MetricsSample::default()
+        };
+        let values = sample.named_values();
+        let value = |metric| metric_value(&sample, &values, metric);
+        assert_eq!(value("memory.used_percent"), Some(75.0));
+        assert_eq!(value("disk.free[/data]"), Some(250.0));
+        assert_eq!(value("disk.free_percent[/data]"), Some(25.0));
+        assert_eq!(value("disk.free[/missing]"), None);
+        assert_eq!(value("memory.used"), Some(3.0));
+    }
+
+    #[test]
+    fn clear_threshold_must_not_cross() {
+        let mut above = rule(Comparison::Above, 90.0, 0);
+        above.clear_threshold = Some(80.0);
+        assert!(above.validate().is_ok());
+        above.clear_threshold = Some(95.0);
+        assert!(above.validate().is_err());
+
+        let mut below = rule(Comparison::Below, 10.0, 0);
+        below.clear_threshold = Some(5.0);
+        assert!(below.validate().is_err());
+        below.threshold = f64::NAN;
+        below.clear_threshold = None;
+        assert!(below.validate().is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Alerting: rules evaluated against the sampled metrics and the tailed log
+//! lines, their state changes and the persisted history of those changes.
+
+pub mod logs;
+pub mod metric;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::log_entry::LogEntry,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+use ts_rs::TS;
+
+/// File in the app data directory the alert rules are saved to.
+pub const ALERT_RULES_FILE: &str = "alert_rules.json";
+/// File in the app data directory the log alert rules are saved to.
+pub const LOG_ALERT_RULES_FILE: &str = "log_alert_rules.json";
+/// File in the app data directory the history is appended to, one JSON
+/// record per line.
+pub const ALERT_HISTORY_FILE: &str = "alert_history.jsonl";
+/// Records kept in memory and after compacting the history file.
+pub const MAX_ALERT_HISTORY: usize = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    /// The condition holds but not yet for the rule's duration.
+    Pending,
+    Firing,
+    /// A firing alert whose condition cleared.
+    Resolved,
+}
+
+/// One state change of an alert.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct AlertRecord {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub status: AlertStatus,
+    pub message: String,
+    /// The metric value that caused the change, or the number of matching
+    /// lines for a log alert.
+    pub value: Option<f64>,
+    #[ts(type = "number")]
+    pub at_ms: i64,
+    /// The log lines that matched, for log alerts.
+    #[serde(default)]
+    pub entries: Vec<LogEntry>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// The latest [`AlertRecord`]s, mirrored to an append-only file when one is
+/// given.
+///
+/// ```
+/// use app_lib::alerts::{AlertHistory, AlertRecord, AlertStatus};
+///
+/// let dir = std::env::temp_dir().join(format!("alert-history-doctest-{}", std::process::id()));
+/// let path = dir.join("alert_history.jsonl");
+/// let record = |at_ms, status| AlertRecord {
+///     rule_id: "cpu".into(),
+///     rule_name: "CPU busy".into(),
+///     status,
+///     message: "cpu.usage above 90".into(),
+///     value: Some(97.5),
+///     at_ms,
+///     entries: Vec::new(),
+/// };
+///
+/// let mut history = AlertHistory::open(path.clone());
+/// history.push(record(1_000, AlertStatus::Firing)).unwrap();
+/// history.push(record(2_000, AlertStatus::Resolved)).unwrap();
+///
+/// let reopened = AlertHistory::open(path);
+/// let recent = reopened.recent(10);
+/// assert_eq!(recent.len(), 2);
+/// assert_eq!(recent[0].status, AlertStatus::Resolved);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct AlertHistory {
+    path: Option<PathBuf>,
+    records: VecDeque<AlertRecord>,
+    /// Lines in the file, compacted once it holds twice the kept records.
+    lines: usize,
+}
+
+impl AlertHistory {
+    /// History that is lost when the app stops.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            records: VecDeque::new(),
+            lines: 0,
+        }
+    }
+
+    /// Loads the last [`MAX_ALERT_HISTORY`] records of `path`, skipping lines
+    /// that do not parse.
+    pub fn open(path: PathBuf) -> Self {
+        let mut records = VecDeque::new();
+        let mut lines = 0;
+        if let Ok(file) = File::open(&path) {
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                lines += 1;
+                match serde_json::from_str(&line) {
+                    Ok(record) => records.push_back(record),
+                    Err(e) => log::warn!("Skipping alert history line: {}", e),
+                }
+                if records.len() > MAX_ALERT_HISTORY {
+                    records.pop_front();
+                }
+            }
+        }
+        Self {
+            path: Some(path),
+            records,
+            lines,
+        }
+    }
+
+    pub fn push(&mut self, record: AlertRecord) -> AppResult<()> {
+        self.records.push_back(record);
+        if self.records.len() > MAX_ALERT_HISTORY {
+            self.records.pop_front();
+        }
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if self.lines >= 2 * MAX_ALERT_HISTORY {
+            return self.compact();
+        }
+        let line = to_line(self.records.back().expect("just pushed"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                AppError::io_context(format!("Failed to create {}", dir.display()), e)
+            })?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| AppError::io_context(format!("Failed to write {}", path.display()), e))?;
+        self.lines += 1;
+        Ok(())
+    }
+
+    /// Up to `limit` records, newest first.
+    pub fn recent(&self, limit: usize) -> Vec<AlertRecord> {
+        self.records.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Rewrites the file with only the records kept in memory.
+    fn compact(&mut self) -> AppResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut content = String::new();
+        for record in &self.records {
+            content.push_str(&to_line(record)?);
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| {
+                AppError::io_context(format!("Failed to compact {}", path.display()), e)
+            })?;
+        self.lines = self.records.len();
+        Ok(())
+    }
+}
+
+fn to_line(record: &AlertRecord) -> AppResult<String> {
+    serde_json::to_string(record)
+        .map(|json| json + "\n")
+        .map_err(|e| AppError::Parse(format!("Failed to serialize alert: {}", e)))
+}
@@ -0,0 +1,13 @@
+//! The headless mode on its own, built without Tauri so it runs on servers
+//! without a display or the GTK/WebKit libraries, and keeps its console on
+//! Windows: `cargo build --release --no-default-features --bin app-cli`.
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    app_lib::cli::run(&args).unwrap_or_else(|| {
+        println!("{}", app_lib::cli::args::USAGE);
+        ExitCode::from(2)
+    })
+}
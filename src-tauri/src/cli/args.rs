@@ -0,0 +1,306 @@
+use crate::{
+    error::{AppError, AppResult},
+    utils::log_parser::normalize_log_level,
+};
+use regex::Regex;
+use std::time::Duration;
+
+use super::tui::TopOptions;
+
+pub const USAGE: &str = "\
+Usage:
+  app tail [OPTIONS] <FILE>          follow FILE like `tail -f`
+  app --headless [OPTIONS] <FILE>    same as `tail`
+  app grep [OPTIONS] <REGEX> <FILE>  print matching lines of FILE and exit
+  app top [--interval <MS>]          terminal dashboard of CPU, memory, GPU and processes
+  app help                           show this message
+
+Tail/grep options:
+  -l, --level <LEVELS>   only show these levels (comma separated, e.g. ERROR,WARNING)
+  -e, --regex <REGEX>    only show lines matching REGEX
+  -a, --from-start       print the existing content before following
+      --no-follow        exit after printing the existing content
+      --json             print one JSON object per line
+      --no-color         disable colored levels
+
+A FILE of `-` reads standard input until it is closed.
+
+Without arguments `app` starts the desktop app; `app-cli` has only the modes above.";
+
+pub enum Command {
+    Tail(TailOptions),
+    Top(TopOptions),
+    Help,
+}
+
+pub struct TailOptions {
+    pub file: String,
+    pub levels: Vec<String>,
+    pub pattern: Option<Regex>,
+    pub from_start: bool,
+    pub follow: bool,
+    pub json: bool,
+    pub color: bool,
+}
+
+/// Parses the process arguments (without the program name).
+///
+/// Returns `None` when they do not ask for a headless mode, in which case the
+/// desktop app should be started instead.
+pub fn parse(args: &[String]) -> Option<AppResult<Command>> {
+    let (first, rest) = args.split_first()?;
+    match first.as_str() {
+        "tail" | "--headless" => Some(parse_tail(rest, false)),
+        "grep" => Some(parse_tail(rest, true)),
+        "top" => Some(parse_top(rest)),
+        "help" | "--help" | "-h" => Some(Ok(Command::Help)),
+        _ => None,
+    }
+}
+
+fn parse_tail(args: &[String], grep: bool) -> AppResult<Command> {
+    let mut options = TailOptions {
+        file: String::new(),
+        levels: Vec::new(),
+        pattern: None,
+        from_start: grep,
+        follow: !grep,
+        json: false,
+        color: true,
+    };
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-l" | "--level" => {
+                let value = required_value(&mut iter, arg)?;
+                options.levels.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .map(normalize_log_level),
+                );
+            }
+            "-e" | "--regex" => {
+                options.pattern = Some(compile(required_value(&mut iter, arg)?)?);
+            }
+            "-a" | "--from-start" => options.from_start = true,
+            "--no-follow" => {
+                options.from_start = true;
+                options.follow = false;
+            }
+            "--json" => options.json = true,
+            "--no-color" => options.color = false,
+            "-h" | "--help" => return Ok(Command::Help),
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(AppError::InvalidArgument(format!(
+                    "unknown option {}",
+                    flag
+                )));
+            }
+            value => positional.push(value.to_string()),
+        }
+    }
+
+    if grep {
+        if positional.len() != 2 {
+            return Err(AppError::InvalidArgument(
+                "grep expects <REGEX> <FILE>".to_string(),
+            ));
+        }
+        options.file = positional.pop().unwrap_or_default();
+        options.pattern = Some(compile(&positional[0])?);
+    } else {
+        if positional.len() != 1 {
+            return Err(AppError::InvalidArgument(
+                "expected exactly one <FILE>".to_string(),
+            ));
+        }
+        options.file = positional.pop().unwrap_or_default();
+    }
+
+    Ok(Command::Tail(options))
+}
+
+fn parse_top(args: &[String]) -> AppResult<Command> {
+    let mut options = TopOptions {
+        interval: Duration::from_millis(1000),
+    };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-i" | "--interval" => {
+                let value = required_value(&mut iter, arg)?;
+                let ms: u64 = value.parse().map_err(|_| {
+                    AppError::InvalidArgument(format!("invalid interval {:?}", value))
+                })?;
+                options.interval = Duration::from_millis(ms.max(100));
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            other => {
+                return Err(AppError::InvalidArgument(format!(
+                    "unexpected argument {}",
+                    other
+                )));
+            }
+        }
+    }
+
+    Ok(Command::Top(options))
+}
+
+fn required_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> AppResult<&'a String> {
+    iter.next()
+        .ok_or_else(|| AppError::InvalidArgument(format!("{} requires a value", flag)))
+}
+
+fn compile(pattern: &str) -> AppResult<Regex> {
+    Regex::new(pattern).map_err(|e| AppError::Parse(format!("invalid regex {:?}: {}", pattern, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn tail(list: &[&str]) -> TailOptions {
+        match parse(&args(list)) {
+            Some(Ok(Command::Tail(options))) => options,
+            _ => panic!("{:?} is not a tail command", list),
+        }
+    }
+
+    fn error(list: &[&str]) -> AppError {
+        match parse(&args(list)) {
+            Some(Err(e)) => e,
+            _ => panic!("{:?} was accepted", list),
+        }
+    }
+
+    #[test]
+    fn no_arguments_or_unknown_first_argument_start_the_app() {
+        assert!(parse(&[]).is_none());
+        assert!(parse(&args(&["/var/log/syslog"])).is_none());
+    }
+
+    #[test]
+    fn tail_follows_from_the_end_by_default() {
+        let options = tail(&["tail", "app.log"]);
+        assert_eq!(options.file, "app.log");
+        assert!(options.follow);
+        assert!(!options.from_start);
+        assert!(options.color);
+        assert!(options.pattern.is_none());
+        assert_eq!(tail(&["--headless", "app.log"]).file, "app.log");
+    }
+
+    #[test]
+    fn tail_options_are_parsed() {
+        let options = tail(&[
+            "tail",
+            "-l",
+            "error, warn,",
+            "--regex",
+            "disk",
+            "--no-follow",
+            "--json",
+            "--no-color",
+            "app.log",
+        ]);
+        assert_eq!(options.levels, ["ERROR", "WARNING"]);
+        assert!(options.pattern.is_some_and(|p| p.is_match("disk full")));
+        assert!(options.from_start);
+        assert!(!options.follow);
+        assert!(options.json);
+        assert!(!options.color);
+    }
+
+    #[test]
+    fn grep_reads_the_whole_file_once() {
+        let options = tail(&["grep", "time.?out", "app.log"]);
+        assert_eq!(options.file, "app.log");
+        assert!(options.from_start);
+        assert!(!options.follow);
+        assert!(options.pattern.is_some_and(|p| p.is_match("timeout")));
+    }
+
+    #[test]
+    fn help_wins_over_other_arguments() {
+        for list in [
+            &["help"][..],
+            &["-h"],
+            &["tail", "--help"],
+            &["grep", "-h", "x"],
+        ] {
+            assert!(matches!(parse(&args(list)), Some(Ok(Command::Help))));
+        }
+    }
+
+    #[test]
+    fn invalid_tail_arguments_are_rejected() {
+        assert!(matches!(error(&["tail"]), AppError::InvalidArgument(_)));
+        assert!(matches!(
+            error(&["tail", "a", "b"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            error(&["tail", "--bogus", "a"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            error(&["tail", "a", "--level"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            error(&["grep", "x"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(error(&["grep", "(", "a"]), AppError::Parse(_)));
+    }
+
+    #[test]
+    fn a_lone_dash_is_standard_input_not_an_option() {
+        // `tail` reads stdin for it instead of opening a file named `-`.
+        assert_eq!(tail(&["tail", "-l", "error", "-"]).file, "-");
+        assert_eq!(tail(&["grep", "oom", "-"]).file, "-");
+    }
+
+    #[test]
+    fn top_interval_is_parsed_and_floored() {
+        let interval = |list: &[&str]| match parse(&args(list)) {
+            Some(Ok(Command::Top(options))) => options.interval,
+            _ => panic!("{:?} is not a top command", list),
+        };
+        assert_eq!(interval(&["top"]), Duration::from_millis(1000));
+        assert_eq!(interval(&["top", "-i", "250"]), Duration::from_millis(250));
+        assert_eq!(
+            interval(&["top", "--interval", "5"]),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn invalid_top_arguments_are_rejected() {
+        assert!(matches!(
+            error(&["top", "-i", "fast"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            error(&["top", "-i"]),
+            AppError::InvalidArgument(_)
+        ));
+        assert!(matches!(
+            error(&["top", "app.log"]),
+            AppError::InvalidArgument(_)
+        ));
+    }
+}
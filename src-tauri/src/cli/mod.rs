@@ -0,0 +1,105 @@
+//! Headless mode: tails and filters log files and shows a metrics dashboard in
+//! a terminal, reusing the parsing and collection code of the desktop app.
+
+pub mod args;
+pub mod output;
+pub mod tui;
+
+use crate::{
+    error::AppResult,
+    models::events::BackendEvent,
+    monitoring::file_monitor::{
+        decode_entry, get_file_size, process_new_data, run_monitoring_loop, FileMonitorState,
+    },
+    state::events::EventSink,
+};
+use args::{Command, TailOptions, USAGE};
+use chrono::Local;
+use output::Printer;
+use std::{
+    fs::File,
+    io::{self, BufRead, IsTerminal},
+    path::Path,
+    process::ExitCode,
+    sync::{Arc, Mutex},
+};
+
+/// Runs the headless mode if `args` ask for it.
+///
+/// Returns `None` when the desktop app should be started instead.
+pub fn run(args: &[String]) -> Option<ExitCode> {
+    let result = match args::parse(args)? {
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Ok(Command::Tail(options)) => tail(options),
+        Ok(Command::Top(options)) => tui::run(options),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return Some(ExitCode::from(2));
+        }
+    };
+
+    Some(match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    })
+}
+
+fn tail(options: TailOptions) -> AppResult<()> {
+    let color = options.color && !options.json && std::io::stdout().is_terminal();
+    let printer = Printer::new(&options, color);
+    if options.file == "-" {
+        return tail_stdin(&printer);
+    }
+
+    let mut file = File::open(&options.file)?;
+    let state = Arc::new(Mutex::new(FileMonitorState {
+        is_running: true,
+        current_file: Some(options.file.clone()),
+        current_offset: 0,
+        initial_hash: None,
+        last_modified: None,
+        attach_writers: false,
+    }));
+
+    let mut offset = 0;
+    if options.from_start {
+        process_new_data(&mut file, 0, &state, &printer, &mut offset)?;
+    } else {
+        offset = get_file_size(Path::new(&options.file));
+    }
+
+    if options.follow {
+        run_monitoring_loop(options.file, state, printer, offset);
+    }
+    Ok(())
+}
+
+/// `-` reads standard input until it is closed, printing each line as it
+/// arrives, e.g. `journalctl -f | app-cli tail -l error -`.
+fn tail_stdin(printer: &Printer) -> AppResult<()> {
+    let mut stdin = io::stdin().lock();
+    let mut line = Vec::new();
+    let mut had_errors = false;
+    loop {
+        line.clear();
+        if stdin.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        if let Some(entry) = decode_entry(&line, &now, &mut had_errors) {
+            printer.send(BackendEvent::LogsBatch {
+                entries: vec![entry],
+            });
+        }
+    }
+    if had_errors {
+        log::warn!("Encoding issues detected");
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use super::args::TailOptions;
+use crate::{
+    models::{events::BackendEvent, log_entry::LogEntry},
+    state::events::EventSink,
+};
+use crossterm::style::{Color, Stylize};
+use regex::Regex;
+use std::io::{self, Write};
+
+/// Prints parsed log entries to stdout, either as colored text or JSON lines.
+pub struct Printer {
+    levels: Vec<String>,
+    pattern: Option<Regex>,
+    json: bool,
+    color: bool,
+}
+
+impl Printer {
+    pub fn new(options: &TailOptions, color: bool) -> Self {
+        Self {
+            levels: options.levels.clone(),
+            pattern: options.pattern.clone(),
+            json: options.json,
+            color,
+        }
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.levels.is_empty() && !self.levels.contains(&entry.level) {
+            return false;
+        }
+        self.pattern
+            .as_ref()
+            .map_or(true, |re| re.is_match(&entry.message))
+    }
+
+    fn print_entries(&self, entries: &[LogEntry]) {
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+
+        for entry in entries.iter().filter(|e| self.matches(e)) {
+            let result = if self.json {
+                match serde_json::to_string(entry) {
+                    Ok(line) => writeln!(out, "{}", line),
+                    Err(e) => {
+                        eprintln!("failed to serialize entry: {}", e);
+                        continue;
+                    }
+                }
+            } else if self.color {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    entry.timestamp.as_str().dark_grey(),
+                    format!("{:<7}", entry.level)
+                        .with(level_color(&entry.level))
+                        .bold(),
+                    entry.message
+                )
+            } else {
+                writeln!(
+                    out,
+                    "{} {:<7} {}",
+                    entry.timestamp, entry.level, entry.message
+                )
+            };
+
+            if let Err(e) = result {
+                if e.kind() == io::ErrorKind::BrokenPipe {
+                    std::process::exit(0);
+                }
+                eprintln!("failed to write output: {}", e);
+            }
+        }
+        let _ = out.flush();
+    }
+}
+
+impl EventSink for Printer {
+    fn send(&self, event: BackendEvent) {
+        match event {
+            BackendEvent::LogsBatch { entries } => self.print_entries(&entries),
+            BackendEvent::LoadingError { message, .. }
+            | BackendEvent::MonitoringError { message, .. } => eprintln!("error: {}", message),
+            BackendEvent::FileCleared | BackendEvent::FileTruncated => {
+                eprintln!("-- file truncated, reading from the start --")
+            }
+            _ => {}
+        }
+    }
+}
+
+fn level_color(level: &str) -> Color {
+    match level {
+        "ERROR" => Color::Red,
+        "WARNING" => Color::Yellow,
+        "INFO" => Color::Green,
+        "DEBUG" => Color::Cyan,
+        "TRACE" => Color::DarkGrey,
+        _ => Color::Reset,
+    }
+}
@@ -0,0 +1,506 @@
+use crate::{
+    error::AppResult,
+    models::system_info::{LocalProcessInfo, PressureStat, SensorKind, SystemInfo},
+    monitoring::system_metrics,
+    state::system::SystemMonitorState,
+    utils::sync::LockExt,
+};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    cmp::Ordering,
+    io::{self, Stdout, Write},
+    time::{Duration, Instant},
+};
+
+pub struct TopOptions {
+    pub interval: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortKey {
+    Cpu,
+    Memory,
+    Pid,
+    Name,
+}
+
+struct Dashboard {
+    info: Option<SystemInfo>,
+    sort: SortKey,
+    selected: usize,
+    selected_pid: Option<u32>,
+    scroll: usize,
+    show_all: bool,
+}
+
+/// Restores the terminal even if drawing fails or panics.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter(out: &mut Stdout) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Full-screen dashboard over the same [`SystemInfo`] the desktop app shows.
+pub fn run(options: TopOptions) -> AppResult<()> {
+    let state = SystemMonitorState::new();
+    let mut out = io::stdout();
+    let _guard = TerminalGuard::enter(&mut out)?;
+
+    let mut dashboard = Dashboard {
+        info: None,
+        sort: SortKey::Cpu,
+        selected: 0,
+        selected_pid: None,
+        scroll: 0,
+        show_all: false,
+    };
+
+    system_metrics::refresh(&state);
+    let mut next_sample = Instant::now() + system_metrics::CPU_SAMPLE_INTERVAL;
+
+    loop {
+        let now = Instant::now();
+        if now >= next_sample {
+            system_metrics::refresh(&state);
+            let mut info = system_metrics::collect(&state, dashboard.selected_pid);
+            if !dashboard.show_all {
+                let filter = state.process_filter.lock_or_recover();
+                info.processes.retain(|p| !filter.hides(p));
+            }
+            sort_processes(&mut info.processes, dashboard.sort);
+            dashboard.info = Some(info);
+            dashboard.sync_selection();
+            draw(&mut out, &mut dashboard)?;
+            next_sample = Instant::now() + options.interval;
+        }
+
+        let timeout = next_sample.saturating_duration_since(Instant::now());
+        if !event::poll(timeout)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Up => dashboard.move_selection(-1),
+                KeyCode::Down => dashboard.move_selection(1),
+                KeyCode::PageUp => dashboard.move_selection(-10),
+                KeyCode::PageDown => dashboard.move_selection(10),
+                KeyCode::Char('c') => dashboard.set_sort(SortKey::Cpu),
+                KeyCode::Char('m') => dashboard.set_sort(SortKey::Memory),
+                KeyCode::Char('p') => dashboard.set_sort(SortKey::Pid),
+                KeyCode::Char('n') => dashboard.set_sort(SortKey::Name),
+                KeyCode::Char('a') => {
+                    dashboard.show_all = !dashboard.show_all;
+                    next_sample = Instant::now();
+                    continue;
+                }
+                _ => continue,
+            },
+            Event::Resize(_, _) => {}
+            _ => continue,
+        }
+        draw(&mut out, &mut dashboard)?;
+    }
+
+    Ok(())
+}
+
+impl Dashboard {
+    fn processes(&self) -> &[LocalProcessInfo] {
+        self.info.as_ref().map_or(&[], |i| &i.processes)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.processes().len();
+        if len == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(delta).min(len - 1);
+        self.selected_pid = self.processes().get(self.selected).map(|p| p.pid);
+    }
+
+    fn set_sort(&mut self, sort: SortKey) {
+        self.sort = sort;
+        if let Some(info) = self.info.as_mut() {
+            sort_processes(&mut info.processes, sort);
+        }
+        self.sync_selection();
+    }
+
+    /// Keeps the cursor on the selected pid after the list was re-sorted or
+    /// refreshed; falls back to the same row if the process exited.
+    fn sync_selection(&mut self) {
+        let len = self.processes().len();
+        if let Some(index) = self
+            .selected_pid
+            .and_then(|pid| self.processes().iter().position(|p| p.pid == pid))
+        {
+            self.selected = index;
+        } else {
+            self.selected = self.selected.min(len.saturating_sub(1));
+            self.selected_pid = self.processes().get(self.selected).map(|p| p.pid);
+        }
+    }
+}
+
+fn sort_processes(processes: &mut [LocalProcessInfo], sort: SortKey) {
+    processes.sort_by(|a, b| match sort {
+        SortKey::Cpu => b
+            .cpu_usage
+            .partial_cmp(&a.cpu_usage)
+            .unwrap_or(Ordering::Equal),
+        SortKey::Memory => b.memory.cmp(&a.memory),
+        SortKey::Pid => a.pid.cmp(&b.pid),
+        SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+}
+
+fn draw(out: &mut Stdout, dashboard: &mut Dashboard) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = width as usize;
+    let height = height as usize;
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+
+    let Some(info) = dashboard.info.as_ref() else {
+        queue!(out, Print("Collecting metrics..."))?;
+        return out.flush();
+    };
+
+    let mut row: u16 = 0;
+    let mut line = |out: &mut Stdout, text: String| -> io::Result<()> {
+        queue!(out, MoveTo(0, row), Print(truncate(&text, width)))?;
+        row += 1;
+        Ok(())
+    };
+
+    line(
+        out,
+        format!(
+            "{}   [q] quit  [↑↓] select  sort: [c]pu [m]em [p]id [n]ame  [a]ll processes",
+            info.cpu_name.as_deref().unwrap_or("Unknown CPU")
+        ),
+    )?;
+    let temp = info
+        .cpu_temp
+        .map_or_else(|| "n/a".to_string(), |t| format!("{:.1}°C", t));
+    line(
+        out,
+        format!(
+            "CPU {} {:5.1}%  temp {}",
+            bar(info.cpu_usage as f64 / 100.0, 30),
+            info.cpu_usage,
+            temp
+        ),
+    )?;
+    let load = info.load_average.as_ref().map_or_else(
+        || "n/a".to_string(),
+        |l| format!("{:.2} {:.2} {:.2}", l.one, l.five, l.fifteen),
+    );
+    line(
+        out,
+        format!(
+            "    {} physical / {} logical @ {} MHz  load {}",
+            info.physical_core_count
+                .map_or_else(|| "?".to_string(), |c| c.to_string()),
+            info.logical_core_count,
+            info.cpu_frequency_mhz,
+            load
+        ),
+    )?;
+    let cores: Vec<String> = info
+        .cpu_cores
+        .iter()
+        .map(|c| format!("{:>3.0}%", c.usage))
+        .collect();
+    line(out, format!("    {}", cores.join(" ")))?;
+    let mem_ratio = if info.total_memory > 0 {
+        info.used_memory as f64 / info.total_memory as f64
+    } else {
+        0.0
+    };
+    line(
+        out,
+        format!(
+            "MEM {} {} / {}  avail {}{}",
+            bar(mem_ratio, 30),
+            format_bytes(info.used_memory),
+            format_bytes(info.total_memory),
+            format_bytes(info.memory.available),
+            info.memory
+                .cached
+                .map_or_else(String::new, |c| format!("  cache {}", format_bytes(c)))
+        ),
+    )?;
+    let swap_ratio = if info.memory.swap_total > 0 {
+        info.memory.swap_used as f64 / info.memory.swap_total as f64
+    } else {
+        0.0
+    };
+    let psi = info.pressure.as_ref().map_or_else(String::new, |p| {
+        let some = |stat: &Option<PressureStat>| {
+            stat.as_ref()
+                .map_or_else(|| "n/a".to_string(), |s| format!("{:.1}%", s.some.avg10))
+        };
+        format!(
+            "  psi cpu {} mem {} io {}",
+            some(&p.cpu),
+            some(&p.memory),
+            some(&p.io)
+        )
+    });
+    line(
+        out,
+        format!(
+            "SWP {} {} / {}{}",
+            bar(swap_ratio, 30),
+            format_bytes(info.memory.swap_used),
+            format_bytes(info.memory.swap_total),
+            psi
+        ),
+    )?;
+    let temps: Vec<String> = info
+        .sensors
+        .iter()
+        .filter(|r| r.kind == SensorKind::Temperature)
+        .map(|r| {
+            let hot =
+                r.critical.is_some_and(|c| r.value >= c) || r.high.is_some_and(|h| r.value >= h);
+            format!(
+                "{} {:.0}°C{}",
+                r.label.as_deref().unwrap_or(&r.chip),
+                r.value,
+                if hot { "!" } else { "" }
+            )
+        })
+        .collect();
+    if !temps.is_empty() {
+        line(out, format!("TMP {}", temps.join("  ")))?;
+    }
+    if info.gpus.is_empty() {
+        line(out, "GPU n/a".to_string())?;
+    }
+    for gpu in &info.gpus {
+        let na = || "n/a".to_string();
+        let memory = match (gpu.memory_used, gpu.memory_total) {
+            (Some(used), Some(total)) => {
+                format!("{} / {}", format_bytes(used), format_bytes(total))
+            }
+            _ => na(),
+        };
+        line(
+            out,
+            format!(
+                "GPU{} {}  {}  temp {}  mem {}  power {}{}",
+                gpu.index,
+                gpu.name,
+                gpu.utilization.map_or_else(na, |u| format!("{}%", u)),
+                gpu.temperature.map_or_else(na, |t| format!("{}°C", t)),
+                memory,
+                gpu.power_draw_mw
+                    .map_or_else(na, |p| format!("{:.0} W", p as f64 / 1000.0)),
+                if gpu.throttle_reasons.is_empty() {
+                    String::new()
+                } else {
+                    format!("  throttle {}", gpu.throttle_reasons.join(","))
+                }
+            ),
+        )?;
+    }
+    if let Some(power) = &info.power {
+        let mut parts = Vec::new();
+        if let Some(battery) = &power.battery {
+            let remaining = battery.time_remaining_secs.map_or_else(String::new, |s| {
+                format!(" {}h{:02}m left", s / 3600, s / 60 % 60)
+            });
+            parts.push(format!(
+                "battery {:.0}% {}{}",
+                battery.charge_percent, battery.state, remaining
+            ));
+        }
+        if let Some(ac) = power.ac_online {
+            parts.push(format!("AC {}", if ac { "on" } else { "off" }));
+        }
+        for zone in power.rapl.iter().filter(|z| z.is_package) {
+            if let Some(watts) = zone.power_watts {
+                parts.push(format!("{} {:.1} W", zone.name, watts));
+            }
+        }
+        line(out, format!("PWR {}", parts.join("  ")))?;
+    }
+    let physical = || info.storage.devices.iter().filter(|d| d.physical);
+    let mounts: Vec<String> = info
+        .storage
+        .mounts
+        .iter()
+        .filter(|m| m.total_bytes > 0)
+        .map(|m| {
+            format!(
+                "{} {:.0}%",
+                m.mount_point,
+                m.used_bytes as f64 * 100.0 / m.total_bytes as f64
+            )
+        })
+        .collect();
+    line(
+        out,
+        format!(
+            "DSK r {}/s  w {}/s  {}",
+            format_bytes(physical().map(|d| d.read_bytes_per_sec).sum::<f64>() as u64),
+            format_bytes(physical().map(|d| d.write_bytes_per_sec).sum::<f64>() as u64),
+            mounts.join("  ")
+        ),
+    )?;
+    let external = || info.network.interfaces.iter().filter(|i| !i.is_loopback);
+    let tcp = info
+        .network
+        .tcp_states
+        .as_ref()
+        .map_or_else(String::new, |states| {
+            format!(
+                "  tcp {} established / {} listen",
+                states.get("established").copied().unwrap_or(0),
+                states.get("listen").copied().unwrap_or(0)
+            )
+        });
+    line(
+        out,
+        format!(
+            "NET rx {}/s  tx {}/s{}",
+            format_bytes(external().map(|i| i.rx_bytes_per_sec).sum::<f64>() as u64),
+            format_bytes(external().map(|i| i.tx_bytes_per_sec).sum::<f64>() as u64),
+            tcp
+        ),
+    )?;
+    row += 1;
+
+    let marker = |key: SortKey| if dashboard.sort == key { "▼" } else { " " };
+    queue!(
+        out,
+        MoveTo(0, row),
+        PrintStyledContent(
+            truncate(
+                &format!(
+                    "{:>8}{} {:<32}{} {:>7}{} {:>10}{}",
+                    "PID",
+                    marker(SortKey::Pid),
+                    "NAME",
+                    marker(SortKey::Name),
+                    "CPU%",
+                    marker(SortKey::Cpu),
+                    "MEM",
+                    marker(SortKey::Memory)
+                ),
+                width
+            )
+            .reverse()
+        )
+    )?;
+    row += 1;
+
+    let detail_rows = if info.selected_process.is_some() {
+        4
+    } else {
+        0
+    };
+    let visible = height.saturating_sub(row as usize + detail_rows).max(1);
+    if dashboard.selected < dashboard.scroll {
+        dashboard.scroll = dashboard.selected;
+    } else if dashboard.selected >= dashboard.scroll + visible {
+        dashboard.scroll = dashboard.selected + 1 - visible;
+    }
+
+    for (index, process) in info
+        .processes
+        .iter()
+        .enumerate()
+        .skip(dashboard.scroll)
+        .take(visible)
+    {
+        let text = truncate(
+            &format!(
+                "{:>8}  {:<33} {:>7.1}  {:>10}",
+                process.pid,
+                truncate(&process.name, 33),
+                process.cpu_usage,
+                format_bytes(process.memory)
+            ),
+            width,
+        );
+        queue!(out, MoveTo(0, row))?;
+        if index == dashboard.selected {
+            queue!(out, PrintStyledContent(text.black().on_cyan()))?;
+        } else {
+            queue!(out, Print(text))?;
+        }
+        row += 1;
+    }
+
+    if let Some(detail) = &info.selected_process {
+        row = height.saturating_sub(detail_rows) as u16;
+        let mut line = |out: &mut Stdout, text: String| -> io::Result<()> {
+            queue!(out, MoveTo(0, row), Print(truncate(&text, width)))?;
+            row += 1;
+            Ok(())
+        };
+        line(out, "─".repeat(width))?;
+        line(
+            out,
+            format!(
+                "{} (pid {})  {}  cpu {:.1}%  mem {}",
+                detail.name,
+                detail.pid,
+                detail.status,
+                detail.cpu_usage,
+                format_bytes(detail.memory)
+            ),
+        )?;
+        line(
+            out,
+            format!("exe: {}", detail.exe_path.as_deref().unwrap_or("")),
+        )?;
+        line(
+            out,
+            format!("cmd: {}", detail.command_line.as_deref().unwrap_or("")),
+        )?;
+    }
+
+    out.flush()
+}
+
+fn bar(ratio: f64, width: usize) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(width - filled))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
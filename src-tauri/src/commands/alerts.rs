@@ -0,0 +1,154 @@
+use crate::{
+    alerts::{
+        logs::LogAlertRule,
+        metric::{ActiveAlert, MetricAlertRule},
+        AlertRecord, ALERT_RULES_FILE, LOG_ALERT_RULES_FILE,
+    },
+    error::{AppError, AppResult},
+    state::alerts::{raise_alert, AlertsState},
+    utils::{json_file, sync::LockExt},
+};
+use chrono::Utc;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+#[tauri::command]
+pub fn get_alert_rules(state: State<'_, AlertsState>) -> Vec<MetricAlertRule> {
+    state.metric_rules.lock_or_recover().clone()
+}
+
+/// Adds `rule`, or replaces the rule with the same id, and saves the rules.
+/// Returns the rule with its id assigned.
+#[tauri::command]
+pub fn save_alert_rule(
+    app: AppHandle,
+    state: State<'_, AlertsState>,
+    mut rule: MetricAlertRule,
+) -> AppResult<MetricAlertRule> {
+    rule.validate()?;
+    let mut rules = state.metric_rules.lock_or_recover();
+    match rules
+        .iter_mut()
+        .find(|r| !rule.id.is_empty() && r.id == rule.id)
+    {
+        Some(existing) => *existing = rule.clone(),
+        None => {
+            rule.id = new_rule_id("alert", |id| rules.iter().any(|r| r.id == id));
+            rules.push(rule.clone());
+        }
+    }
+    save_rules(&app, ALERT_RULES_FILE, &rules)?;
+    drop(rules);
+    let resolved = state.metric_engine.lock_or_recover().reset(
+        &rule.id,
+        Utc::now().timestamp_millis(),
+        if rule.enabled {
+            "rule edited"
+        } else {
+            "rule disabled"
+        },
+    );
+    if let Some(record) = resolved {
+        raise_alert(&app, record, rule.notify);
+    }
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_alert_rule(
+    app: AppHandle,
+    state: State<'_, AlertsState>,
+    id: String,
+) -> AppResult<()> {
+    let mut rules = state.metric_rules.lock_or_recover();
+    let Some(index) = rules.iter().position(|r| r.id == id) else {
+        return Err(AppError::NotFound(format!("alert rule {}", id)));
+    };
+    let removed = rules.remove(index);
+    save_rules(&app, ALERT_RULES_FILE, &rules)?;
+    drop(rules);
+    let resolved = state.metric_engine.lock_or_recover().reset(
+        &id,
+        Utc::now().timestamp_millis(),
+        "rule removed",
+    );
+    if let Some(record) = resolved {
+        raise_alert(&app, record, removed.notify);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_log_alert_rules(state: State<'_, AlertsState>) -> Vec<LogAlertRule> {
+    state.log_rules.lock_or_recover().clone()
+}
+
+/// Adds `rule`, or replaces the log alert rule with the same id, and saves
+/// the rules. Returns the rule with its id assigned.
+#[tauri::command]
+pub fn save_log_alert_rule(
+    app: AppHandle,
+    state: State<'_, AlertsState>,
+    mut rule: LogAlertRule,
+) -> AppResult<LogAlertRule> {
+    rule.validate()?;
+    let mut rules = state.log_rules.lock_or_recover();
+    match rules
+        .iter_mut()
+        .find(|r| !rule.id.is_empty() && r.id == rule.id)
+    {
+        Some(existing) => *existing = rule.clone(),
+        None => {
+            rule.id = new_rule_id("log-alert", |id| rules.iter().any(|r| r.id == id));
+            rules.push(rule.clone());
+        }
+    }
+    save_rules(&app, LOG_ALERT_RULES_FILE, &rules)?;
+    state.log_engine.lock_or_recover().reset(&rule.id);
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_log_alert_rule(
+    app: AppHandle,
+    state: State<'_, AlertsState>,
+    id: String,
+) -> AppResult<()> {
+    let mut rules = state.log_rules.lock_or_recover();
+    let before = rules.len();
+    rules.retain(|r| r.id != id);
+    if rules.len() == before {
+        return Err(AppError::NotFound(format!("log alert rule {}", id)));
+    }
+    save_rules(&app, LOG_ALERT_RULES_FILE, &rules)?;
+    state.log_engine.lock_or_recover().reset(&id);
+    Ok(())
+}
+
+/// Alerts that are pending or firing right now.
+#[tauri::command]
+pub fn get_active_alerts(state: State<'_, AlertsState>) -> Vec<ActiveAlert> {
+    state.metric_engine.lock_or_recover().active()
+}
+
+/// The latest alert state changes, newest first (100 by default).
+#[tauri::command]
+pub fn get_alert_history(state: State<'_, AlertsState>, limit: Option<usize>) -> Vec<AlertRecord> {
+    state.history.lock_or_recover().recent(limit.unwrap_or(100))
+}
+
+/// `<prefix>-<now in ms>`, counting up past ids already `taken`.
+fn new_rule_id(prefix: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut millis = Utc::now().timestamp_millis();
+    while taken(&format!("{}-{}", prefix, millis)) {
+        millis += 1;
+    }
+    format!("{}-{}", prefix, millis)
+}
+
+fn save_rules<T: Serialize>(app: &AppHandle, file: &str, rules: &[T]) -> AppResult<()> {
+    if let Ok(dir) = app.path().app_data_dir() {
+        json_file::save(&dir.join(file), &rules)?;
+    }
+    Ok(())
+}
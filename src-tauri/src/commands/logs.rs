@@ -1,11 +1,23 @@
 use crate::{
-    models::log_entry::LogEntry,
-    monitoring::file_monitor::{get_file_size, run_monitoring_loop, MonitoringState},
-    state::logs::*,
+    error::{AppError, AppResult},
+    models::{
+        command::{CommandSpec, CommandStatus},
+        events::BackendEvent,
+        log_entry::LogEntry,
+        process::FileWriter,
+    },
+    monitoring::{
+        command_monitor::{self, CommandMonitorState},
+        file_monitor::{get_file_size, run_monitoring_loop, FileMonitorState, MonitoringState},
+        system_metrics,
+    },
+    process::file_writers::find_writers,
+    state::{events::emit_backend_event, logs::*, system::SystemMonitorState},
     utils::{
         encoding::detect_encoding,
         hashing::hash_file_start,
         log_parser::{count_lines, extract_log_level},
+        sync::LockExt,
     },
 };
 use chrono::Local;
@@ -13,11 +25,11 @@ use std::{
     fs::File,
     io::{BufRead, BufReader, Seek, SeekFrom},
     path::Path,
-    sync::Arc,
+    sync::{Arc, Mutex},
     thread,
     time::SystemTime,
 };
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, State};
 use tokio_util::sync::CancellationToken;
 
 const BATCH_SIZE: usize = 568;
@@ -28,14 +40,11 @@ pub fn start_file_loading(
     loading_state: State<'_, Arc<LoadingState>>,
     reload_all: bool,
     file_path: String,
-) -> Result<(), String> {
-    {
-        let mut fl = loading_state.is_loading.lock().unwrap();
-        *fl = true;
-    }
+) -> AppResult<()> {
+    *loading_state.is_loading.lock_or_recover() = true;
 
     let start_offset = {
-        let mut mon = state.state.lock().unwrap();
+        let mut mon = state.state.lock_or_recover();
 
         if reload_all || mon.current_file.as_deref() != Some(&file_path) {
             mon.current_file = Some(file_path.clone());
@@ -51,60 +60,62 @@ pub fn start_file_loading(
         start_offset
     );
     let cancel_token = CancellationToken::new();
-    {
-        let mut tok = loading_state.cancel_token.lock().unwrap();
-        *tok = Some(cancel_token.clone());
-    }
+    *loading_state.cancel_token.lock_or_recover() = Some(cancel_token.clone());
     let app = app_handle.clone();
     let mon_state = state.state.clone();
     let loading_st = loading_state.inner().clone();
 
     std::thread::spawn(move || {
-        let file = match File::open(&file_path) {
-            Ok(f) => f,
-            Err(e) => {
-                let _ = app.emit("loading_error", e.to_string());
-                let mut fl = loading_st.is_loading.lock().unwrap();
-                *fl = false;
-                return;
-            }
-        };
-        let metadata = match file.metadata() {
-            Ok(m) => m,
-            Err(e) => {
-                let _ = app.emit(
-                    "loading_error",
-                    format!("Failed to get file metadata: {}", e),
-                );
-                let mut fl = loading_st.is_loading.lock().unwrap();
-                *fl = false;
-                return;
+        let result = load_file(
+            &app,
+            &mon_state,
+            &cancel_token,
+            &file_path,
+            start_offset,
+            reload_all,
+        );
+        match result {
+            Ok(()) => {}
+            Err(AppError::Cancelled) => {
+                log::info!("Loading of {} cancelled", file_path);
+                emit_backend_event(&app, BackendEvent::LoadingCancelled);
             }
-        };
-        let file_size = metadata.len();
-        let file_modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-        let current_hash = match hash_file_start(&file_path, 1024) {
-            Ok(h) => h,
             Err(e) => {
-                let _ = app.emit("loading_error", format!("Failed to hash file: {}", e));
-                let mut fl = loading_st.is_loading.lock().unwrap();
-                *fl = false;
-                return;
+                log::error!("Loading of {} failed: {}", file_path, e);
+                emit_backend_event(&app, BackendEvent::loading_error(&e));
             }
-        };
+        }
+        *loading_st.is_loading.lock_or_recover() = false;
+    });
+
+    Ok(())
+}
 
-        let latest_hash = match hash_file_start(&file_path, 1024) {
-            Ok(h) => h,
-            Err(_) => current_hash,
-        };
+fn load_file(
+    app: &AppHandle,
+    mon_state: &Mutex<FileMonitorState>,
+    cancel_token: &CancellationToken,
+    file_path: &str,
+    start_offset: u64,
+    reload_all: bool,
+) -> AppResult<()> {
+    let file = File::open(file_path).map_err(|e| AppError::io_context("Failed to open file", e))?;
+    let metadata = file
+        .metadata()
+        .map_err(|e| AppError::io_context("Failed to get file metadata", e))?;
+    let file_size = metadata.len();
+    let file_modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let current_hash = hash_file_start(file_path, 1024)
+        .map_err(|e| AppError::io_context("Failed to hash file", e))?;
 
-        let mut mon = mon_state.lock().unwrap();
-        let hash_changed = mon.initial_hash.map_or(true, |h| h != latest_hash);
-        let was_truncated = mon.current_offset > file_size;
+    let latest_hash = hash_file_start(file_path, 1024).unwrap_or(current_hash);
 
-        let force_reload = was_truncated || hash_changed;
+    {
+        let mut mon = mon_state.lock_or_recover();
+        let hash_changed = mon.initial_hash != Some(latest_hash);
+        let was_truncated = mon.current_offset > file_size;
 
-        if force_reload {
+        if was_truncated || hash_changed {
             log::info!(
                 "Reload triggered: truncated = {}, hash_changed = {}",
                 was_truncated,
@@ -112,170 +123,139 @@ pub fn start_file_loading(
             );
             mon.current_offset = 0;
             mon.initial_hash = Some(latest_hash);
-            mon.last_modified = Some(file_modified);
             drop(mon);
 
-            let _ = app.emit("file_truncated", ());
+            emit_backend_event(app, BackendEvent::FileTruncated);
         }
-
-        if force_reload {
-            let _ = app.emit("file_truncated", ());
+    }
+    {
+        let mut mon = mon_state.lock_or_recover();
+        mon.last_modified = Some(file_modified);
+        if mon.current_offset > file_size {
+            log::info!(
+                "Detected file truncation: offset {} > filesize {}, resetting to 0",
+                mon.current_offset,
+                file_size
+            );
+            mon.current_offset = 0;
         }
-        {
-            let mut mon = mon_state.lock().unwrap();
-            mon.last_modified = Some(file_modified);
+    }
+    let mut reader = BufReader::new(file);
+    reader
+        .seek(SeekFrom::Start(start_offset))
+        .map_err(|e| AppError::io_context("Seek failed", e))?;
+    if file_size == 0 {
+        emit_backend_event(
+            app,
+            BackendEvent::LoadProgress {
+                current: 0,
+                total: 0,
+            },
+        );
+        emit_backend_event(app, BackendEvent::LoadingSuccess);
+        return Ok(());
+    }
+    let total = if start_offset == 0 {
+        count_lines(file_path).unwrap_or(0)
+    } else {
+        0
+    };
+    emit_backend_event(app, BackendEvent::LoadProgress { current: 0, total });
+
+    let mut buf = Vec::new();
+    let mut count = 0;
+    let mut batch = Vec::new();
+
+    loop {
+        if cancel_token.is_cancelled() {
+            return Err(AppError::Cancelled);
         }
-        {
-            let mut mon = mon_state.lock().unwrap();
-            if mon.current_offset > file_size {
-                log::info!(
-                    "Detected file truncation: offset {} > filesize {}, resetting to 0",
-                    mon.current_offset,
-                    file_size
-                );
+
+        buf.clear();
+        if !reload_all {
+            let latest_hash = hash_file_start(file_path, 1024).unwrap_or(current_hash);
+
+            let mut mon = mon_state.lock_or_recover();
+            if mon.initial_hash != Some(latest_hash) {
+                log::info!("Detected inline change, forcing reload");
+                mon.initial_hash = Some(latest_hash);
                 mon.current_offset = 0;
+                drop(mon);
+                emit_backend_event(app, BackendEvent::FileTruncated);
+                return Ok(());
             }
         }
-        let mut reader = BufReader::new(file);
-        if let Err(e) = reader.seek(SeekFrom::Start(start_offset)) {
-            let _ = app.emit("loading_error", e.to_string());
-            let mut fl = loading_st.is_loading.lock().unwrap();
-            *fl = false;
-            return;
+        let n = reader
+            .read_until(b'\n', &mut buf)
+            .map_err(|e| AppError::io_context("Read failed", e))?;
+        if n == 0 {
+            break;
         }
-        if file_size == 0 {
-            let _ = app.emit(
-                "load_progress",
-                LoadProgress {
-                    current: 0,
-                    total: 0,
+        count += 1;
+        mon_state.lock_or_recover().current_offset += n as u64;
+        if count % BATCH_SIZE == 0 || count == total {
+            emit_backend_event(
+                app,
+                BackendEvent::LoadProgress {
+                    current: count,
+                    total,
                 },
             );
-            let _ = app.emit("loading_success", ());
-            let mut fl = loading_st.is_loading.lock().unwrap();
-            *fl = false;
-            return;
         }
-        let total = if start_offset == 0 {
-            count_lines(&file_path).unwrap_or(0)
-        } else {
-            0
-        };
-        let _ = app.emit("load_progress", LoadProgress { current: 0, total });
-
-        let mut buf = Vec::new();
-        let mut count = 0;
-        let mut batch = Vec::new();
-
-        loop {
-            if cancel_token.is_cancelled() {
-                let _ = app.emit("loading_cancelled", ());
-                let mut fl = loading_st.is_loading.lock().unwrap();
-                *fl = false;
-                return;
-            }
-
-            buf.clear();
-            if !reload_all {
-                let latest_hash = match hash_file_start(&file_path, 1024) {
-                    Ok(h) => h,
-                    Err(_) => current_hash,
-                };
-
-                let mut mon = mon_state.lock().unwrap();
-                if mon.initial_hash != Some(latest_hash) {
-                    log::info!("Detected inline change, forcing reload");
-                    mon.initial_hash = Some(latest_hash);
-                    mon.current_offset = 0;
-                    let _ = app.emit("file_truncated", ());
-                    drop(mon);
-                    let mut fl = loading_st.is_loading.lock().unwrap();
-                    *fl = false;
-                    return;
-                }
-            }
-            match reader.read_until(b'\n', &mut buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    count += 1;
-                    {
-                        let mut mon = mon_state.lock().unwrap();
-                        mon.current_offset += n as u64;
-                    }
-                    if count % BATCH_SIZE == 0 || count == total {
-                        let _ = app.emit(
-                            "load_progress",
-                            LoadProgress {
-                                current: count,
-                                total,
-                            },
-                        );
-                    }
-                    let (cow, _, err) = detect_encoding(&buf).decode(&buf);
-                    if err {
-                        log::warn!("Invalid chars");
-                    }
-                    let line = cow.into_owned();
-                    if !line.trim().is_empty() {
-                        let (lvl, msg) = extract_log_level(line.trim());
-                        let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-                        batch.push(LogEntry {
-                            timestamp: now,
-                            level: lvl,
-                            message: msg.to_string(),
-                        });
-                    }
-                    if batch.len() >= BATCH_SIZE {
-                        let _ = app.emit("new_logs_batch", batch.clone());
-                        batch.clear();
-                    }
-                }
-                Err(e) => {
-                    let _ = app.emit("loading_error", e.to_string());
-                    let mut fl = loading_st.is_loading.lock().unwrap();
-                    *fl = false;
-                    return;
-                }
-            }
+        let (cow, _, err) = detect_encoding(&buf).decode(&buf);
+        if err {
+            log::warn!("Invalid chars");
         }
-        if !batch.is_empty() {
-            let _ = app.emit("new_logs_batch", batch);
+        let line = cow.into_owned();
+        if !line.trim().is_empty() {
+            let (lvl, msg) = extract_log_level(line.trim());
+            let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            batch.push(LogEntry {
+                timestamp: now,
+                level: lvl,
+                message: msg.to_string(),
+                stream: None,
+            });
         }
-        if count == 0 && start_offset > 0 {
-            log::info!("No new lines detected since last load");
-            let _ = app.emit("loading_already_loaded", ());
+        if batch.len() >= BATCH_SIZE {
+            emit_backend_event(
+                app,
+                BackendEvent::LogsBatch {
+                    entries: std::mem::take(&mut batch),
+                },
+            );
         }
-        let _ = app.emit("loading_success", ());
-        let mut fl = loading_st.is_loading.lock().unwrap();
-        *fl = false;
-    });
-
+    }
+    if !batch.is_empty() {
+        emit_backend_event(app, BackendEvent::LogsBatch { entries: batch });
+    }
+    if count == 0 && start_offset > 0 {
+        log::info!("No new lines detected since last load");
+        emit_backend_event(app, BackendEvent::LoadingAlreadyLoaded);
+    }
+    emit_backend_event(app, BackendEvent::LoadingSuccess);
     Ok(())
 }
 #[tauri::command]
 pub fn cancel_file_loading(loading_state: State<'_, Arc<LoadingState>>) {
-    println!("[CANCEL] Command received");
+    log::debug!("cancel_file_loading: command received");
 
-    let mut guard = loading_state.cancel_token.lock().unwrap();
-    println!("[CANCEL] Lock acquired");
+    let mut guard = loading_state.cancel_token.lock_or_recover();
 
     if let Some(token) = guard.take() {
-        println!("[CANCEL] Token found, cancelling...");
         token.cancel();
-        println!("[CANCEL] Token cancelled successfully");
+        log::info!("cancel_file_loading: token cancelled");
     } else {
-        println!("[CANCEL] No token found. Trying to cancel anyway");
+        log::info!("cancel_file_loading: no active token, storing a cancelled one");
         let new_token = CancellationToken::new();
         new_token.cancel();
         *guard = Some(new_token);
-        println!("[CANCEL] Fallback token created and cancelled");
     }
-    let mut is_loading = loading_state.is_loading.lock().unwrap();
-    *is_loading = false;
+    *loading_state.is_loading.lock_or_recover() = false;
 }
 #[tauri::command]
 pub fn set_current_file(path: String, state: State<'_, MonitoringState>) {
-    let mut monitor = state.state.lock().unwrap();
+    let mut monitor = state.state.lock_or_recover();
     monitor.current_file = Some(path);
     monitor.current_offset = 0;
     monitor.initial_hash = None;
@@ -283,31 +263,34 @@ pub fn set_current_file(path: String, state: State<'_, MonitoringState>) {
 }
 #[tauri::command]
 pub fn is_loading(loading_state: State<'_, Arc<LoadingState>>) -> bool {
-    let flag = loading_state.is_loading.lock().unwrap();
-    *flag
+    *loading_state.is_loading.lock_or_recover()
 }
 #[tauri::command]
 pub fn start_file_monitoring(
     file_path: String,
     state: State<'_, MonitoringState>,
     app_handle: AppHandle,
-) -> Result<(), String> {
-    println!("[MONITOR] Request to monitor: {}", file_path);
+) -> AppResult<()> {
+    log::info!("start_file_monitoring: {}", file_path);
 
-    let mut monitor = state.state.lock().unwrap();
+    let mut monitor = state.state.lock_or_recover();
 
     if monitor.is_running && monitor.current_file.as_ref() == Some(&file_path) {
-        println!("[MONITOR] Already monitoring this file.");
+        log::debug!("start_file_monitoring: already monitoring {}", file_path);
         return Ok(());
     }
 
+    if !Path::new(&file_path).is_file() {
+        return Err(AppError::NotFound(file_path));
+    }
+
     let initial_offset = if monitor.current_file.as_ref() == Some(&file_path) {
         monitor.current_offset
     } else {
         get_file_size(Path::new(&file_path))
     };
 
-    println!("[MONITOR] Initial offset: {}", initial_offset);
+    log::debug!("start_file_monitoring: initial offset {}", initial_offset);
 
     monitor.is_running = true;
     monitor.current_file = Some(file_path.clone());
@@ -324,12 +307,67 @@ pub fn start_file_monitoring(
 }
 #[tauri::command]
 pub fn stop_file_monitoring(state: State<'_, MonitoringState>) {
-    println!("[STOP] Stopping file monitoring");
-    let mut monitor = state.state.lock().unwrap();
-    monitor.is_running = false;
+    log::info!("stop_file_monitoring");
+    state.state.lock_or_recover().is_running = false;
 }
 #[tauri::command]
 pub fn get_current_file(state: State<'_, MonitoringState>) -> Option<String> {
-    let monitor = state.state.lock().unwrap();
-    monitor.current_file.clone()
+    state.state.lock_or_recover().current_file.clone()
+}
+/// Processes that have the current file open for writing (Linux only), with
+/// their detail when `with_detail` is set.
+#[tauri::command]
+pub fn get_file_writers(
+    state: State<'_, MonitoringState>,
+    system_state: State<'_, SystemMonitorState>,
+    with_detail: Option<bool>,
+) -> AppResult<Vec<FileWriter>> {
+    let file = state
+        .state
+        .lock_or_recover()
+        .current_file
+        .clone()
+        .ok_or_else(|| AppError::NotFound("no file is being monitored".to_string()))?;
+    let mut writers = find_writers(Path::new("/proc"), Path::new(&file));
+    if with_detail.unwrap_or(false) {
+        for writer in &mut writers {
+            writer.detail = system_metrics::process_detail_once(&system_state, writer.pid);
+        }
+    }
+    Ok(writers)
+}
+/// Turns the `file_writers` events sent with every metrics sample on or off.
+#[tauri::command]
+pub fn set_attach_file_writers(attach: bool, state: State<'_, MonitoringState>) {
+    state.state.lock_or_recover().attach_writers = attach;
+}
+/// Starts `spec` as a log source: its stdout and stderr arrive as
+/// `logs_batch` events like the lines of a followed file.
+#[tauri::command]
+pub fn launch_command(
+    app_handle: AppHandle,
+    state: State<'_, CommandMonitorState>,
+    spec: CommandSpec,
+) -> AppResult<CommandStatus> {
+    command_monitor::launch(&state.state, spec, 0, app_handle)
+}
+#[tauri::command]
+pub fn stop_command(state: State<'_, CommandMonitorState>) -> AppResult<()> {
+    command_monitor::stop(&state.state)
+}
+#[tauri::command]
+pub fn restart_command(
+    app_handle: AppHandle,
+    state: State<'_, CommandMonitorState>,
+) -> AppResult<()> {
+    command_monitor::restart(&state.state, app_handle)
+}
+/// The running or last launched command.
+#[tauri::command]
+pub fn get_command_status(state: State<'_, CommandMonitorState>) -> Option<CommandStatus> {
+    state
+        .state
+        .lock_or_recover()
+        .as_ref()
+        .map(|command| command.status.clone())
 }
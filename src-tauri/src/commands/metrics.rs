@@ -0,0 +1,30 @@
+use crate::{
+    error::{AppError, AppResult},
+    models::metrics::{MetricPoint, Resolution},
+    storage::MetricStore,
+};
+use tauri::{AppHandle, Manager};
+
+fn store(app: &AppHandle) -> AppResult<tauri::State<'_, MetricStore>> {
+    app.try_state::<MetricStore>()
+        .ok_or_else(|| AppError::NotFound("metrics store is not available".to_string()))
+}
+
+/// Stored points of `metric` between `from_ms` and `to_ms` (Unix ms). The
+/// resolution (`"1s"`, `"1m"` or `"1h"`) is picked from the range if omitted;
+/// per-core, per-GPU, per-sensor and per-mount series start at `"1m"`.
+#[tauri::command]
+pub fn query_metrics(
+    app: AppHandle,
+    metric: String,
+    from_ms: i64,
+    to_ms: i64,
+    resolution: Option<Resolution>,
+) -> AppResult<Vec<MetricPoint>> {
+    store(&app)?.query(&metric, from_ms, to_ms, resolution)
+}
+
+#[tauri::command]
+pub fn list_metrics(app: AppHandle) -> AppResult<Vec<String>> {
+    Ok(store(&app)?.metric_names())
+}
@@ -0,0 +1,207 @@
+use super::system::snapshot;
+use crate::{
+    error::{AppError, AppResult},
+    models::process::{PendingProcessAction, ProcessAction, ProcessSeries, ProcessTreeNode},
+    process::{
+        control,
+        filter::{ProcessFilter, PROCESS_FILTER_FILE},
+        tree,
+        watch::{WatchRule, WATCH_RULES_FILE},
+    },
+    state::{
+        process::{PendingAction, ProcessControlState, WatchState, CONFIRMATION_TTL},
+        system::SystemMonitorState,
+    },
+    utils::{json_file, sync::LockExt},
+};
+use chrono::Utc;
+use sysinfo::{Pid, PidExt, ProcessExt, ProcessRefreshKind, SystemExt};
+use tauri::{AppHandle, Manager, State};
+
+/// The processes of the latest snapshot arranged by parent, with CPU and
+/// memory summed per subtree.
+#[tauri::command]
+pub async fn get_process_tree(
+    state: State<'_, SystemMonitorState>,
+    show_all: Option<bool>,
+) -> AppResult<Vec<ProcessTreeNode>> {
+    let info = snapshot(&state, None, show_all.unwrap_or(false)).await;
+    Ok(tree::build_tree(&info.processes))
+}
+
+/// Rolling CPU, memory and disk samples of `pid` over the last `range_secs`
+/// (everything kept by default). Only the busiest processes and the one
+/// selected in `get_system_info` have a history.
+#[tauri::command]
+pub fn get_process_history(
+    state: State<'_, SystemMonitorState>,
+    pid: u32,
+    range_secs: Option<u64>,
+) -> AppResult<ProcessSeries> {
+    let since_ms = range_secs.map_or(i64::MIN, |secs| {
+        Utc::now().timestamp_millis() - secs as i64 * 1000
+    });
+    state
+        .process_history
+        .lock_or_recover()
+        .series(pid, since_ms)
+        .ok_or_else(|| AppError::NotFound(format!("no history for process {}", pid)))
+}
+
+#[tauri::command]
+pub fn get_process_filter(state: State<'_, SystemMonitorState>) -> ProcessFilter {
+    state.process_filter.lock_or_recover().clone()
+}
+
+/// Replaces the process filter and saves it for the next start.
+#[tauri::command]
+pub fn set_process_filter(
+    app: AppHandle,
+    state: State<'_, SystemMonitorState>,
+    filter: ProcessFilter,
+) -> AppResult<()> {
+    filter.validate()?;
+    if let Ok(dir) = app.path().app_data_dir() {
+        json_file::save(&dir.join(PROCESS_FILTER_FILE), &filter)?;
+    }
+    *state.process_filter.lock_or_recover() = filter;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_watch_rules(state: State<'_, WatchState>) -> Vec<WatchRule> {
+    state.rules.lock_or_recover().clone()
+}
+
+/// Adds `rule`, or replaces the rule with the same id, and saves the rules.
+/// Returns the rule with its id assigned.
+#[tauri::command]
+pub fn save_watch_rule(
+    app: AppHandle,
+    state: State<'_, WatchState>,
+    monitor: State<'_, SystemMonitorState>,
+    mut rule: WatchRule,
+) -> AppResult<WatchRule> {
+    rule.validate(monitor.sys.lock_or_recover().cpus().len())?;
+    let mut rules = state.rules.lock_or_recover();
+    match rules
+        .iter_mut()
+        .find(|r| !rule.id.is_empty() && r.id == rule.id)
+    {
+        Some(existing) => *existing = rule.clone(),
+        None => {
+            let mut id = Utc::now().timestamp_millis();
+            while rules.iter().any(|r| r.id == format!("rule-{}", id)) {
+                id += 1;
+            }
+            rule.id = format!("rule-{}", id);
+            rules.push(rule.clone());
+        }
+    }
+    save_watch_rules(&app, &rules)?;
+    state.engine.lock_or_recover().reset(&rule.id);
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_watch_rule(
+    app: AppHandle,
+    state: State<'_, WatchState>,
+    id: String,
+) -> AppResult<()> {
+    let mut rules = state.rules.lock_or_recover();
+    let before = rules.len();
+    rules.retain(|r| r.id != id);
+    if rules.len() == before {
+        return Err(AppError::NotFound(format!("watch rule {}", id)));
+    }
+    save_watch_rules(&app, &rules)
+}
+
+fn save_watch_rules(app: &AppHandle, rules: &[WatchRule]) -> AppResult<()> {
+    if let Ok(dir) = app.path().app_data_dir() {
+        json_file::save(&dir.join(WATCH_RULES_FILE), &rules)?;
+    }
+    Ok(())
+}
+
+/// First step of every process action: checks the target and arguments and
+/// returns a short-lived token. Nothing is sent until the token is confirmed.
+#[tauri::command]
+pub fn request_process_action(
+    control_state: State<'_, ProcessControlState>,
+    system_state: State<'_, SystemMonitorState>,
+    pid: u32,
+    action: ProcessAction,
+) -> AppResult<PendingProcessAction> {
+    control::guard(pid)?;
+    control::validate(&action)?;
+    let (name, start_time) = identify(&system_state, pid)?;
+
+    let now_ms = Utc::now().timestamp_millis();
+    let expires_at_ms = now_ms + CONFIRMATION_TTL.as_millis() as i64;
+    let token = control_state.next_token();
+
+    let mut pending = control_state.pending.lock_or_recover();
+    pending.retain(|_, p| p.expires_at_ms > now_ms);
+    pending.insert(
+        token.clone(),
+        PendingAction {
+            pid,
+            start_time,
+            action: action.clone(),
+            expires_at_ms,
+        },
+    );
+
+    Ok(PendingProcessAction {
+        token,
+        pid,
+        name,
+        action,
+        expires_at_ms,
+    })
+}
+
+/// Runs the action behind `token`. Tokens are single use.
+#[tauri::command]
+pub fn confirm_process_action(
+    control_state: State<'_, ProcessControlState>,
+    system_state: State<'_, SystemMonitorState>,
+    token: String,
+) -> AppResult<()> {
+    let pending = control_state
+        .pending
+        .lock_or_recover()
+        .remove(&token)
+        .filter(|p| p.expires_at_ms > Utc::now().timestamp_millis())
+        .ok_or_else(|| {
+            AppError::InvalidArgument("unknown or expired confirmation token".to_string())
+        })?;
+
+    let (_, start_time) = identify(&system_state, pending.pid)?;
+    if start_time != pending.start_time {
+        return Err(AppError::NotFound(format!(
+            "process {} exited and its pid was reused",
+            pending.pid
+        )));
+    }
+    control::apply(pending.pid, &pending.action)
+}
+
+/// Drops a pending action the user declined.
+#[tauri::command]
+pub fn cancel_process_action(control_state: State<'_, ProcessControlState>, token: String) {
+    control_state.pending.lock_or_recover().remove(&token);
+}
+
+/// Name and start time of `pid`, freshly read.
+fn identify(state: &SystemMonitorState, pid: u32) -> AppResult<(String, u64)> {
+    let mut sys = state.sys.lock_or_recover();
+    let sys_pid = Pid::from_u32(pid);
+    sys.refresh_process_specifics(sys_pid, ProcessRefreshKind::new());
+    let process = sys
+        .process(sys_pid)
+        .ok_or_else(|| AppError::NotFound(format!("process {}", pid)))?;
+    Ok((process.name().to_string(), process.start_time()))
+}
@@ -1,111 +1,87 @@
 use crate::{
-    models::system_info::{SystemInfo, ProcessDetail, LocalProcessInfo},
+    error::AppResult,
+    models::{metrics::MetricsSample, system_info::SystemInfo},
+    monitoring::system_metrics::{self, CPU_SAMPLE_INTERVAL},
     state::system::SystemMonitorState,
-    utils::log_parser::is_system_process,
+    utils::sync::LockExt,
 };
-use sysinfo::{CpuExt, PidExt, ProcessExt, SystemExt};
-use systemstat::Platform;
+use chrono::Utc;
+use std::sync::atomic::Ordering;
 use tauri::State;
 use tokio::time;
-use nvml_wrapper::{self as nvml};
 
+const DEFAULT_HISTORY_RANGE_SECS: u64 = 300;
+/// CPU usage needs two refreshes [`CPU_SAMPLE_INTERVAL`] apart, so sampling
+/// faster than that only yields noise.
+const MIN_SAMPLE_INTERVAL_MS: u64 = CPU_SAMPLE_INTERVAL.as_millis() as u64;
+const MAX_SAMPLE_INTERVAL_MS: u64 = 60_000;
+
+/// Returns the latest background snapshot, with `selected_pid`'s detail read
+/// from the same refresh. Before the sampler's first tick it measures once.
+/// Processes hidden by the process filter are left out unless `show_all`.
 #[tauri::command]
 pub async fn get_system_info(
     state: State<'_, SystemMonitorState>,
     selected_pid: Option<u32>,
-) -> Result<SystemInfo, String> {
-    {
-        let mut sys = state.sys.lock().unwrap();
-        sys.refresh_all();
-    }
-
-    time::sleep(time::Duration::from_millis(300)).await;
-
-    let (cpu_usage, total_memory, used_memory, cpu_name, processes, selected_process_detail) = {
-        let mut sys = state.sys.lock().unwrap();
-        sys.refresh_cpu();
-
-        let cpu_usage = sys.global_cpu_info().cpu_usage();
-        let total_memory = sys.total_memory();
-        let used_memory = sys.used_memory();
-        let cpu_name = sys.global_cpu_info().brand().to_string();
-
-        let mut processes = Vec::new();
-        let mut selected_process_detail = None;
-
-        for (pid, process) in sys.processes() {
-            let pid_value = pid.as_u32();
-            let name = process.name().to_string();
-
-            if is_system_process(pid_value, &name) {
-                continue;
-            }
-
-            let process_info = LocalProcessInfo {
-                pid: pid_value,
-                name: name.clone(),
-                cpu_usage: process.cpu_usage()/ (sys.cpus().len() as f32),
-                memory: process.memory(),
-            };
-
-            processes.push(process_info);
+    show_all: Option<bool>,
+) -> AppResult<SystemInfo> {
+    *state.selected_pid.lock_or_recover() = selected_pid;
+    Ok(snapshot(&state, selected_pid, show_all.unwrap_or(false)).await)
+}
 
-            if Some(pid_value) == selected_pid {
-                selected_process_detail = Some(ProcessDetail {
-                    pid: pid_value,
-                    name,
-                    cpu_usage: process.cpu_usage() / (sys.cpus().len() as f32),
-                    memory: process.memory(),
-                    status: format!("{:?}", process.status()),
-                    exe_path: Some(process.exe().to_string_lossy().to_string()),
-                    command_line: Some(process.cmd().join(" ")),
-                });
-            }
+pub(crate) async fn snapshot(
+    state: &SystemMonitorState,
+    selected_pid: Option<u32>,
+    show_all: bool,
+) -> SystemInfo {
+    let latest = state.latest.lock_or_recover().clone();
+    let mut info = match latest {
+        Some(mut info) => {
+            info.selected_process =
+                selected_pid.and_then(|pid| system_metrics::process_detail(state, pid));
+            info
+        }
+        None => {
+            system_metrics::refresh(state);
+            time::sleep(CPU_SAMPLE_INTERVAL).await;
+            system_metrics::refresh(state);
+            system_metrics::collect(state, selected_pid)
         }
-
-        processes.sort_by(|a, b| {
-            b.cpu_usage
-                .partial_cmp(&a.cpu_usage)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        (
-            cpu_usage,
-            total_memory,
-            used_memory,
-            cpu_name,
-            processes,
-            selected_process_detail,
-        )
     };
+    if !show_all {
+        let filter = state.process_filter.lock_or_recover();
+        info.processes.retain(|p| !filter.hides(p));
+    }
+    info
+}
 
-    let cpu_temp = state.stats.cpu_temp().ok();
-
-    let (gpu_name, gpu_temp, gpu_usage) = match &state.nvml {
-        Some(nvml) => match nvml.device_by_index(0) {
-            Ok(device) => {
-                let name = device.name().ok();
-                let temp = device
-                    .temperature(nvml::enum_wrappers::device::TemperatureSensor::Gpu)
-                    .ok();
-                let usage = device.utilization_rates().map(|u| u.gpu).ok();
-                (name, temp, usage)
-            }
-            Err(_) => (None, None, None),
-        },
-        None => (None, None, None),
-    };
+/// Samples from the last `range_secs` (5 minutes by default), averaged into
+/// `resolution_secs` buckets. Without a resolution the raw samples are
+/// returned.
+#[tauri::command]
+pub fn get_metrics_history(
+    state: State<'_, SystemMonitorState>,
+    range_secs: Option<u64>,
+    resolution_secs: Option<u64>,
+) -> Vec<MetricsSample> {
+    let range_ms = range_secs.unwrap_or(DEFAULT_HISTORY_RANGE_SECS) as i64 * 1000;
+    let resolution_ms = resolution_secs.unwrap_or(0) as i64 * 1000;
+    state
+        .history
+        .lock_or_recover()
+        .query(Utc::now().timestamp_millis(), range_ms, resolution_ms)
+}
 
-    Ok(SystemInfo {
-        cpu_usage,
-        total_memory,
-        used_memory,
-        cpu_temp,
-        cpu_name: Some(cpu_name),
-        gpu_name,
-        gpu_temp,
-        gpu_usage,
-        processes,
-        selected_process: selected_process_detail,
-    })
-}
\ No newline at end of file
+/// Sets how often the background sampler runs, clamped to what CPU sampling
+/// allows. Returns the interval that was applied.
+#[tauri::command]
+pub fn set_metrics_interval(state: State<'_, SystemMonitorState>, interval_ms: u64) -> u64 {
+    let applied = interval_ms.clamp(MIN_SAMPLE_INTERVAL_MS, MAX_SAMPLE_INTERVAL_MS);
+    log::info!(
+        "Metrics sample interval set to {} ms (requested {} ms)",
+        applied,
+        interval_ms
+    );
+    state.sample_interval_ms.store(applied, Ordering::Relaxed);
+    applied
+}
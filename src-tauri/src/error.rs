@@ -0,0 +1,89 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::io;
+
+/// Error returned by every command and carried by error events.
+///
+/// Serialized for the UI as a stable machine-readable `code` plus the
+/// human-readable `message`:
+///
+/// ```
+/// use app_lib::error::AppError;
+///
+/// let err = AppError::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+/// let json = serde_json::to_value(&err).unwrap();
+/// assert_eq!(json["code"], "not_found");
+/// assert_eq!(json["message"], err.to_string());
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("I/O error: {0}")]
+    Io(#[source] io::Error),
+    #[error("encoding error: {0}")]
+    Encoding(String),
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("operation cancelled")]
+    Cancelled,
+    #[error("NVML error: {0}")]
+    Nvml(#[from] nvml_wrapper::error::NvmlError),
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("refusing to act on protected process {0}")]
+    ProtectedProcess(u32),
+    #[error("not supported on this platform: {0}")]
+    Unsupported(String),
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "io",
+            AppError::Encoding(_) => "encoding",
+            AppError::Parse(_) => "parse",
+            AppError::NotFound(_) => "not_found",
+            AppError::PermissionDenied(_) => "permission_denied",
+            AppError::Cancelled => "cancelled",
+            AppError::Nvml(_) => "nvml",
+            AppError::InvalidArgument(_) => "invalid_argument",
+            AppError::ProtectedProcess(_) => "protected_process",
+            AppError::Unsupported(_) => "unsupported",
+        }
+    }
+
+    /// Wraps an I/O error with the operation that failed, keeping the
+    /// `NotFound` / `PermissionDenied` classification.
+    pub fn io_context(context: impl std::fmt::Display, err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::NotFound => AppError::NotFound(format!("{}: {}", context, err)),
+            io::ErrorKind::PermissionDenied => {
+                AppError::PermissionDenied(format!("{}: {}", context, err))
+            }
+            _ => AppError::Io(io::Error::new(err.kind(), format!("{}: {}", context, err))),
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::NotFound => AppError::NotFound(err.to_string()),
+            io::ErrorKind::PermissionDenied => AppError::PermissionDenied(err.to_string()),
+            _ => AppError::Io(err),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AppError", 2)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", &self.to_string())?;
+        s.end()
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,118 @@
+//! The desktop app: Tauri plugins, managed state and the IPC commands.
+
+use crate::{
+    alerts::{AlertHistory, ALERT_HISTORY_FILE, ALERT_RULES_FILE, LOG_ALERT_RULES_FILE},
+    commands,
+    monitoring::{self, command_monitor::CommandMonitorState, file_monitor::MonitoringState},
+    process::{filter::PROCESS_FILTER_FILE, watch::WATCH_RULES_FILE},
+    state::{
+        alerts::AlertsState,
+        events::EventSequencer,
+        logs::LoadingState,
+        process::{ProcessControlState, WatchState},
+        system::SystemMonitorState,
+    },
+    storage::{MetricStore, Retention},
+    utils::{json_file, sync::LockExt},
+};
+use std::sync::Arc;
+use tauri::Manager;
+
+/// Builds the app with every plugin, managed state and command registered.
+/// Shared by the desktop binary and the mobile entry point.
+pub fn builder() -> tauri::Builder<tauri::Wry> {
+    let builder = tauri::Builder::default()
+        .plugin(
+            tauri_plugin_log::Builder::default()
+                .level(log::LevelFilter::Info)
+                .build(),
+        )
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init());
+
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_window_state::Builder::new().build());
+
+    builder
+        .setup(|app| {
+            log::info!("Starting application");
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    *app.state::<SystemMonitorState>()
+                        .process_filter
+                        .lock_or_recover() = json_file::load(&dir.join(PROCESS_FILTER_FILE));
+                    *app.state::<WatchState>().rules.lock_or_recover() =
+                        json_file::load(&dir.join(WATCH_RULES_FILE));
+                    let alerts = app.state::<AlertsState>();
+                    *alerts.metric_rules.lock_or_recover() =
+                        json_file::load(&dir.join(ALERT_RULES_FILE));
+                    *alerts.log_rules.lock_or_recover() =
+                        json_file::load(&dir.join(LOG_ALERT_RULES_FILE));
+                    *alerts.history.lock_or_recover() =
+                        AlertHistory::open(dir.join(ALERT_HISTORY_FILE));
+                    match MetricStore::open(dir.join("metrics"), Retention::default()) {
+                        Ok(store) => {
+                            app.manage(store);
+                        }
+                        Err(e) => log::warn!("Metrics history will not be persisted: {}", e),
+                    }
+                }
+                Err(e) => log::warn!("No app data directory, metrics history disabled: {}", e),
+            }
+            monitoring::sampler::spawn_sampler(app.handle().clone());
+            Ok(())
+        })
+        .manage(SystemMonitorState::new())
+        .manage(EventSequencer::new())
+        .manage(MonitoringState::new())
+        .manage(CommandMonitorState::new())
+        .manage(Arc::new(LoadingState::new()))
+        .manage(ProcessControlState::new())
+        .manage(WatchState::new())
+        .manage(AlertsState::new())
+        .invoke_handler(tauri::generate_handler![
+            commands::logs::set_current_file,
+            commands::logs::start_file_monitoring,
+            commands::logs::stop_file_monitoring,
+            commands::logs::start_file_loading,
+            commands::system::get_system_info,
+            commands::system::get_metrics_history,
+            commands::system::set_metrics_interval,
+            commands::metrics::query_metrics,
+            commands::metrics::list_metrics,
+            commands::process::get_process_tree,
+            commands::process::get_process_history,
+            commands::process::get_process_filter,
+            commands::process::set_process_filter,
+            commands::process::request_process_action,
+            commands::process::confirm_process_action,
+            commands::process::cancel_process_action,
+            commands::process::get_watch_rules,
+            commands::process::save_watch_rule,
+            commands::process::delete_watch_rule,
+            commands::alerts::get_alert_rules,
+            commands::alerts::save_alert_rule,
+            commands::alerts::delete_alert_rule,
+            commands::alerts::get_log_alert_rules,
+            commands::alerts::save_log_alert_rule,
+            commands::alerts::delete_log_alert_rule,
+            commands::alerts::get_active_alerts,
+            commands::alerts::get_alert_history,
+            commands::logs::get_current_file,
+            commands::logs::is_loading,
+            commands::logs::cancel_file_loading,
+            commands::logs::get_file_writers,
+            commands::logs::set_attach_file_writers,
+            commands::logs::launch_command,
+            commands::logs::stop_command,
+            commands::logs::restart_command,
+            commands::logs::get_command_status
+        ])
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    builder()
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
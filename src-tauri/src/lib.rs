@@ -1,16 +1,16 @@
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
-            Ok(())
-        })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+pub mod alerts;
+pub mod cli;
+#[cfg(feature = "gui")]
+pub mod commands;
+pub mod error;
+#[cfg(feature = "gui")]
+mod gui;
+pub mod models;
+pub mod monitoring;
+pub mod process;
+pub mod state;
+pub mod storage;
+pub mod utils;
+
+#[cfg(feature = "gui")]
+pub use gui::{builder, run};
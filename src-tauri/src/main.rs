@@ -1,58 +1,35 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod commands;
-mod models;
-mod monitoring;
-mod state;
-mod utils;
+use std::process::ExitCode;
 
-use state::{system::SystemMonitorState};
-use tauri::{generate_context};
-use std::sync::{Arc, Mutex};
-use nvml_wrapper;
-use sysinfo::{CpuRefreshKind, RefreshKind, System, SystemExt};
-use systemstat::{Platform, System as Stats};
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        attach_console();
+    }
+    if let Some(code) = app_lib::cli::run(&args) {
+        return code;
+    }
 
-use crate::monitoring::file_monitor::FileMonitorState;
+    app_lib::run();
+    ExitCode::SUCCESS
+}
 
-fn main() {
-    println!("[INIT] Starting application");
-    
-    let sys_monitor = SystemMonitorState {
-        sys: Mutex::new(System::new_with_specifics(
-            RefreshKind::new().with_cpu(CpuRefreshKind::everything()),
-        )),
-        stats: Stats::new(),
-        nvml: nvml_wrapper::Nvml::init().ok().map(Arc::new),
-    };
-    
-    tauri::Builder::default()
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_window_state::Builder::new().build())
-        .manage(sys_monitor)
-        .manage(monitoring::file_monitor::MonitoringState {
-            state: Arc::new(Mutex::new(FileMonitorState {
-                is_running: false,
-                current_file: None,
-                current_offset: 0,
-                last_modified: None,
-                initial_hash: None,
-            })),
-        })
-        .manage(Arc::new(state::logs::LoadingState {
-            cancel_token: Mutex::new(None),
-            is_loading: Mutex::new(false),
-        }))
-        .invoke_handler(tauri::generate_handler![
-            commands::logs::set_current_file,
-            commands::logs::start_file_monitoring,
-            commands::logs::stop_file_monitoring,
-            commands::logs::start_file_loading,
-            commands::system::get_system_info,
-            commands::logs::get_current_file,
-            commands::logs::is_loading,
-            commands::logs::cancel_file_loading
-        ])
-        .run(generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+/// A release build on Windows is a GUI-subsystem program without a console,
+/// so the headless mode would print nothing; write to the console of the
+/// shell it was started from instead. `app-cli` does not need this.
+#[cfg(all(windows, not(debug_assertions)))]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: AttachConsole has no memory-safety preconditions; it fails
+    // harmlessly when there is no parent console.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(all(windows, not(debug_assertions))))]
+fn attach_console() {}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A command launched from the app whose output is followed like a log file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct CommandSpec {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Working directory, the app's own when unset.
+    #[serde(default)]
+    pub cwd: Option<String>,
+}
+
+impl CommandSpec {
+    /// The command line for display, with arguments containing whitespace
+    /// quoted.
+    ///
+    /// ```
+    /// use app_lib::models::command::CommandSpec;
+    ///
+    /// let spec = CommandSpec {
+    ///     program: "cargo".into(),
+    ///     args: vec!["run".into(), "--".into(), "two words".into()],
+    ///     cwd: None,
+    /// };
+    /// assert_eq!(spec.command_line(), r#"cargo run -- "two words""#);
+    /// ```
+    pub fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|part| {
+                if part.is_empty() || part.contains(char::is_whitespace) {
+                    format!("\"{}\"", part)
+                } else {
+                    part.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// How a launched command ended. `signal` is set instead of `code` when it
+/// was killed by a signal (Unix).
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct CommandExit {
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    #[ts(type = "number")]
+    pub at_ms: i64,
+}
+
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct CommandStatus {
+    pub spec: CommandSpec,
+    pub command_line: String,
+    /// Set while the command runs.
+    pub pid: Option<u32>,
+    #[ts(type = "number")]
+    pub started_at_ms: i64,
+    pub exit: Option<CommandExit>,
+    /// How often the command was restarted from the app.
+    pub restarts: u32,
+}
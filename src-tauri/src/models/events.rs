@@ -0,0 +1,153 @@
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::{
+    command::CommandStatus, log_entry::LogEntry, metrics::MetricsSample, process::FileWriter,
+};
+use crate::{alerts::AlertRecord, error::AppError, process::watch::WatchTrigger};
+
+/// Name of the single Tauri channel every [`EventEnvelope`] is emitted on.
+pub const BACKEND_EVENT: &str = "backend_event";
+
+/// Bumped whenever the serialized shape of [`BackendEvent`] changes.
+pub const EVENT_PROTOCOL_VERSION: u32 = 8;
+
+/// Everything the backend pushes to the UI.
+///
+/// Serialized with a `type` tag next to the variant fields:
+///
+/// ```
+/// use app_lib::models::events::BackendEvent;
+///
+/// let json = serde_json::to_value(BackendEvent::LoadProgress { current: 10, total: 20 }).unwrap();
+/// assert_eq!(json, serde_json::json!({ "type": "load_progress", "current": 10, "total": 20 }));
+///
+/// let json = serde_json::to_value(BackendEvent::FileTruncated).unwrap();
+/// assert_eq!(json, serde_json::json!({ "type": "file_truncated" }));
+///
+/// let err = app_lib::error::AppError::PermissionDenied("/var/log/syslog".into());
+/// let json = serde_json::to_value(BackendEvent::loading_error(&err)).unwrap();
+/// assert_eq!(
+///     json,
+///     serde_json::json!({
+///         "type": "loading_error",
+///         "code": "permission_denied",
+///         "message": "permission denied: /var/log/syslog",
+///     })
+/// );
+/// ```
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackendEvent {
+    LogsBatch {
+        entries: Vec<LogEntry>,
+    },
+    LoadProgress {
+        current: usize,
+        total: usize,
+    },
+    LoadingSuccess,
+    LoadingAlreadyLoaded,
+    LoadingCancelled,
+    LoadingError {
+        code: String,
+        message: String,
+    },
+    FileCleared,
+    FileTruncated,
+    MonitoringError {
+        code: String,
+        message: String,
+    },
+    MetricsSample {
+        sample: Box<MetricsSample>,
+    },
+    /// Processes writing the monitored file, when attached to the session.
+    FileWriters {
+        path: String,
+        writers: Vec<FileWriter>,
+    },
+    /// Lifecycle of the command launched as a log source.
+    CommandStarted {
+        status: CommandStatus,
+    },
+    CommandExited {
+        status: CommandStatus,
+    },
+    WatchTriggered {
+        trigger: WatchTrigger,
+    },
+    /// An alert changed state; log alerts carry the lines that matched.
+    Alert {
+        record: AlertRecord,
+    },
+}
+
+impl BackendEvent {
+    pub fn loading_error(err: &AppError) -> Self {
+        BackendEvent::LoadingError {
+            code: err.code().to_string(),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn monitoring_error(err: &AppError) -> Self {
+        BackendEvent::MonitoringError {
+            code: err.code().to_string(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// A [`BackendEvent`] stamped with the protocol version, the backend session
+/// it came from and its position in that session.
+///
+/// The event fields are flattened into the envelope:
+///
+/// ```
+/// use app_lib::models::{
+///     events::{BackendEvent, EventEnvelope, EVENT_PROTOCOL_VERSION},
+///     log_entry::LogEntry,
+/// };
+///
+/// let entry = LogEntry {
+///     timestamp: "2025-06-20 22:01:05".into(),
+///     level: "ERROR".into(),
+///     message: "boom".into(),
+///     stream: None,
+/// };
+/// let envelope = EventEnvelope::new("abc", 7, BackendEvent::LogsBatch { entries: vec![entry] });
+///
+/// assert_eq!(
+///     serde_json::to_value(&envelope).unwrap(),
+///     serde_json::json!({
+///         "version": EVENT_PROTOCOL_VERSION,
+///         "session_id": "abc",
+///         "seq": 7,
+///         "type": "logs_batch",
+///         "entries": [{ "timestamp": "2025-06-20 22:01:05", "level": "ERROR", "message": "boom" }],
+///     })
+/// );
+/// ```
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct EventEnvelope {
+    pub version: u32,
+    pub session_id: String,
+    #[ts(type = "number")]
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: BackendEvent,
+}
+
+impl EventEnvelope {
+    pub fn new(session_id: impl Into<String>, seq: u64, event: BackendEvent) -> Self {
+        Self {
+            version: EVENT_PROTOCOL_VERSION,
+            session_id: session_id.into(),
+            seq,
+            event,
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+use super::system_info::{PowerInfo, PressureInfo, PressureStat, RaplZone, SystemInfo};
+
+/// The numeric part of a [`SystemInfo`] snapshot kept in the metrics history.
+#[derive(Serialize, Clone, Debug, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct MetricsSample {
+    /// Unix time in milliseconds.
+    #[ts(type = "number")]
+    pub timestamp_ms: i64,
+    pub cpu_usage: f32,
+    pub core_usage: Vec<f32>,
+    #[ts(type = "number")]
+    pub memory_used: u64,
+    #[ts(type = "number")]
+    pub memory_total: u64,
+    #[ts(type = "number")]
+    pub memory_available: u64,
+    #[ts(type = "number")]
+    pub swap_used: u64,
+    /// `some` avg10 stall percentages from PSI (Linux).
+    pub cpu_pressure: Option<f64>,
+    pub memory_pressure: Option<f64>,
+    pub io_pressure: Option<f64>,
+    pub cpu_temp: Option<f32>,
+    /// Of the first GPU; every GPU is in `gpus`.
+    pub gpu_usage: Option<u32>,
+    pub gpu_temp: Option<u32>,
+    pub gpus: Vec<GpuSample>,
+    /// Summed over physical block devices.
+    pub disk_read_bytes_per_sec: f64,
+    pub disk_write_bytes_per_sec: f64,
+    pub disk_read_iops: f64,
+    pub disk_write_iops: f64,
+    pub mounts: Vec<MountSample>,
+    /// Summed over non-loopback interfaces.
+    pub net_rx_bytes_per_sec: f64,
+    pub net_tx_bytes_per_sec: f64,
+    pub sensors: Vec<SensorSample>,
+    pub battery_percent: Option<f32>,
+    /// Summed over RAPL package zones.
+    pub package_power_watts: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct SensorSample {
+    pub id: String,
+    pub value: f64,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct GpuSample {
+    /// The `index` of the GPU in `SystemInfo::gpus`.
+    pub index: u32,
+    pub usage: Option<u32>,
+    pub temp: Option<u32>,
+    #[ts(type = "number | null")]
+    pub memory_used: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct MountSample {
+    pub mount_point: String,
+    #[ts(type = "number")]
+    pub used_bytes: u64,
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+}
+
+impl MetricsSample {
+    pub fn from_info(timestamp_ms: i64, info: &SystemInfo) -> Self {
+        let physical = || info.storage.devices.iter().filter(|d| d.physical);
+        let external = || info.network.interfaces.iter().filter(|i| !i.is_loopback);
+        let avg10 = |pick: fn(&PressureInfo) -> Option<&PressureStat>| {
+            info.pressure.as_ref().and_then(pick).map(|s| s.some.avg10)
+        };
+        Self {
+            timestamp_ms,
+            cpu_usage: info.cpu_usage,
+            core_usage: info.cpu_cores.iter().map(|c| c.usage).collect(),
+            memory_used: info.used_memory,
+            memory_total: info.total_memory,
+            memory_available: info.memory.available,
+            swap_used: info.memory.swap_used,
+            cpu_pressure: avg10(|p| p.cpu.as_ref()),
+            memory_pressure: avg10(|p| p.memory.as_ref()),
+            io_pressure: avg10(|p| p.io.as_ref()),
+            cpu_temp: info.cpu_temp,
+            gpu_usage: info.gpu_usage,
+            gpu_temp: info.gpu_temp,
+            gpus: info
+                .gpus
+                .iter()
+                .map(|g| GpuSample {
+                    index: g.index,
+                    usage: g.utilization,
+                    temp: g.temperature,
+                    memory_used: g.memory_used,
+                })
+                .collect(),
+            disk_read_bytes_per_sec: physical().map(|d| d.read_bytes_per_sec).sum(),
+            disk_write_bytes_per_sec: physical().map(|d| d.write_bytes_per_sec).sum(),
+            disk_read_iops: physical().map(|d| d.read_iops).sum(),
+            disk_write_iops: physical().map(|d| d.write_iops).sum(),
+            mounts: info
+                .storage
+                .mounts
+                .iter()
+                .map(|m| MountSample {
+                    mount_point: m.mount_point.clone(),
+                    used_bytes: m.used_bytes,
+                    total_bytes: m.total_bytes,
+                })
+                .collect(),
+            net_rx_bytes_per_sec: external().map(|i| i.rx_bytes_per_sec).sum(),
+            net_tx_bytes_per_sec: external().map(|i| i.tx_bytes_per_sec).sum(),
+            sensors: info
+                .sensors
+                .iter()
+                .map(|r| SensorSample {
+                    id: r.id.clone(),
+                    value: r.value,
+                })
+                .collect(),
+            battery_percent: info
+                .power
+                .as_ref()
+                .and_then(|p| p.battery.as_ref())
+                .map(|b| b.charge_percent),
+            package_power_watts: info.power.as_ref().and_then(package_power),
+        }
+    }
+
+    /// Flattens the sample into named series for the metrics store, e.g.
+    /// `cpu.usage`, `cpu.core.3.usage`, `gpu.1.temp`, `memory.used`, `disk.used[/home]` or
+    /// `sensor[thermal/x86_pkg_temp/0]`.
+    pub fn named_values(&self) -> BTreeMap<String, f64> {
+        let mut values = BTreeMap::new();
+        values.insert("cpu.usage".to_string(), self.cpu_usage as f64);
+        for (index, usage) in self.core_usage.iter().enumerate() {
+            values.insert(format!("cpu.core.{}.usage", index), *usage as f64);
+        }
+        values.insert("memory.used".to_string(), self.memory_used as f64);
+        values.insert("memory.total".to_string(), self.memory_total as f64);
+        values.insert("memory.available".to_string(), self.memory_available as f64);
+        values.insert("swap.used".to_string(), self.swap_used as f64);
+        for (name, pressure) in [
+            ("pressure.cpu", self.cpu_pressure),
+            ("pressure.memory", self.memory_pressure),
+            ("pressure.io", self.io_pressure),
+        ] {
+            if let Some(value) = pressure {
+                values.insert(name.to_string(), value);
+            }
+        }
+        if let Some(temp) = self.cpu_temp {
+            values.insert("cpu.temp".to_string(), temp as f64);
+        }
+        if let Some(usage) = self.gpu_usage {
+            values.insert("gpu.usage".to_string(), usage as f64);
+        }
+        if let Some(temp) = self.gpu_temp {
+            values.insert("gpu.temp".to_string(), temp as f64);
+        }
+        for gpu in &self.gpus {
+            for (name, value) in [
+                ("usage", gpu.usage.map(f64::from)),
+                ("temp", gpu.temp.map(f64::from)),
+                ("memory_used", gpu.memory_used.map(|b| b as f64)),
+            ] {
+                if let Some(value) = value {
+                    values.insert(format!("gpu.{}.{}", gpu.index, name), value);
+                }
+            }
+        }
+        values.insert(
+            "disk.read_bytes_per_sec".to_string(),
+            self.disk_read_bytes_per_sec,
+        );
+        values.insert(
+            "disk.write_bytes_per_sec".to_string(),
+            self.disk_write_bytes_per_sec,
+        );
+        values.insert("disk.read_iops".to_string(), self.disk_read_iops);
+        values.insert("disk.write_iops".to_string(), self.disk_write_iops);
+        values.insert(
+            "net.rx_bytes_per_sec".to_string(),
+            self.net_rx_bytes_per_sec,
+        );
+        values.insert(
+            "net.tx_bytes_per_sec".to_string(),
+            self.net_tx_bytes_per_sec,
+        );
+        if let Some(percent) = self.battery_percent {
+            values.insert("power.battery_percent".to_string(), percent as f64);
+        }
+        if let Some(watts) = self.package_power_watts {
+            values.insert("power.package_watts".to_string(), watts);
+        }
+        for sensor in &self.sensors {
+            values.insert(format!("sensor[{}]", sensor.id), sensor.value);
+        }
+        for mount in &self.mounts {
+            values.insert(
+                format!("disk.used[{}]", mount.mount_point),
+                mount.used_bytes as f64,
+            );
+            values.insert(
+                format!("disk.total[{}]", mount.mount_point),
+                mount.total_bytes as f64,
+            );
+        }
+        values
+    }
+
+    /// Averages `samples` into one sample stamped `timestamp_ms`. Optional
+    /// values are averaged over the samples that have them; mount usage
+    /// changes slowly and is taken from the last sample.
+    pub fn average(timestamp_ms: i64, samples: &[MetricsSample]) -> Option<Self> {
+        let last = samples.last()?;
+        let n = samples.len();
+
+        let cores = samples
+            .iter()
+            .map(|s| s.core_usage.len())
+            .max()
+            .unwrap_or(0);
+        let core_usage = (0..cores)
+            .map(|i| mean_f32(samples.iter().filter_map(|s| s.core_usage.get(i).copied())))
+            .map(|v| v.unwrap_or(0.0))
+            .collect();
+
+        Some(Self {
+            timestamp_ms,
+            cpu_usage: samples.iter().map(|s| s.cpu_usage).sum::<f32>() / n as f32,
+            core_usage,
+            memory_used: samples.iter().map(|s| s.memory_used).sum::<u64>() / n as u64,
+            memory_total: last.memory_total,
+            memory_available: samples.iter().map(|s| s.memory_available).sum::<u64>() / n as u64,
+            swap_used: samples.iter().map(|s| s.swap_used).sum::<u64>() / n as u64,
+            cpu_pressure: mean_f64(samples.iter().filter_map(|s| s.cpu_pressure)),
+            memory_pressure: mean_f64(samples.iter().filter_map(|s| s.memory_pressure)),
+            io_pressure: mean_f64(samples.iter().filter_map(|s| s.io_pressure)),
+            cpu_temp: mean_f32(samples.iter().filter_map(|s| s.cpu_temp)),
+            gpu_usage: mean_u32(samples.iter().filter_map(|s| s.gpu_usage)),
+            gpu_temp: mean_u32(samples.iter().filter_map(|s| s.gpu_temp)),
+            gpus: mean_gpus(samples),
+            disk_read_bytes_per_sec: mean(samples, |s| s.disk_read_bytes_per_sec),
+            disk_write_bytes_per_sec: mean(samples, |s| s.disk_write_bytes_per_sec),
+            disk_read_iops: mean(samples, |s| s.disk_read_iops),
+            disk_write_iops: mean(samples, |s| s.disk_write_iops),
+            mounts: last.mounts.clone(),
+            net_rx_bytes_per_sec: mean(samples, |s| s.net_rx_bytes_per_sec),
+            net_tx_bytes_per_sec: mean(samples, |s| s.net_tx_bytes_per_sec),
+            sensors: mean_sensors(samples),
+            battery_percent: mean_f32(samples.iter().filter_map(|s| s.battery_percent)),
+            package_power_watts: mean_f64(samples.iter().filter_map(|s| s.package_power_watts)),
+        })
+    }
+}
+
+fn mean(samples: &[MetricsSample], value: impl Fn(&MetricsSample) -> f64) -> f64 {
+    samples.iter().map(value).sum::<f64>() / samples.len() as f64
+}
+
+/// Averages each sensor id over the samples that report it, in the order of
+/// the last sample.
+fn mean_sensors(samples: &[MetricsSample]) -> Vec<SensorSample> {
+    let mut sums: BTreeMap<&str, (f64, u32)> = BTreeMap::new();
+    for sensor in samples.iter().flat_map(|s| &s.sensors) {
+        let entry = sums.entry(sensor.id.as_str()).or_insert((0.0, 0));
+        entry.0 += sensor.value;
+        entry.1 += 1;
+    }
+    let last = samples.last().map_or(&[][..], |s| &s.sensors[..]);
+    last.iter()
+        .map(|sensor| {
+            let (sum, count) = sums[sensor.id.as_str()];
+            SensorSample {
+                id: sensor.id.clone(),
+                value: sum / count as f64,
+            }
+        })
+        .collect()
+}
+
+/// Averages each GPU over the samples that report it, in the order of the
+/// last sample.
+fn mean_gpus(samples: &[MetricsSample]) -> Vec<GpuSample> {
+    let last = samples.last().map_or(&[][..], |s| &s.gpus[..]);
+    last.iter()
+        .map(|gpu| {
+            let same = || {
+                samples
+                    .iter()
+                    .flat_map(|s| &s.gpus)
+                    .filter(|g| g.index == gpu.index)
+            };
+            GpuSample {
+                index: gpu.index,
+                usage: mean_u32(same().filter_map(|g| g.usage)),
+                temp: mean_u32(same().filter_map(|g| g.temp)),
+                memory_used: mean_u64(same().filter_map(|g| g.memory_used)),
+            }
+        })
+        .collect()
+}
+
+/// Power of all RAPL packages together; `None` without any package zone
+/// (e.g. only a battery) or while one of them has no reading yet.
+fn package_power(power: &PowerInfo) -> Option<f64> {
+    let packages: Vec<&RaplZone> = power.rapl.iter().filter(|z| z.is_package).collect();
+    if packages.is_empty() {
+        return None;
+    }
+    packages.iter().map(|z| z.power_watts).sum()
+}
+
+fn mean_f64(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(s, c), v| (s + v, c + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+fn mean_f32(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0), |(s, c), v| (s + v, c + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+fn mean_u32(values: impl Iterator<Item = u32>) -> Option<u32> {
+    let (sum, count) = values.fold((0u64, 0u64), |(s, c), v| (s + v as u64, c + 1));
+    (count > 0).then(|| (sum / count) as u32)
+}
+
+fn mean_u64(values: impl Iterator<Item = u64>) -> Option<u64> {
+    let (sum, count) = values.fold((0u128, 0u128), |(s, c), v| (s + v as u128, c + 1));
+    (count > 0).then(|| (sum / count) as u64)
+}
+
+/// Resolution tier of the on-disk metrics store.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum Resolution {
+    #[serde(rename = "1s")]
+    Second,
+    #[serde(rename = "1m")]
+    Minute,
+    #[serde(rename = "1h")]
+    Hour,
+}
+
+impl Resolution {
+    /// Finest first.
+    pub const ALL: [Resolution; 3] = [Resolution::Second, Resolution::Minute, Resolution::Hour];
+
+    pub fn millis(self) -> i64 {
+        match self {
+            Resolution::Second => 1000,
+            Resolution::Minute => 60 * 1000,
+            Resolution::Hour => 60 * 60 * 1000,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Resolution::Second => "1s",
+            Resolution::Minute => "1m",
+            Resolution::Hour => "1h",
+        }
+    }
+}
+
+/// One point of a stored metric series. `min`/`max` cover the samples rolled
+/// up into the point and equal `value` at 1s resolution.
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct MetricPoint {
+    #[ts(type = "number")]
+    pub timestamp_ms: i64,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(zone: &str, is_package: bool, power_watts: Option<f64>) -> RaplZone {
+        RaplZone {
+            zone: zone.to_string(),
+            name: zone.to_string(),
+            is_package,
+            energy_uj: 0,
+            power_watts,
+        }
+    }
+
+    fn power(rapl: Vec<RaplZone>) -> PowerInfo {
+        PowerInfo {
+            battery: None,
+            ac_online: Some(true),
+            rapl,
+        }
+    }
+
+    #[test]
+    fn package_power_without_a_package_zone_is_unknown() {
+        assert_eq!(package_power(&power(Vec::new())), None);
+        assert_eq!(
+            package_power(&power(vec![zone("intel-rapl:0:0", false, Some(3.0))])),
+            None
+        );
+    }
+
+    #[test]
+    fn package_power_sums_the_packages_only() {
+        let rapl = vec![
+            zone("intel-rapl:0", true, Some(10.0)),
+            zone("intel-rapl:0:0", false, Some(6.0)),
+            zone("intel-rapl:1", true, Some(12.5)),
+        ];
+        assert_eq!(package_power(&power(rapl)), Some(22.5));
+        assert_eq!(
+            package_power(&power(vec![zone("intel-rapl:0", true, None)])),
+            None
+        );
+    }
+}
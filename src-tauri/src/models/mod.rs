@@ -1,2 +1,6 @@
+pub mod command;
+pub mod events;
 pub mod log_entry;
-pub mod system_info;
\ No newline at end of file
+pub mod metrics;
+pub mod process;
+pub mod system_info;
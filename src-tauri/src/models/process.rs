@@ -0,0 +1,110 @@
+use super::system_info::{LocalProcessInfo, ProcessDetail};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Signals the UI can send to a process.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ProcessSignal {
+    Term,
+    Kill,
+    Stop,
+    Cont,
+    Hup,
+}
+
+/// A control action waiting for, or carried by, a confirmation.
+///
+/// Serialized with a `type` tag:
+///
+/// ```
+/// use app_lib::models::process::{ProcessAction, ProcessSignal};
+///
+/// let json = serde_json::to_value(ProcessAction::Signal { signal: ProcessSignal::Term }).unwrap();
+/// assert_eq!(json, serde_json::json!({ "type": "signal", "signal": "TERM" }));
+///
+/// let action: ProcessAction =
+///     serde_json::from_value(serde_json::json!({ "type": "set_affinity", "cpus": [0, 2] })).unwrap();
+/// assert_eq!(action, ProcessAction::SetAffinity { cpus: vec![0, 2] });
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessAction {
+    Signal {
+        signal: ProcessSignal,
+    },
+    /// Niceness between -20 (highest priority) and 19, for every thread.
+    Renice {
+        nice: i32,
+    },
+    /// Linux only: the logical CPUs every thread of the process may run on.
+    SetAffinity {
+        cpus: Vec<usize>,
+    },
+}
+
+/// Returned by `request_process_action`; the action only runs once `token`
+/// is passed to `confirm_process_action` before `expires_at_ms`.
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct PendingProcessAction {
+    pub token: String,
+    pub pid: u32,
+    pub name: String,
+    pub action: ProcessAction,
+    #[ts(type = "number")]
+    pub expires_at_ms: i64,
+}
+
+/// One process with its descendants, as returned by `get_process_tree`.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ProcessTreeNode {
+    #[serde(flatten)]
+    pub process: LocalProcessInfo,
+    /// CPU usage of this process and all its descendants.
+    pub subtree_cpu_usage: f32,
+    #[ts(type = "number")]
+    pub subtree_memory: u64,
+    /// Number of processes in the subtree, this one included.
+    pub subtree_count: usize,
+    pub children: Vec<ProcessTreeNode>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ProcessSample {
+    #[ts(type = "number")]
+    pub timestamp_ms: i64,
+    pub cpu_usage: f32,
+    #[ts(type = "number")]
+    pub memory: u64,
+    pub disk_read_bytes_per_sec: f64,
+    pub disk_write_bytes_per_sec: f64,
+}
+
+/// Rolling history of one process, as returned by `get_process_history`.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ProcessSeries {
+    pub pid: u32,
+    pub name: String,
+    #[ts(type = "number")]
+    pub start_time: u64,
+    pub samples: Vec<ProcessSample>,
+}
+
+/// A process holding the monitored log file open for writing.
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct FileWriter {
+    pub pid: u32,
+    pub name: String,
+    /// Descriptors open on the file.
+    pub fds: Vec<u32>,
+    /// Attached when requested, without rates. In `file_writers` events it
+    /// also leaves out the environment and sockets.
+    pub detail: Option<ProcessDetail>,
+}
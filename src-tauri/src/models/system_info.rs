@@ -1,34 +1,404 @@
 use serde::Serialize;
+use std::collections::BTreeMap;
+use ts_rs::TS;
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
 pub struct SystemInfo {
     pub cpu_usage: f32,
+    #[ts(type = "number")]
+    pub cpu_frequency_mhz: u64,
+    pub cpu_cores: Vec<CpuCoreInfo>,
+    pub physical_core_count: Option<usize>,
+    pub logical_core_count: usize,
+    pub load_average: Option<LoadAverage>,
+    #[ts(type = "number")]
     pub total_memory: u64,
+    #[ts(type = "number")]
     pub used_memory: u64,
+    pub memory: MemoryInfo,
+    pub pressure: Option<PressureInfo>,
     pub cpu_temp: Option<f32>,
+    pub sensors: Vec<SensorReading>,
     pub cpu_name: Option<String>,
     pub gpu_name: Option<String>,
     pub gpu_temp: Option<u32>,
     pub gpu_usage: Option<u32>,
+    pub gpus: Vec<GpuInfo>,
+    pub power: Option<PowerInfo>,
+    pub storage: StorageInfo,
+    pub network: NetworkInfo,
     pub processes: Vec<LocalProcessInfo>,
     pub selected_process: Option<ProcessDetail>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct CpuCoreInfo {
+    pub index: usize,
+    pub name: String,
+    pub usage: f32,
+    #[ts(type = "number")]
+    pub frequency_mhz: u64,
+    #[ts(type = "number | null")]
+    pub min_frequency_mhz: Option<u64>,
+    #[ts(type = "number | null")]
+    pub max_frequency_mhz: Option<u64>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// Memory in bytes. `available` estimates what can be allocated without
+/// swapping, i.e. `free` plus reclaimable cache.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct MemoryInfo {
+    #[ts(type = "number")]
+    pub total: u64,
+    #[ts(type = "number")]
+    pub used: u64,
+    #[ts(type = "number")]
+    pub free: u64,
+    #[ts(type = "number")]
+    pub available: u64,
+    /// Linux only.
+    #[ts(type = "number | null")]
+    pub buffers: Option<u64>,
+    /// Page cache plus reclaimable slab. Linux only.
+    #[ts(type = "number | null")]
+    pub cached: Option<u64>,
+    #[ts(type = "number")]
+    pub swap_total: u64,
+    #[ts(type = "number")]
+    pub swap_used: u64,
+    #[ts(type = "number")]
+    pub swap_free: u64,
+}
+
+/// Linux pressure stall information per resource.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct PressureInfo {
+    pub cpu: Option<PressureStat>,
+    pub memory: Option<PressureStat>,
+    pub io: Option<PressureStat>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct PressureStat {
+    /// Share of time at least one task was stalled.
+    pub some: PressureLine,
+    /// Share of time all non-idle tasks were stalled at once.
+    pub full: Option<PressureLine>,
+}
+
+/// Stall percentages over the last 10s/60s/300s, plus the total stall time.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct PressureLine {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    #[ts(type = "number")]
+    pub total_us: u64,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+}
+
+/// One hardware sensor. Values are in °C, RPM or volts depending on `kind`.
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct SensorReading {
+    /// Stable across reboots, e.g. `hwmon/k10temp/0000:00:18.3/temp1`.
+    pub id: String,
+    pub kind: SensorKind,
+    /// hwmon chip name or thermal zone type.
+    pub chip: String,
+    pub label: Option<String>,
+    pub value: f64,
+    pub high: Option<f64>,
+    pub critical: Option<f64>,
+}
+
+/// One GPU. Fields a driver does not report are `None`.
+#[derive(Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct GpuInfo {
+    pub index: u32,
+    pub name: String,
+    pub vendor: Option<String>,
+    pub uuid: Option<String>,
+    /// PCI address, e.g. `0000:01:00.0`.
+    pub pci_bus_id: Option<String>,
+    pub temperature: Option<u32>,
+    pub utilization: Option<u32>,
+    #[ts(type = "number | null")]
+    pub memory_used: Option<u64>,
+    #[ts(type = "number | null")]
+    pub memory_total: Option<u64>,
+    pub power_draw_mw: Option<u32>,
+    pub power_limit_mw: Option<u32>,
+    pub fan_speed_percent: Option<u32>,
+    pub graphics_clock_mhz: Option<u32>,
+    pub memory_clock_mhz: Option<u32>,
+    pub sm_clock_mhz: Option<u32>,
+    pub encoder_utilization: Option<u32>,
+    pub decoder_utilization: Option<u32>,
+    /// Active clock throttle reasons, e.g. `sw_power_cap`.
+    pub throttle_reasons: Vec<String>,
+    pub processes: Vec<GpuProcessInfo>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct GpuProcessInfo {
+    pub pid: u32,
+    #[ts(type = "number | null")]
+    pub used_memory: Option<u64>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct PowerInfo {
+    pub battery: Option<BatteryInfo>,
+    pub ac_online: Option<bool>,
+    pub rapl: Vec<RaplZone>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct BatteryInfo {
+    /// `charging`, `discharging`, `full`, `not_charging` or `unknown`.
+    pub state: String,
+    pub charge_percent: f32,
+    #[ts(type = "number | null")]
+    pub time_remaining_secs: Option<u64>,
+}
+
+/// One RAPL power domain, e.g. `package-0` or its `core` subzone.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct RaplZone {
+    pub zone: String,
+    pub name: String,
+    pub is_package: bool,
+    #[ts(type = "number")]
+    pub energy_uj: u64,
+    /// Average since the previous refresh.
+    pub power_watts: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct StorageInfo {
+    pub mounts: Vec<MountUsage>,
+    pub devices: Vec<BlockDeviceIo>,
+}
+
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct MountUsage {
+    pub mount_point: String,
+    pub device: String,
+    pub file_system: String,
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+    #[ts(type = "number")]
+    pub used_bytes: u64,
+    #[ts(type = "number")]
+    pub available_bytes: u64,
+    #[ts(type = "number | null")]
+    pub inodes_total: Option<u64>,
+    #[ts(type = "number | null")]
+    pub inodes_used: Option<u64>,
+    #[ts(type = "number | null")]
+    pub inodes_available: Option<u64>,
+    pub is_removable: bool,
+}
+
+/// Throughput of one block device since the previous refresh.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct BlockDeviceIo {
+    pub name: String,
+    /// Backed by hardware rather than stacked on other devices (dm, md, ...).
+    pub physical: bool,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    #[ts(type = "number")]
+    pub read_bytes_total: u64,
+    #[ts(type = "number")]
+    pub write_bytes_total: u64,
+}
+
+#[derive(Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct NetworkInfo {
+    pub interfaces: Vec<NetworkInterfaceInfo>,
+    /// Open TCP sockets (IPv4 and IPv6) by state, e.g. `established`,
+    /// `time_wait`. Linux only.
+    pub tcp_states: Option<BTreeMap<String, u32>>,
+}
+
+/// Counters of one interface since boot, with rates since the previous
+/// refresh.
+#[derive(Serialize, Clone, TS)]
+#[ts(export)]
+pub struct NetworkInterfaceInfo {
+    pub name: String,
+    pub addresses: Vec<String>,
+    pub is_loopback: bool,
+    #[ts(type = "number")]
+    pub rx_bytes: u64,
+    #[ts(type = "number")]
+    pub tx_bytes: u64,
+    #[ts(type = "number")]
+    pub rx_packets: u64,
+    #[ts(type = "number")]
+    pub tx_packets: u64,
+    #[ts(type = "number")]
+    pub rx_errors: u64,
+    #[ts(type = "number")]
+    pub tx_errors: u64,
+    #[ts(type = "number | null")]
+    pub rx_dropped: Option<u64>,
+    #[ts(type = "number | null")]
+    pub tx_dropped: Option<u64>,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_packets_per_sec: f64,
+    pub tx_packets_per_sec: f64,
+}
+
+/// Everything known about the selected process. Apart from the first few
+/// fields this is read from `/proc/<pid>` and only filled in on Linux, as far
+/// as the app is allowed to read the process.
+#[derive(Serialize, Clone, Default, Debug, TS)]
+#[ts(export)]
 pub struct ProcessDetail {
     pub pid: u32,
     pub name: String,
     pub cpu_usage: f32,
+    #[ts(type = "number")]
     pub memory: u64,
     pub status: String,
     pub exe_path: Option<String>,
     pub command_line: Option<String>,
+    pub cwd: Option<String>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub cgroup: Option<String>,
+    pub memory_breakdown: Option<ProcessMemory>,
+    pub io: Option<ProcessIo>,
+    pub fd_count: Option<usize>,
+    /// At most [`crate::process::detail::MAX_OPEN_FILES`] entries.
+    pub open_files: Vec<OpenFile>,
+    pub sockets: Vec<ProcessSocket>,
+    pub threads: Vec<ThreadInfo>,
+    pub environment: Vec<EnvVar>,
+}
+
+/// Bytes, from `/proc/<pid>/status`.
+#[derive(Serialize, Clone, Default, Debug, TS)]
+#[ts(export)]
+pub struct ProcessMemory {
+    #[ts(type = "number")]
+    pub rss: u64,
+    #[ts(type = "number")]
+    pub vms: u64,
+    /// File-backed and shared-memory resident pages.
+    #[ts(type = "number")]
+    pub shared: u64,
+    #[ts(type = "number")]
+    pub swap: u64,
+}
+
+/// Storage I/O of the process, with rates since the previous detail read.
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct ProcessIo {
+    #[ts(type = "number")]
+    pub read_bytes: u64,
+    #[ts(type = "number")]
+    pub write_bytes: u64,
+    pub read_bytes_per_sec: Option<f64>,
+    pub write_bytes_per_sec: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct OpenFile {
+    pub fd: u32,
+    /// Link target, e.g. a path, `pipe:[123]` or `socket:[456]`.
+    pub target: String,
+}
+
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct ProcessSocket {
+    pub fd: u32,
+    /// `tcp`, `tcp6`, `udp`, `udp6` or `unix`.
+    pub protocol: String,
+    pub local_address: Option<String>,
+    pub remote_address: Option<String>,
+    /// TCP state, e.g. `listen`; `None` for other protocols.
+    pub state: Option<String>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    /// Single-letter state from `/proc/<pid>/task/<tid>/stat`, e.g. `R`, `S`.
+    pub state: String,
+    /// Share of total CPU like [`ProcessDetail::cpu_usage`]; `None` on the
+    /// first read.
+    pub cpu_usage: Option<f32>,
+}
+
+#[derive(Serialize, Clone, Debug, TS)]
+#[ts(export)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+    /// The value looked like a secret and was replaced.
+    pub masked: bool,
+}
+
+#[derive(Serialize, Clone, Default, TS)]
+#[ts(export)]
 pub struct LocalProcessInfo {
     pub pid: u32,
     pub name: String,
     pub cpu_usage: f32,
+    #[ts(type = "number")]
     pub memory: u64,
-}
\ No newline at end of file
+    pub parent_pid: Option<u32>,
+    pub user: Option<String>,
+    /// Seconds since the Unix epoch.
+    #[ts(type = "number")]
+    pub start_time: u64,
+    /// Only known on Linux.
+    pub threads: Option<usize>,
+    /// Linux kernel thread, see [`crate::process::filter::is_kernel_thread`].
+    pub kernel_thread: bool,
+    pub disk_read_bytes_per_sec: f64,
+    pub disk_write_bytes_per_sec: f64,
+}
@@ -0,0 +1,339 @@
+//! A launched command as a log source: its stdout and stderr go through the
+//! same parsing as a followed file, tagged with the stream, and its exit is
+//! reported so it can be restarted.
+//!
+//! On Unix the command gets its own process group and stopping it signals
+//! the whole group, so whatever it spawned (the binary behind `cargo run`,
+//! a wrapper script's server) goes with it.
+
+use super::file_monitor::BATCH_SIZE;
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        command::{CommandExit, CommandSpec, CommandStatus},
+        events::BackendEvent,
+        log_entry::{LogEntry, LogStream},
+    },
+    state::events::EventSink,
+    utils::{encoding::detect_encoding, log_parser::extract_log_level, sync::LockExt},
+};
+use chrono::{Local, Utc};
+use std::{
+    io::{BufRead, BufReader, Read},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How long output is collected before it is sent as one batch.
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+/// Time between the SIGTERM of a stop and the SIGKILL.
+pub const STOP_GRACE: Duration = Duration::from_secs(5);
+/// How long output is still read after the command exited. Children it left
+/// behind may keep the pipes open indefinitely.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+static NEXT_RUN: AtomicU64 = AtomicU64::new(0);
+
+pub struct SupervisedCommand {
+    pub status: CommandStatus,
+    child: Option<Child>,
+    stop_deadline: Option<Instant>,
+    restart_requested: bool,
+    run: u64,
+}
+
+impl SupervisedCommand {
+    pub fn is_running(&self) -> bool {
+        self.child.is_some()
+    }
+}
+
+/// The command launched from the app, at most one at a time. Kept after it
+/// exits so its status stays visible and it can be restarted.
+pub struct CommandMonitorState {
+    pub state: Arc<Mutex<Option<SupervisedCommand>>>,
+}
+
+impl CommandMonitorState {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Default for CommandMonitorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Starts `spec` and a supervisor thread that sends its output to `sink` in
+/// [`BackendEvent::LogsBatch`]es until it exits.
+pub fn launch<S: EventSink + Send + 'static>(
+    state: &Arc<Mutex<Option<SupervisedCommand>>>,
+    spec: CommandSpec,
+    restarts: u32,
+    sink: S,
+) -> AppResult<CommandStatus> {
+    if spec.program.trim().is_empty() {
+        return Err(AppError::InvalidArgument("no program given".to_string()));
+    }
+    let mut current = state.lock_or_recover();
+    if current.as_ref().is_some_and(SupervisedCommand::is_running) {
+        return Err(AppError::InvalidArgument(
+            "a command is already running, stop it first".to_string(),
+        ));
+    }
+
+    let mut command = Command::new(&spec.program);
+    command
+        .args(&spec.args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = &spec.cwd {
+        command.current_dir(cwd);
+    }
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command
+        .spawn()
+        .map_err(|e| AppError::io_context(format!("Failed to start {}", spec.command_line()), e))?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_reader(stdout, LogStream::Stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_reader(stderr, LogStream::Stderr, tx);
+    }
+
+    let status = CommandStatus {
+        command_line: spec.command_line(),
+        spec,
+        pid: Some(child.id()),
+        started_at_ms: Utc::now().timestamp_millis(),
+        exit: None,
+        restarts,
+    };
+    let run = NEXT_RUN.fetch_add(1, Ordering::Relaxed);
+    *current = Some(SupervisedCommand {
+        status: status.clone(),
+        child: Some(child),
+        stop_deadline: None,
+        restart_requested: false,
+        run,
+    });
+    drop(current);
+
+    log::info!("Launched {} (pid {:?})", status.command_line, status.pid);
+    sink.send(BackendEvent::CommandStarted {
+        status: status.clone(),
+    });
+    let state = state.clone();
+    thread::spawn(move || supervise(state, run, rx, sink));
+    Ok(status)
+}
+
+/// Asks the running command to terminate, killing it if it is still alive
+/// after [`STOP_GRACE`].
+pub fn stop(state: &Mutex<Option<SupervisedCommand>>) -> AppResult<()> {
+    let mut current = state.lock_or_recover();
+    let command = current
+        .as_mut()
+        .filter(|c| c.is_running())
+        .ok_or_else(|| AppError::NotFound("no command is running".to_string()))?;
+    request_stop(command);
+    Ok(())
+}
+
+/// Launches the last command again, stopping it first if it still runs.
+pub fn restart<S: EventSink + Send + 'static>(
+    state: &Arc<Mutex<Option<SupervisedCommand>>>,
+    sink: S,
+) -> AppResult<()> {
+    let mut current = state.lock_or_recover();
+    let command = current
+        .as_mut()
+        .ok_or_else(|| AppError::NotFound("no command was launched".to_string()))?;
+    if command.is_running() {
+        // The supervisor relaunches once the command has exited.
+        command.restart_requested = true;
+        request_stop(command);
+        return Ok(());
+    }
+    let spec = command.status.spec.clone();
+    let restarts = command.status.restarts + 1;
+    drop(current);
+    launch(state, spec, restarts, sink).map(|_| ())
+}
+
+fn request_stop(command: &mut SupervisedCommand) {
+    if command.stop_deadline.is_some() {
+        return;
+    }
+    command.stop_deadline = Some(Instant::now() + STOP_GRACE);
+    #[cfg(unix)]
+    let terminated = command
+        .status
+        .pid
+        .is_some_and(|pid| signal_group(pid, libc::SIGTERM));
+    #[cfg(not(unix))]
+    let terminated = false;
+    if !terminated {
+        if let Some(child) = command.child.as_mut() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Reads `stream` line by line into parsed entries until it closes.
+fn spawn_reader<R: Read + Send + 'static>(stream: R, origin: LogStream, tx: Sender<LogEntry>) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("Reading {:?} of launched command failed: {}", origin, e);
+                    break;
+                }
+            }
+            let (line, _, _) = detect_encoding(&buf).decode(&buf);
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let (level, message) = extract_log_level(trimmed);
+            let entry = LogEntry {
+                timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                level,
+                message,
+                stream: Some(origin),
+            };
+            if tx.send(entry).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn supervise<S: EventSink + Send + 'static>(
+    state: Arc<Mutex<Option<SupervisedCommand>>>,
+    run: u64,
+    lines: Receiver<LogEntry>,
+    sink: S,
+) {
+    let mut batch = Vec::new();
+    let mut open = true;
+    let mut exited_at: Option<Instant> = None;
+
+    loop {
+        let deadline = Instant::now() + FLUSH_INTERVAL;
+        while open && batch.len() < BATCH_SIZE {
+            match lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(entry) => batch.push(entry),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => open = false,
+            }
+        }
+        if !batch.is_empty() {
+            sink.check_log_alerts(&batch);
+            sink.send(BackendEvent::LogsBatch {
+                entries: std::mem::take(&mut batch),
+            });
+        }
+
+        if exited_at.is_none() && poll_exit(&state, run) {
+            exited_at = Some(Instant::now());
+        }
+        match exited_at {
+            Some(at) if !open || at.elapsed() >= DRAIN_TIMEOUT => break,
+            // Both pipes closed but the process lives on.
+            None if !open => thread::sleep(FLUSH_INTERVAL),
+            _ => {}
+        }
+    }
+
+    let (status, restart) = {
+        let current = state.lock_or_recover();
+        match current.as_ref().filter(|c| c.run == run) {
+            Some(command) => (command.status.clone(), command.restart_requested),
+            None => return,
+        }
+    };
+    log::info!("{} exited: {:?}", status.command_line, status.exit);
+    sink.send(BackendEvent::CommandExited {
+        status: status.clone(),
+    });
+
+    if restart {
+        if let Err(e) = launch(&state, status.spec, status.restarts + 1, sink) {
+            log::error!("Restarting {} failed: {}", status.command_line, e);
+        }
+    }
+}
+
+/// Reaps the command if it has exited, killing it first once a stop's grace
+/// period is over. Returns whether it is gone.
+fn poll_exit(state: &Mutex<Option<SupervisedCommand>>, run: u64) -> bool {
+    let mut current = state.lock_or_recover();
+    let Some(command) = current.as_mut().filter(|c| c.run == run) else {
+        return true;
+    };
+    let Some(child) = command.child.as_mut() else {
+        return true;
+    };
+    if command.stop_deadline.is_some_and(|d| Instant::now() >= d) {
+        #[cfg(unix)]
+        signal_group(child.id(), libc::SIGKILL);
+        let _ = child.kill();
+    }
+
+    let exit = match child.try_wait() {
+        Ok(None) => return false,
+        Ok(Some(status)) => exit_info(status),
+        Err(e) => {
+            log::warn!("Waiting for {} failed: {}", command.status.command_line, e);
+            CommandExit {
+                code: None,
+                signal: None,
+                at_ms: Utc::now().timestamp_millis(),
+            }
+        }
+    };
+    command.child = None;
+    command.stop_deadline = None;
+    command.status.pid = None;
+    command.status.exit = Some(exit);
+    true
+}
+
+/// Signals the process group led by `pid`.
+#[cfg(unix)]
+fn signal_group(pid: u32, signal: libc::c_int) -> bool {
+    // SAFETY: killpg has no memory-safety preconditions.
+    unsafe { libc::killpg(pid as libc::pid_t, signal) == 0 }
+}
+
+fn exit_info(status: ExitStatus) -> CommandExit {
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+    #[cfg(not(unix))]
+    let signal = None;
+    CommandExit {
+        code: status.code(),
+        signal,
+        at_ms: Utc::now().timestamp_millis(),
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    models::system_info::{CpuCoreInfo, LoadAverage},
+    utils::sysfs,
+};
+use sysinfo::{CpuExt, System, SystemExt};
+
+pub fn cores(sys: &System) -> Vec<CpuCoreInfo> {
+    sys.cpus()
+        .iter()
+        .enumerate()
+        .map(|(index, cpu)| {
+            let (min_frequency_mhz, max_frequency_mhz) = frequency_bounds(index);
+            CpuCoreInfo {
+                index,
+                name: cpu.name().to_string(),
+                usage: cpu.cpu_usage(),
+                frequency_mhz: cpu.frequency(),
+                min_frequency_mhz,
+                max_frequency_mhz,
+            }
+        })
+        .collect()
+}
+
+/// Hardware frequency limits of a logical CPU in MHz, from cpufreq on Linux.
+pub fn frequency_bounds(index: usize) -> (Option<u64>, Option<u64>) {
+    if !cfg!(target_os = "linux") {
+        return (None, None);
+    }
+    let base = format!("/sys/devices/system/cpu/cpu{}/cpufreq", index);
+    let khz_to_mhz = |khz: u64| khz / 1000;
+    (
+        sysfs::read_u64(format!("{}/cpuinfo_min_freq", base)).map(khz_to_mhz),
+        sysfs::read_u64(format!("{}/cpuinfo_max_freq", base)).map(khz_to_mhz),
+    )
+}
+
+/// 1/5/15 minute load averages; Windows has no equivalent and reports zeros,
+/// so it gets `None` instead.
+pub fn load_average(sys: &System) -> Option<LoadAverage> {
+    if cfg!(windows) {
+        return None;
+    }
+    let load = sys.load_average();
+    Some(LoadAverage {
+        one: load.one,
+        five: load.five,
+        fifteen: load.fifteen,
+    })
+}
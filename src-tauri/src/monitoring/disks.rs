@@ -0,0 +1,91 @@
+use super::rates::{per_sec, RateWindow};
+use crate::models::system_info::{BlockDeviceIo, MountUsage};
+use std::{collections::BTreeMap, path::Path};
+use sysinfo::{DiskExt, System, SystemExt};
+use systemstat::{BlockDeviceStats, Platform};
+
+/// `/proc/diskstats` always counts 512-byte sectors.
+const SECTOR_SIZE: u64 = 512;
+
+pub type BlockDeviceCounters = BTreeMap<String, BlockDeviceStats>;
+
+/// Space per mounted filesystem from sysinfo, with inode counts from
+/// systemstat where the platform reports them.
+pub fn mounts(sys: &System, stats: &systemstat::System) -> Vec<MountUsage> {
+    let filesystems = stats.mounts().unwrap_or_default();
+
+    sys.disks()
+        .iter()
+        .map(|disk| {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let inodes = filesystems
+                .iter()
+                .find(|fs| fs.fs_mounted_on == mount_point && fs.files_total > 0);
+            let total_bytes = disk.total_space();
+            let available_bytes = disk.available_space();
+
+            MountUsage {
+                device: disk.name().to_string_lossy().to_string(),
+                file_system: String::from_utf8_lossy(disk.file_system()).to_string(),
+                total_bytes,
+                used_bytes: total_bytes.saturating_sub(available_bytes),
+                available_bytes,
+                inodes_total: inodes.map(|fs| fs.files_total as u64),
+                inodes_used: inodes.map(|fs| fs.files as u64),
+                inodes_available: inodes.map(|fs| fs.files_avail as u64),
+                is_removable: disk.is_removable(),
+                mount_point,
+            }
+        })
+        .collect()
+}
+
+/// Reads the block device counters, keeping whole devices only: partitions
+/// would double count their disk, and loop/ram devices are noise.
+pub fn read_block_devices(stats: &systemstat::System) -> Option<BlockDeviceCounters> {
+    let mut devices = stats.block_device_statistics().ok()?;
+    devices.retain(|name, _| {
+        !name.starts_with("loop")
+            && !name.starts_with("ram")
+            && Path::new("/sys/block").join(name).exists()
+    });
+    Some(devices)
+}
+
+pub fn devices(window: &RateWindow<BlockDeviceCounters>) -> Vec<BlockDeviceIo> {
+    let Some(current) = window.current() else {
+        return Vec::new();
+    };
+    let previous = window.previous();
+
+    current
+        .values()
+        .map(|dev| {
+            let rate = |pick: fn(&BlockDeviceStats) -> usize, scale: u64| {
+                previous
+                    .and_then(|(prev, secs)| {
+                        let before = prev.get(&dev.name)?;
+                        Some(per_sec(
+                            pick(dev) as u64 * scale,
+                            pick(before) as u64 * scale,
+                            secs,
+                        ))
+                    })
+                    .unwrap_or(0.0)
+            };
+            BlockDeviceIo {
+                name: dev.name.clone(),
+                physical: Path::new("/sys/block")
+                    .join(&dev.name)
+                    .join("device")
+                    .exists(),
+                read_bytes_per_sec: rate(|d| d.read_sectors, SECTOR_SIZE),
+                write_bytes_per_sec: rate(|d| d.write_sectors, SECTOR_SIZE),
+                read_iops: rate(|d| d.read_ios, 1),
+                write_iops: rate(|d| d.write_ios, 1),
+                read_bytes_total: dev.read_sectors as u64 * SECTOR_SIZE,
+                write_bytes_total: dev.write_sectors as u64 * SECTOR_SIZE,
+            }
+        })
+        .collect()
+}
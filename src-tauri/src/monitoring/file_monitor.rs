@@ -1,17 +1,18 @@
 use crate::{
-    models::log_entry::LogEntry,
-    utils::{encoding::detect_encoding, log_parser::extract_log_level},
+    error::{AppError, AppResult},
+    models::{events::BackendEvent, log_entry::LogEntry},
+    state::events::EventSink,
+    utils::{encoding::detect_encoding, log_parser::extract_log_level, sync::LockExt},
 };
 use chrono::Local;
 use std::{
     fs::File,
-    io::{Read, Seek, SeekFrom},
+    io::{BufRead, BufReader, Seek, SeekFrom},
     path::Path,
     sync::{Arc, Mutex},
     thread,
     time::{Duration, SystemTime},
 };
-use tauri::{AppHandle, Emitter};
 
 pub const SLEEP_DURATION: Duration = Duration::from_millis(200);
 pub const BATCH_SIZE: usize = 568;
@@ -22,15 +23,39 @@ pub struct FileMonitorState {
     pub current_offset: u64,
     pub initial_hash: Option<[u8; 32]>,
     pub last_modified: Option<SystemTime>,
+    /// Emit the processes writing the file, with their detail, alongside
+    /// the metrics samples.
+    pub attach_writers: bool,
 }
 
 pub struct MonitoringState {
     pub state: Arc<Mutex<FileMonitorState>>,
 }
-pub fn run_monitoring_loop(
+
+impl MonitoringState {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FileMonitorState {
+                is_running: false,
+                current_file: None,
+                current_offset: 0,
+                initial_hash: None,
+                last_modified: None,
+                attach_writers: false,
+            })),
+        }
+    }
+}
+
+impl Default for MonitoringState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+pub fn run_monitoring_loop<S: EventSink>(
     file_path: String,
     state: Arc<Mutex<FileMonitorState>>,
-    app_handle: AppHandle,
+    sink: S,
     mut offset: u64,
 ) {
     let path = Path::new(&file_path);
@@ -38,7 +63,7 @@ pub fn run_monitoring_loop(
     let mut file = match File::open(path) {
         Ok(f) => f,
         Err(e) => {
-            emit_error(&app_handle, format!("Failed to open file: {}", e));
+            emit_error(&sink, AppError::io_context("Failed to open file", e));
             return;
         }
     };
@@ -48,9 +73,9 @@ pub fn run_monitoring_loop(
 
     loop {
         {
-            let monitor = state.lock().unwrap();
+            let monitor = state.lock_or_recover();
             if !monitor.is_running || monitor.current_file.as_ref() != Some(&file_path) {
-                println!("[MONITOR] Monitoring stopped for: {}", file_path);
+                log::info!("Monitoring stopped for: {}", file_path);
                 break;
             }
         }
@@ -58,7 +83,7 @@ pub fn run_monitoring_loop(
         let metadata = match path.metadata() {
             Ok(m) => m,
             Err(e) => {
-                emit_error(&app_handle, format!("Failed to get metadata: {}", e));
+                emit_error(&sink, AppError::io_context("Failed to get metadata", e));
                 thread::sleep(SLEEP_DURATION);
                 continue;
             }
@@ -67,12 +92,11 @@ pub fn run_monitoring_loop(
         let current_size = metadata.len();
 
         if current_size < last_size {
-            println!("[MONITOR] File truncated. Resetting offset.");
+            log::info!("File truncated, resetting offset: {}", file_path);
             if file.seek(SeekFrom::Start(0)).is_ok() {
                 offset = 0;
-                let mut monitor = state.lock().unwrap();
-                monitor.current_offset = 0;
-                emit_event(&app_handle, "file_cleared", ());
+                state.lock_or_recover().current_offset = 0;
+                sink.send(BackendEvent::FileCleared);
             }
         }
 
@@ -80,10 +104,8 @@ pub fn run_monitoring_loop(
 
         if offset < current_size {
             if !first_cycle {
-                if let Err(e) =
-                    process_new_data(&mut file, offset, &state, &app_handle, &mut offset)
-                {
-                    emit_error(&app_handle, e);
+                if let Err(e) = process_new_data(&mut file, offset, &state, &sink, &mut offset) {
+                    emit_error(&sink, e);
                 }
             } else {
                 first_cycle = false;
@@ -93,79 +115,91 @@ pub fn run_monitoring_loop(
         thread::sleep(SLEEP_DURATION);
     }
 
-    println!("[MONITOR] Monitoring thread exited.");
+    log::debug!("Monitoring thread exited: {}", file_path);
 }
 
-fn process_new_data(
+/// Reads everything past `offset` line by line, parses it into [`LogEntry`]
+/// batches for `sink`, checking each against the log alert rules, and
+/// advances `new_offset` / the shared state by the bytes consumed. Only the
+/// current batch is held in memory, so reading a large file from the start
+/// streams through it.
+pub fn process_new_data<S: EventSink>(
     file: &mut File,
     offset: u64,
-    state: &Arc<Mutex<FileMonitorState>>,
-    app_handle: &AppHandle,
+    state: &Mutex<FileMonitorState>,
+    sink: &S,
     new_offset: &mut u64,
-) -> Result<(), String> {
-    let mut buffer = Vec::new();
-
+) -> AppResult<()> {
     file.seek(SeekFrom::Start(offset))
-        .map_err(|e| format!("Seek failed: {}", e))?;
-    let bytes_read = file
-        .read_to_end(&mut buffer)
-        .map_err(|e| format!("Read failed: {}", e))?;
-
-    if bytes_read == 0 {
-        return Ok(()); 
-    }
-
-    *new_offset += bytes_read as u64;
-
-    {
-        let mut monitor = state.lock().unwrap();
-        monitor.current_offset = *new_offset;
-    }
-
-    let encoding = detect_encoding(&buffer);
-    let (cow, _enc, had_errors) = encoding.decode(&buffer);
-    if had_errors {
-        log::warn!("Encoding issues detected");
-    }
+        .map_err(|e| AppError::io_context("Seek failed", e))?;
+    let mut reader = BufReader::new(file);
 
     let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-    let content = cow.into_owned();
+    let mut line = Vec::new();
     let mut batch = Vec::new();
+    let mut had_errors = false;
 
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
+    loop {
+        line.clear();
+        let bytes_read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| AppError::io_context("Read failed", e))?;
+        if bytes_read == 0 {
+            break;
         }
+        *new_offset += bytes_read as u64;
 
-        let (level, message) = extract_log_level(trimmed);
-        batch.push(LogEntry {
-            timestamp: now.clone(),
-            level,
-            message: message.to_string(),
-        });
+        let Some(entry) = decode_entry(&line, &now, &mut had_errors) else {
+            continue;
+        };
+        batch.push(entry);
 
         if batch.len() >= BATCH_SIZE {
-            emit_event(app_handle, "new_logs_batch", batch.clone());
-            batch.clear();
+            state.lock_or_recover().current_offset = *new_offset;
+            sink.check_log_alerts(&batch);
+            sink.send(BackendEvent::LogsBatch {
+                entries: std::mem::take(&mut batch),
+            });
         }
     }
 
+    state.lock_or_recover().current_offset = *new_offset;
+    if had_errors {
+        log::warn!("Encoding issues detected");
+    }
     if !batch.is_empty() {
-        emit_event(app_handle, "new_logs_batch", batch);
+        sink.check_log_alerts(&batch);
+        sink.send(BackendEvent::LogsBatch { entries: batch });
     }
 
     Ok(())
 }
 
-fn emit_error(app_handle: &AppHandle, message: impl ToString) {
-    let _ = app_handle.emit("monitoring_error", message.to_string());
+/// Decodes one raw line (UTF-8 or windows-1251) into a [`LogEntry`] read at
+/// `timestamp`; `None` for blank lines. Sets `had_errors` when the bytes did
+/// not decode cleanly.
+pub fn decode_entry(line: &[u8], timestamp: &str, had_errors: &mut bool) -> Option<LogEntry> {
+    let (text, _enc, errors) = detect_encoding(line).decode(line);
+    *had_errors |= errors;
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let (level, message) = extract_log_level(trimmed);
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level,
+        message,
+        stream: None,
+    })
 }
 
-fn emit_event<T: serde::Serialize + Clone>(app_handle: &AppHandle, event: &str, payload: T) {
-    let _ = app_handle.emit(event, payload);
+fn emit_error<S: EventSink>(sink: &S, err: AppError) {
+    log::error!("Monitoring error: {}", err);
+    sink.send(BackendEvent::monitoring_error(&err));
 }
 
 pub fn get_file_size(path: &Path) -> u64 {
     path.metadata().map(|m| m.len()).unwrap_or(0)
-}
\ No newline at end of file
+}
@@ -0,0 +1,140 @@
+mod nvml;
+mod sysfs;
+
+pub use self::{nvml::NvmlProvider, sysfs::SysfsProvider};
+
+use crate::models::system_info::GpuInfo;
+
+/// Source of per-GPU metrics. Kept behind a trait so the rest of the
+/// pipeline can run against a fake on machines without a GPU.
+pub trait GpuProvider: Send + Sync {
+    /// Short name for logs, e.g. `nvml`.
+    fn name(&self) -> &'static str;
+
+    /// Current metrics of every GPU, in device order.
+    fn gpus(&self) -> Vec<GpuInfo>;
+}
+
+/// The devices of a `primary` provider followed by those of a `secondary`
+/// one that the primary does not already report, matched by PCI address.
+/// Devices are numbered in that order.
+pub struct MergedProvider {
+    pub primary: Box<dyn GpuProvider>,
+    pub secondary: Box<dyn GpuProvider>,
+}
+
+impl GpuProvider for MergedProvider {
+    fn name(&self) -> &'static str {
+        "merged"
+    }
+
+    fn gpus(&self) -> Vec<GpuInfo> {
+        let mut gpus = self.primary.gpus();
+        let covered: Vec<String> = gpus.iter().filter_map(|g| g.pci_bus_id.clone()).collect();
+        gpus.extend(self.secondary.gpus().into_iter().filter(|g| {
+            g.pci_bus_id
+                .as_ref()
+                .map_or(true, |id| !covered.contains(id))
+        }));
+        for (index, gpu) in gpus.iter_mut().enumerate() {
+            gpu.index = index as u32;
+        }
+        gpus
+    }
+}
+
+/// Picks the GPU providers for this machine: NVML for NVIDIA devices and,
+/// on Linux, sysfs for the cards NVML does not cover, such as an integrated
+/// Intel or AMD GPU next to an NVIDIA one.
+pub fn detect() -> Option<Box<dyn GpuProvider>> {
+    let nvml: Option<Box<dyn GpuProvider>> = match NvmlProvider::init() {
+        Ok(provider) if !provider.gpus().is_empty() => Some(Box::new(provider)),
+        Ok(_) => {
+            log::info!("NVML found no devices");
+            None
+        }
+        Err(e) => {
+            log::info!("NVML unavailable: {}", e);
+            None
+        }
+    };
+    let sysfs = if cfg!(target_os = "linux") {
+        let provider = SysfsProvider::new("/sys");
+        (!provider.gpus().is_empty()).then(|| Box::new(provider) as Box<dyn GpuProvider>)
+    } else {
+        None
+    };
+
+    match (nvml, sysfs) {
+        (Some(primary), Some(secondary)) => Some(Box::new(MergedProvider { primary, secondary })),
+        (Some(provider), None) | (None, Some(provider)) => Some(provider),
+        (None, None) => {
+            log::info!("No GPU found, GPU metrics disabled");
+            None
+        }
+    }
+}
+
+/// Lowercases a PCI address and shortens its domain to four digits, as
+/// sysfs writes it: NVML reports `00000000:01:00.0` for `0000:01:00.0`.
+fn normalize_bus_id(bus_id: &str) -> String {
+    let bus_id = bus_id.trim().to_ascii_lowercase();
+    match bus_id.split_once(':') {
+        Some((domain, rest)) => match u32::from_str_radix(domain, 16) {
+            Ok(domain) => format!("{:04x}:{}", domain, rest),
+            Err(_) => bus_id,
+        },
+        None => bus_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeGpus(Vec<GpuInfo>);
+
+    impl GpuProvider for FakeGpus {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn gpus(&self) -> Vec<GpuInfo> {
+            self.0.clone()
+        }
+    }
+
+    fn gpu(index: u32, name: &str, pci_bus_id: Option<&str>) -> GpuInfo {
+        GpuInfo {
+            index,
+            name: name.to_string(),
+            pci_bus_id: pci_bus_id.map(str::to_string),
+            ..GpuInfo::default()
+        }
+    }
+
+    #[test]
+    fn merged_provider_skips_cards_the_primary_covers() {
+        let merged = MergedProvider {
+            primary: Box::new(FakeGpus(vec![gpu(0, "RTX", Some("0000:01:00.0"))])),
+            secondary: Box::new(FakeGpus(vec![
+                gpu(0, "Intel iGPU", Some("0000:00:02.0")),
+                gpu(1, "RTX via sysfs", Some("0000:01:00.0")),
+                gpu(2, "No address", None),
+            ])),
+        };
+
+        let gpus = merged.gpus();
+        let names: Vec<&str> = gpus.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["RTX", "Intel iGPU", "No address"]);
+        let indices: Vec<u32> = gpus.iter().map(|g| g.index).collect();
+        assert_eq!(indices, [0, 1, 2]);
+    }
+
+    #[test]
+    fn bus_ids_are_normalized_to_the_sysfs_form() {
+        assert_eq!(normalize_bus_id("00000000:01:00.0"), "0000:01:00.0");
+        assert_eq!(normalize_bus_id("0000:0A:00.0\n"), "0000:0a:00.0");
+        assert_eq!(normalize_bus_id("garbage"), "garbage");
+    }
+}
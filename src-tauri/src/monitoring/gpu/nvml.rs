@@ -0,0 +1,137 @@
+use super::GpuProvider;
+use crate::models::system_info::{GpuInfo, GpuProcessInfo};
+use nvml_wrapper::{
+    bitmasks::device::ThrottleReasons,
+    enum_wrappers::device::{Clock, TemperatureSensor},
+    enums::device::UsedGpuMemory,
+    error::NvmlError,
+    Device, Nvml,
+};
+use std::collections::BTreeMap;
+
+const THROTTLE_REASONS: [(ThrottleReasons, &str); 9] = [
+    (ThrottleReasons::GPU_IDLE, "gpu_idle"),
+    (
+        ThrottleReasons::APPLICATIONS_CLOCKS_SETTING,
+        "applications_clocks_setting",
+    ),
+    (ThrottleReasons::SW_POWER_CAP, "sw_power_cap"),
+    (ThrottleReasons::HW_SLOWDOWN, "hw_slowdown"),
+    (ThrottleReasons::SYNC_BOOST, "sync_boost"),
+    (ThrottleReasons::SW_THERMAL_SLOWDOWN, "sw_thermal_slowdown"),
+    (ThrottleReasons::HW_THERMAL_SLOWDOWN, "hw_thermal_slowdown"),
+    (
+        ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN,
+        "hw_power_brake_slowdown",
+    ),
+    (
+        ThrottleReasons::DISPLAY_CLOCK_SETTING,
+        "display_clock_setting",
+    ),
+];
+
+/// NVIDIA GPUs through NVML.
+pub struct NvmlProvider {
+    nvml: Nvml,
+}
+
+impl NvmlProvider {
+    pub fn init() -> Result<Self, NvmlError> {
+        Ok(Self {
+            nvml: Nvml::init()?,
+        })
+    }
+}
+
+impl GpuProvider for NvmlProvider {
+    fn name(&self) -> &'static str {
+        "nvml"
+    }
+
+    fn gpus(&self) -> Vec<GpuInfo> {
+        let count = match self.nvml.device_count() {
+            Ok(count) => count,
+            Err(e) => {
+                log::debug!("NVML device count failed: {}", e);
+                return Vec::new();
+            }
+        };
+        (0..count)
+            .filter_map(|index| match self.nvml.device_by_index(index) {
+                Ok(device) => Some(device_info(index, &device)),
+                Err(e) => {
+                    log::debug!("NVML device {} unavailable: {}", index, e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Every query is optional: consumer cards and older drivers report
+/// `NotSupported` for many of them.
+fn device_info(index: u32, device: &Device) -> GpuInfo {
+    let memory = device.memory_info().ok();
+    let fan_speed_percent = device
+        .num_fans()
+        .ok()
+        .filter(|&fans| fans > 0)
+        .and_then(|_| device.fan_speed(0).ok());
+    let throttle_reasons = device
+        .current_throttle_reasons()
+        .map(|reasons| {
+            THROTTLE_REASONS
+                .iter()
+                .filter(|(flag, _)| reasons.contains(*flag))
+                .map(|(_, name)| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    GpuInfo {
+        index,
+        name: device.name().unwrap_or_else(|_| format!("GPU {}", index)),
+        vendor: Some("NVIDIA".to_string()),
+        uuid: device.uuid().ok(),
+        pci_bus_id: device
+            .pci_info()
+            .ok()
+            .map(|pci| super::normalize_bus_id(&pci.bus_id)),
+        temperature: device.temperature(TemperatureSensor::Gpu).ok(),
+        utilization: device.utilization_rates().map(|u| u.gpu).ok(),
+        memory_used: memory.as_ref().map(|m| m.used),
+        memory_total: memory.as_ref().map(|m| m.total),
+        power_draw_mw: device.power_usage().ok(),
+        power_limit_mw: device.enforced_power_limit().ok(),
+        fan_speed_percent,
+        graphics_clock_mhz: device.clock_info(Clock::Graphics).ok(),
+        memory_clock_mhz: device.clock_info(Clock::Memory).ok(),
+        sm_clock_mhz: device.clock_info(Clock::SM).ok(),
+        encoder_utilization: device.encoder_utilization().map(|u| u.utilization).ok(),
+        decoder_utilization: device.decoder_utilization().map(|u| u.utilization).ok(),
+        throttle_reasons,
+        processes: processes(device),
+    }
+}
+
+/// Compute and graphics processes merged by pid; a process doing both is
+/// listed once.
+fn processes(device: &Device) -> Vec<GpuProcessInfo> {
+    let mut by_pid: BTreeMap<u32, Option<u64>> = BTreeMap::new();
+    let compute = device.running_compute_processes().unwrap_or_default();
+    let graphics = device.running_graphics_processes().unwrap_or_default();
+
+    for process in compute.into_iter().chain(graphics) {
+        let used = match process.used_gpu_memory {
+            UsedGpuMemory::Used(bytes) => Some(bytes),
+            UsedGpuMemory::Unavailable => None,
+        };
+        let entry = by_pid.entry(process.pid).or_insert(None);
+        *entry = (*entry).max(used);
+    }
+
+    by_pid
+        .into_iter()
+        .map(|(pid, used_memory)| GpuProcessInfo { pid, used_memory })
+        .collect()
+}
@@ -0,0 +1,192 @@
+use super::GpuProvider;
+use crate::{models::system_info::GpuInfo, utils::sysfs};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const VENDOR_AMD: &str = "0x1002";
+const VENDOR_INTEL: &str = "0x8086";
+const VENDOR_NVIDIA: &str = "0x10de";
+
+/// AMD and Intel GPUs through the DRM and hwmon entries in sysfs (Linux).
+///
+/// `root` is where sysfs is mounted, so a fake tree can stand in for `/sys`:
+///
+/// ```
+/// use app_lib::monitoring::gpu::{GpuProvider, SysfsProvider};
+/// use std::fs;
+///
+/// let root = std::env::temp_dir().join(format!("gpu-sysfs-doctest-{}", std::process::id()));
+/// let device = root.join("class/drm/card0/device");
+/// let hwmon = device.join("hwmon/hwmon3");
+/// fs::create_dir_all(&hwmon).unwrap();
+/// for (file, value) in [
+///     (device.join("vendor"), "0x1002"),
+///     (device.join("uevent"), "DRIVER=amdgpu\nPCI_ID=1002:73BF\nPCI_SLOT_NAME=0000:03:00.0"),
+///     (device.join("gpu_busy_percent"), "37"),
+///     (device.join("mem_info_vram_used"), "1073741824"),
+///     (device.join("mem_info_vram_total"), "17163091968"),
+///     (hwmon.join("temp1_input"), "54000"),
+///     (hwmon.join("power1_average"), "123000000"),
+/// ] {
+///     fs::write(file, value).unwrap();
+/// }
+/// // Connectors live next to the cards and are not GPUs.
+/// fs::create_dir_all(root.join("class/drm/card0-DP-1")).unwrap();
+///
+/// let gpus = SysfsProvider::new(&root).gpus();
+/// assert_eq!(gpus.len(), 1);
+/// assert_eq!(gpus[0].vendor.as_deref(), Some("AMD"));
+/// assert_eq!(gpus[0].utilization, Some(37));
+/// assert_eq!(gpus[0].memory_used, Some(1 << 30));
+/// assert_eq!(gpus[0].temperature, Some(54));
+/// assert_eq!(gpus[0].power_draw_mw, Some(123_000));
+/// assert_eq!(gpus[0].pci_bus_id.as_deref(), Some("0000:03:00.0"));
+/// # fs::remove_dir_all(&root).unwrap();
+/// ```
+pub struct SysfsProvider {
+    root: PathBuf,
+}
+
+impl SysfsProvider {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `cardN` directories, skipping connectors such as `card0-DP-1`.
+    fn cards(&self) -> Vec<(u32, PathBuf)> {
+        let Ok(entries) = fs::read_dir(self.root.join("class/drm")) else {
+            return Vec::new();
+        };
+        let mut cards: Vec<(u32, PathBuf)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let index = name.to_str()?.strip_prefix("card")?.parse().ok()?;
+                Some((index, entry.path()))
+            })
+            .collect();
+        cards.sort_by_key(|(index, _)| *index);
+        cards
+    }
+}
+
+impl GpuProvider for SysfsProvider {
+    fn name(&self) -> &'static str {
+        "sysfs"
+    }
+
+    fn gpus(&self) -> Vec<GpuInfo> {
+        self.cards()
+            .into_iter()
+            .filter_map(|(index, card)| card_info(index, &card))
+            .collect()
+    }
+}
+
+fn card_info(index: u32, card: &Path) -> Option<GpuInfo> {
+    let device = card.join("device");
+    let vendor_id = sysfs::read_trimmed(device.join("vendor"))?;
+    let vendor = match vendor_id.as_str() {
+        VENDOR_AMD => "AMD",
+        VENDOR_INTEL => "Intel",
+        VENDOR_NVIDIA => "NVIDIA",
+        _ => "Unknown",
+    };
+    let driver = uevent_value(&device, "DRIVER").unwrap_or_else(|| "unknown".to_string());
+    let hwmon = hwmon_dir(&device);
+    let hwmon_u64 = |file: &str| hwmon.as_ref().and_then(|h| sysfs::read_u64(h.join(file)));
+
+    let name = sysfs::read_trimmed(device.join("product_name"))
+        .unwrap_or_else(|| format!("{} GPU ({})", vendor, driver));
+
+    // i915/xe report the actual GPU frequency next to the card, amdgpu in
+    // hwmon (in Hz).
+    let graphics_clock_mhz = sysfs::read_u64(card.join("gt_act_freq_mhz"))
+        .or_else(|| hwmon_u64("freq1_input").map(|hz| hz / 1_000_000));
+
+    Some(GpuInfo {
+        index,
+        name,
+        vendor: Some(vendor.to_string()),
+        uuid: sysfs::read_trimmed(device.join("unique_id")),
+        pci_bus_id: uevent_value(&device, "PCI_SLOT_NAME").map(|id| super::normalize_bus_id(&id)),
+        temperature: hwmon_u64("temp1_input").map(|millideg| (millideg / 1000) as u32),
+        utilization: sysfs::read_u64(device.join("gpu_busy_percent")).map(|p| p as u32),
+        memory_used: sysfs::read_u64(device.join("mem_info_vram_used")),
+        memory_total: sysfs::read_u64(device.join("mem_info_vram_total")),
+        power_draw_mw: hwmon_u64("power1_average")
+            .or_else(|| hwmon_u64("power1_input"))
+            .map(microwatts_to_milliwatts),
+        power_limit_mw: hwmon_u64("power1_cap").map(microwatts_to_milliwatts),
+        fan_speed_percent: hwmon_u64("pwm1").map(|pwm| (pwm * 100 / 255) as u32),
+        graphics_clock_mhz: graphics_clock_mhz.map(|mhz| mhz as u32),
+        memory_clock_mhz: hwmon_u64("freq2_input").map(|hz| (hz / 1_000_000) as u32),
+        ..GpuInfo::default()
+    })
+}
+
+fn microwatts_to_milliwatts(microwatts: u64) -> u32 {
+    (microwatts / 1000) as u32
+}
+
+fn uevent_value(device: &Path, key: &str) -> Option<String> {
+    let uevent = fs::read_to_string(device.join("uevent")).ok()?;
+    uevent.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+fn hwmon_dir(device: &Path) -> Option<PathBuf> {
+    fs::read_dir(device.join("hwmon"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intel_igpu_reports_its_clock_and_nothing_it_lacks() {
+        let root = std::env::temp_dir().join(format!("gpu-sysfs-i915-{}", std::process::id()));
+        let card = root.join("class/drm/card1");
+        let device = card.join("device");
+        fs::create_dir_all(&device).unwrap();
+        for (file, value) in [
+            (device.join("vendor"), "0x8086\n"),
+            (
+                device.join("uevent"),
+                "DRIVER=i915\nPCI_ID=8086:A7A0\nPCI_SLOT_NAME=0000:00:02.0\n",
+            ),
+            (card.join("gt_act_freq_mhz"), "350\n"),
+            (card.join("gt_max_freq_mhz"), "1300\n"),
+        ] {
+            fs::write(file, value).unwrap();
+        }
+
+        let gpus = SysfsProvider::new(&root).gpus();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(gpus.len(), 1);
+        let gpu = &gpus[0];
+        assert_eq!(gpu.index, 1);
+        assert_eq!(gpu.name, "Intel GPU (i915)");
+        assert_eq!(gpu.vendor.as_deref(), Some("Intel"));
+        assert_eq!(gpu.pci_bus_id.as_deref(), Some("0000:00:02.0"));
+        // The current frequency, not the maximum.
+        assert_eq!(gpu.graphics_clock_mhz, Some(350));
+        // No busy counter, VRAM, hwmon or fan on an integrated GPU.
+        assert_eq!(gpu.utilization, None);
+        assert_eq!(gpu.memory_used, None);
+        assert_eq!(gpu.memory_total, None);
+        assert_eq!(gpu.temperature, None);
+        assert_eq!(gpu.power_draw_mw, None);
+        assert_eq!(gpu.power_limit_mw, None);
+        assert_eq!(gpu.fan_speed_percent, None);
+        assert_eq!(gpu.memory_clock_mhz, None);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::models::metrics::MetricsSample;
+use std::collections::VecDeque;
+
+/// One hour of samples at the default one-second interval.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 3600;
+
+/// Fixed-size in-memory history of [`MetricsSample`]s, oldest first.
+///
+/// Queries are downsampled by averaging every sample that falls into the same
+/// `resolution`-sized bucket:
+///
+/// ```
+/// use app_lib::{models::metrics::MetricsSample, monitoring::history::MetricsHistory};
+///
+/// let sample = |timestamp_ms, cpu_usage| MetricsSample {
+///     timestamp_ms,
+///     cpu_usage,
+///     ..MetricsSample::default()
+/// };
+///
+/// let mut history = MetricsHistory::new(3);
+/// for (i, cpu) in [10.0, 20.0, 30.0, 40.0].into_iter().enumerate() {
+///     history.push(sample(i as i64 * 1000, cpu));
+/// }
+///
+/// // The first sample fell out of the buffer.
+/// assert_eq!(history.len(), 3);
+///
+/// let buckets = history.query(4000, 60_000, 2000);
+/// let cpu: Vec<f32> = buckets.iter().map(|s| s.cpu_usage).collect();
+/// assert_eq!(cpu, [20.0, 35.0]);
+/// assert_eq!(buckets[1].timestamp_ms, 2000);
+/// ```
+pub struct MetricsHistory {
+    samples: VecDeque<MetricsSample>,
+    capacity: usize,
+}
+
+impl MetricsHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, sample: MetricsSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Samples newer than `now_ms - range_ms`, averaged into buckets of
+    /// `resolution_ms`. A resolution of 0 returns the raw samples.
+    pub fn query(&self, now_ms: i64, range_ms: i64, resolution_ms: i64) -> Vec<MetricsSample> {
+        let since = now_ms - range_ms;
+        let start = self.samples.partition_point(|s| s.timestamp_ms < since);
+        let recent = self.samples.range(start..);
+
+        if resolution_ms <= 0 {
+            return recent.cloned().collect();
+        }
+
+        let mut out = Vec::new();
+        let mut bucket: Vec<MetricsSample> = Vec::new();
+        let mut bucket_start = 0;
+        for sample in recent {
+            let start = sample.timestamp_ms.div_euclid(resolution_ms) * resolution_ms;
+            if start != bucket_start && !bucket.is_empty() {
+                out.extend(MetricsSample::average(bucket_start, &bucket));
+                bucket.clear();
+            }
+            bucket_start = start;
+            bucket.push(sample.clone());
+        }
+        out.extend(MetricsSample::average(bucket_start, &bucket));
+        out
+    }
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::models::system_info::{MemoryInfo, PressureInfo, PressureLine, PressureStat};
+use std::{collections::HashMap, fs};
+use sysinfo::{System, SystemExt};
+
+pub fn memory(sys: &System) -> MemoryInfo {
+    let meminfo = read_meminfo();
+    let field = |name: &str| meminfo.as_ref().and_then(|m| m.get(name).copied());
+
+    MemoryInfo {
+        total: sys.total_memory(),
+        used: sys.used_memory(),
+        free: sys.free_memory(),
+        available: sys.available_memory(),
+        buffers: field("Buffers"),
+        // Reclaimable slab is cache too; `free` counts it the same way.
+        cached: field("Cached").map(|c| c + field("SReclaimable").unwrap_or(0)),
+        swap_total: sys.total_swap(),
+        swap_used: sys.used_swap(),
+        swap_free: sys.free_swap(),
+    }
+}
+
+/// `/proc/meminfo` in bytes; `None` off Linux.
+fn read_meminfo() -> Option<HashMap<String, u64>> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let content = fs::read_to_string("/proc/meminfo").ok()?;
+    Some(
+        content
+            .lines()
+            .filter_map(|line| {
+                let (name, rest) = line.split_once(':')?;
+                let mut parts = rest.split_whitespace();
+                let value: u64 = parts.next()?.parse().ok()?;
+                let scale = if parts.next() == Some("kB") { 1024 } else { 1 };
+                Some((name.to_string(), value * scale))
+            })
+            .collect(),
+    )
+}
+
+/// Pressure stall information from `/proc/pressure`; `None` off Linux or on
+/// kernels built without PSI.
+pub fn pressure() -> Option<PressureInfo> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let read = |resource: &str| {
+        fs::read_to_string(format!("/proc/pressure/{}", resource))
+            .ok()
+            .and_then(|content| parse_pressure(&content))
+    };
+    let info = PressureInfo {
+        cpu: read("cpu"),
+        memory: read("memory"),
+        io: read("io"),
+    };
+    (info.cpu.is_some() || info.memory.is_some() || info.io.is_some()).then_some(info)
+}
+
+/// Parses lines like `some avg10=0.12 avg60=0.05 avg300=0.01 total=123456`.
+fn parse_pressure(content: &str) -> Option<PressureStat> {
+    let mut some = None;
+    let mut full = None;
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        let mut parsed = PressureLine {
+            avg10: 0.0,
+            avg60: 0.0,
+            avg300: 0.0,
+            total_us: 0,
+        };
+        for field in fields {
+            match field.split_once('=') {
+                Some(("avg10", v)) => parsed.avg10 = v.parse().ok()?,
+                Some(("avg60", v)) => parsed.avg60 = v.parse().ok()?,
+                Some(("avg300", v)) => parsed.avg300 = v.parse().ok()?,
+                Some(("total", v)) => parsed.total_us = v.parse().ok()?,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(parsed),
+            Some("full") => full = Some(parsed),
+            _ => {}
+        }
+    }
+    Some(PressureStat { some: some?, full })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn some_and_full_lines_are_parsed() {
+        let stat = parse_pressure(
+            "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456\n\
+             full avg10=0.25 avg60=0.00 avg300=0.00 total=42\n",
+        )
+        .expect("valid pressure file");
+        assert_eq!(stat.some.avg10, 1.5);
+        assert_eq!(stat.some.avg60, 0.75);
+        assert_eq!(stat.some.avg300, 0.1);
+        assert_eq!(stat.some.total_us, 123456);
+        let full = stat.full.expect("full line");
+        assert_eq!(full.avg10, 0.25);
+        assert_eq!(full.total_us, 42);
+    }
+
+    #[test]
+    fn cpu_pressure_may_lack_the_full_line() {
+        let stat = parse_pressure("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n")
+            .expect("valid pressure file");
+        assert!(stat.full.is_none());
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let stat = parse_pressure("some avg10=2.00 avg600=9.00 total=7\n").expect("some line");
+        assert_eq!(stat.some.avg10, 2.0);
+        assert_eq!(stat.some.total_us, 7);
+    }
+
+    #[test]
+    fn malformed_or_missing_some_line_is_rejected() {
+        assert!(parse_pressure("").is_none());
+        assert!(parse_pressure("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").is_none());
+        assert!(parse_pressure("some avg10=high avg60=0.00 avg300=0.00 total=0\n").is_none());
+    }
+}
@@ -1 +1,15 @@
-pub mod file_monitor;
\ No newline at end of file
+pub mod command_monitor;
+pub mod cpu;
+pub mod disks;
+pub mod file_monitor;
+pub mod gpu;
+pub mod history;
+pub mod memory;
+pub mod network;
+pub mod power;
+pub mod process_history;
+pub mod rates;
+#[cfg(feature = "gui")]
+pub mod sampler;
+pub mod sensors;
+pub mod system_metrics;
@@ -0,0 +1,189 @@
+use super::rates::{per_sec, RateWindow};
+use crate::{models::system_info::NetworkInterfaceInfo, utils::sysfs};
+use std::{collections::BTreeMap, fs};
+use systemstat::{IpAddr, Platform};
+
+#[derive(Clone)]
+pub struct InterfaceCounters {
+    pub addresses: Vec<String>,
+    pub is_loopback: bool,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: Option<u64>,
+    pub tx_dropped: Option<u64>,
+}
+
+pub type NetworkCounters = BTreeMap<String, InterfaceCounters>;
+
+/// Reads the counters of every interface systemstat knows about. Drops are
+/// not part of systemstat's stats and come from sysfs on Linux.
+pub fn read_interfaces(stats: &systemstat::System) -> Option<NetworkCounters> {
+    let networks = stats.networks().ok()?;
+    let mut counters = BTreeMap::new();
+
+    for (name, network) in networks {
+        let Ok(net) = stats.network_stats(&name) else {
+            continue;
+        };
+        let addresses: Vec<std::net::IpAddr> = network
+            .addrs
+            .iter()
+            .filter_map(|a| match a.addr {
+                IpAddr::V4(ip) => Some(ip.into()),
+                IpAddr::V6(ip) => Some(ip.into()),
+                IpAddr::Empty | IpAddr::Unsupported => None,
+            })
+            .collect();
+        let dropped = |direction: &str| {
+            if cfg!(target_os = "linux") {
+                sysfs::read_u64(format!(
+                    "/sys/class/net/{}/statistics/{}_dropped",
+                    name, direction
+                ))
+            } else {
+                None
+            }
+        };
+
+        counters.insert(
+            name.clone(),
+            InterfaceCounters {
+                is_loopback: !addresses.is_empty() && addresses.iter().all(|a| a.is_loopback()),
+                addresses: addresses.iter().map(|a| a.to_string()).collect(),
+                rx_bytes: net.rx_bytes.as_u64(),
+                tx_bytes: net.tx_bytes.as_u64(),
+                rx_packets: net.rx_packets,
+                tx_packets: net.tx_packets,
+                rx_errors: net.rx_errors,
+                tx_errors: net.tx_errors,
+                rx_dropped: dropped("rx"),
+                tx_dropped: dropped("tx"),
+            },
+        );
+    }
+    Some(counters)
+}
+
+pub fn interfaces(window: &RateWindow<NetworkCounters>) -> Vec<NetworkInterfaceInfo> {
+    let Some(current) = window.current() else {
+        return Vec::new();
+    };
+    let previous = window.previous();
+
+    current
+        .iter()
+        .map(|(name, now)| {
+            let rate = |pick: fn(&InterfaceCounters) -> u64| {
+                previous
+                    .and_then(|(prev, secs)| Some(per_sec(pick(now), pick(prev.get(name)?), secs)))
+                    .unwrap_or(0.0)
+            };
+            NetworkInterfaceInfo {
+                name: name.clone(),
+                addresses: now.addresses.clone(),
+                is_loopback: now.is_loopback,
+                rx_bytes: now.rx_bytes,
+                tx_bytes: now.tx_bytes,
+                rx_packets: now.rx_packets,
+                tx_packets: now.tx_packets,
+                rx_errors: now.rx_errors,
+                tx_errors: now.tx_errors,
+                rx_dropped: now.rx_dropped,
+                tx_dropped: now.tx_dropped,
+                rx_bytes_per_sec: rate(|c| c.rx_bytes),
+                tx_bytes_per_sec: rate(|c| c.tx_bytes),
+                rx_packets_per_sec: rate(|c| c.rx_packets),
+                tx_packets_per_sec: rate(|c| c.tx_packets),
+            }
+        })
+        .collect()
+}
+
+/// TCP sockets by state from `/proc/net/tcp` and `/proc/net/tcp6`; `None`
+/// off Linux or when neither table is readable.
+pub fn tcp_states() -> Option<BTreeMap<String, u32>> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let mut counts = BTreeMap::new();
+    let mut any = false;
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(content) = fs::read_to_string(table) {
+            any = true;
+            count_tcp_states(&content, &mut counts);
+        }
+    }
+    any.then_some(counts)
+}
+
+fn count_tcp_states(table: &str, counts: &mut BTreeMap<String, u32>) {
+    // Header, then: sl local_address rem_address st ...
+    for line in table.lines().skip(1) {
+        let Some(state) = line
+            .split_whitespace()
+            .nth(3)
+            .and_then(|st| u8::from_str_radix(st, 16).ok())
+        else {
+            continue;
+        };
+        *counts.entry(tcp_state_name(state).to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Names from `include/net/tcp_states.h`.
+pub fn tcp_state_name(state: u8) -> &'static str {
+    match state {
+        0x01 => "established",
+        0x02 => "syn_sent",
+        0x03 => "syn_recv",
+        0x04 => "fin_wait1",
+        0x05 => "fin_wait2",
+        0x06 => "time_wait",
+        0x07 => "close",
+        0x08 => "close_wait",
+        0x09 => "last_ack",
+        0x0A => "listen",
+        0x0B => "closing",
+        0x0C => "new_syn_recv",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_states_are_counted_by_name() {
+        let table = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0035 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1001 1
+   1: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 1002 1
+   2: 0100007F:1F90 0100007F:C351 01 00000000:00000000 00:00000000 00000000  1000        0 1003 1
+   3: 0100007F:1F90 0100007F:C352 0D 00000000:00000000 00:00000000 00000000  1000        0 1004 1
+   4: garbage
+";
+        let mut counts = BTreeMap::new();
+        count_tcp_states(table, &mut counts);
+        count_tcp_states(table, &mut counts);
+        assert_eq!(
+            counts,
+            BTreeMap::from([
+                ("established".to_string(), 4),
+                ("listen".to_string(), 2),
+                ("unknown".to_string(), 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn header_only_table_counts_nothing() {
+        let mut counts = BTreeMap::new();
+        count_tcp_states("  sl  local_address rem_address   st\n", &mut counts);
+        assert!(counts.is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+use super::rates::RateWindow;
+use crate::{
+    models::system_info::{BatteryInfo, PowerInfo, RaplZone},
+    utils::sysfs,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use systemstat::Platform;
+
+#[derive(Clone)]
+pub struct RaplCounter {
+    pub name: String,
+    pub energy_uj: u64,
+    /// The counter wraps back to 0 after this value; `None` when the range
+    /// is not readable.
+    pub max_energy_uj: Option<u64>,
+}
+
+/// Keyed by powercap directory, e.g. `intel-rapl:0:1`.
+pub type RaplCounters = BTreeMap<String, RaplCounter>;
+
+/// Energy counters of every RAPL zone under the sysfs mount `root`. Empty
+/// off Linux, without RAPL, or when `energy_uj` is root-only (the default
+/// on many distributions).
+pub fn read_rapl(root: &Path) -> RaplCounters {
+    let mut counters = BTreeMap::new();
+    if !cfg!(target_os = "linux") {
+        return counters;
+    }
+    let Ok(entries) = fs::read_dir(root.join("class/powercap")) else {
+        return counters;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.file_name().to_string_lossy().to_string();
+        // `intel-rapl` itself is the control type, zones are `intel-rapl:N[:M]`.
+        if !dir.contains(':') {
+            continue;
+        }
+        let path = entry.path();
+        let Some(energy_uj) = sysfs::read_u64(path.join("energy_uj")) else {
+            continue;
+        };
+        counters.insert(
+            dir.clone(),
+            RaplCounter {
+                name: sysfs::read_trimmed(path.join("name")).unwrap_or(dir),
+                energy_uj,
+                max_energy_uj: sysfs::read_u64(path.join("max_energy_range_uj")),
+            },
+        );
+    }
+    counters
+}
+
+fn rapl_zones(window: &RateWindow<RaplCounters>) -> Vec<RaplZone> {
+    let Some(current) = window.current() else {
+        return Vec::new();
+    };
+    let previous = window.previous();
+
+    current
+        .iter()
+        .map(|(zone, counter)| {
+            let power_watts = previous.and_then(|(prev, secs)| {
+                let before = prev.get(zone)?.energy_uj;
+                let delta = match counter.energy_uj.checked_sub(before) {
+                    Some(delta) => delta,
+                    // Wrapped: only measurable when the range is known.
+                    None => counter
+                        .max_energy_uj?
+                        .checked_sub(before)?
+                        .checked_add(counter.energy_uj)?,
+                };
+                Some(delta as f64 / 1_000_000.0 / secs)
+            });
+            RaplZone {
+                zone: zone.clone(),
+                name: counter.name.clone(),
+                is_package: is_package_zone(zone),
+                energy_uj: counter.energy_uj,
+                power_watts,
+            }
+        })
+        .collect()
+}
+
+/// Packages are the top-level `intel-rapl:N` zones (AMD uses the same
+/// names). Subzones (`intel-rapl:0:0`) are part of their package, and
+/// `intel-rapl-mmio:N` measures the same package as `intel-rapl:N` again.
+fn is_package_zone(zone: &str) -> bool {
+    zone.strip_prefix("intel-rapl:")
+        .is_some_and(|index| !index.contains(':'))
+}
+
+fn battery(stats: &systemstat::System, ac_online: Option<bool>) -> Option<BatteryInfo> {
+    let life = stats.battery_life().ok()?;
+    let charge_percent = life.remaining_capacity * 100.0;
+    let state = battery_status().unwrap_or_else(|| {
+        match ac_online {
+            Some(true) if charge_percent >= 99.0 => "full",
+            Some(true) => "charging",
+            Some(false) => "discharging",
+            None => "unknown",
+        }
+        .to_string()
+    });
+    let secs = life.remaining_time.as_secs();
+
+    Some(BatteryInfo {
+        state,
+        charge_percent,
+        time_remaining_secs: (secs > 0).then_some(secs),
+    })
+}
+
+/// Power supplies of `kind` (`Battery`, `Mains`, ...) known to the kernel.
+fn power_supplies(kind: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| sysfs::read_trimmed(path.join("type")).as_deref() == Some(kind))
+        .collect()
+}
+
+/// systemstat reports "not on AC" on Linux machines without any adapter,
+/// e.g. servers, so only trust it when the kernel knows one.
+fn has_power_supply(kind: &str) -> bool {
+    !cfg!(target_os = "linux") || !power_supplies(kind).is_empty()
+}
+
+/// `charging`/`discharging`/`full`/`not_charging` as the kernel reports it
+/// for the first battery.
+fn battery_status() -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    power_supplies("Battery")
+        .iter()
+        .find_map(|path| sysfs::read_trimmed(path.join("status")))
+        .map(|status| status.to_lowercase().replace(' ', "_"))
+}
+
+/// `None` when the machine has no battery, no AC adapter info and no
+/// readable RAPL zones.
+pub fn power(stats: &systemstat::System, rapl: &RateWindow<RaplCounters>) -> Option<PowerInfo> {
+    let ac_online = if has_power_supply("Mains") {
+        stats.on_ac_power().ok()
+    } else {
+        None
+    };
+    let info = PowerInfo {
+        battery: battery(stats, ac_online),
+        ac_online,
+        rapl: rapl_zones(rapl),
+    };
+    (info.battery.is_some() || info.ac_online.is_some() || !info.rapl.is_empty()).then_some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{thread, time::Duration};
+
+    fn counters(energy_uj: u64, max_energy_uj: Option<u64>) -> RaplCounters {
+        BTreeMap::from([(
+            "intel-rapl:0".to_string(),
+            RaplCounter {
+                name: "package-0".to_string(),
+                energy_uj,
+                max_energy_uj,
+            },
+        )])
+    }
+
+    fn power_after(before: RaplCounters, after: RaplCounters) -> Option<f64> {
+        let mut window = RateWindow::new();
+        window.push(before);
+        thread::sleep(Duration::from_millis(5));
+        window.push(after);
+        rapl_zones(&window)[0].power_watts
+    }
+
+    #[test]
+    fn wrapped_counter_uses_the_range() {
+        let watts = power_after(counters(900, Some(1_000)), counters(100, Some(1_000)));
+        assert!(watts.is_some_and(|w| w > 0.0));
+    }
+
+    #[test]
+    fn wrap_without_a_known_range_is_unknown() {
+        assert_eq!(power_after(counters(900, None), counters(100, None)), None);
+    }
+
+    #[test]
+    fn reading_above_the_range_is_unknown() {
+        assert_eq!(
+            power_after(counters(5_000, Some(1_000)), counters(100, Some(1_000))),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mmio_zones_are_not_counted_as_packages() {
+        let root = std::env::temp_dir().join(format!("powercap-mmio-{}", std::process::id()));
+        for (dir, name) in [
+            ("intel-rapl", None),
+            ("intel-rapl:0", Some("package-0")),
+            ("intel-rapl:0:0", Some("core")),
+            ("intel-rapl-mmio", None),
+            ("intel-rapl-mmio:0", Some("package-0")),
+        ] {
+            let zone = root.join("class/powercap").join(dir);
+            fs::create_dir_all(&zone).unwrap();
+            if let Some(name) = name {
+                fs::write(zone.join("name"), format!("{}\n", name)).unwrap();
+                fs::write(zone.join("energy_uj"), "1000\n").unwrap();
+            }
+        }
+
+        let mut window = RateWindow::new();
+        window.push(read_rapl(&root));
+        let zones = rapl_zones(&window);
+        let names: Vec<&str> = zones.iter().map(|z| z.zone.as_str()).collect();
+        assert_eq!(
+            names,
+            ["intel-rapl-mmio:0", "intel-rapl:0", "intel-rapl:0:0"]
+        );
+        let packages: Vec<&str> = zones
+            .iter()
+            .filter(|z| z.is_package)
+            .map(|z| z.zone.as_str())
+            .collect();
+        assert_eq!(packages, ["intel-rapl:0"]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
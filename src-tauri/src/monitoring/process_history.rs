@@ -0,0 +1,146 @@
+use crate::models::{
+    process::{ProcessSample, ProcessSeries},
+    system_info::LocalProcessInfo,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Ten minutes of samples per process at the default one-second interval.
+pub const DEFAULT_PROCESS_HISTORY_CAPACITY: usize = 600;
+
+/// How many of the busiest processes (by CPU and by memory) are tracked.
+pub const DEFAULT_TRACKED_PROCESSES: usize = 10;
+
+/// Rolling per-process samples for the busiest processes and the one
+/// selected in the UI.
+///
+/// A process stays tracked while it is alive until it has been out of the
+/// top and unselected for a full buffer's worth of samples:
+///
+/// ```
+/// use app_lib::{models::system_info::LocalProcessInfo, monitoring::process_history::ProcessHistory};
+///
+/// let process = |pid, cpu_usage, memory| LocalProcessInfo {
+///     pid,
+///     cpu_usage,
+///     memory,
+///     ..LocalProcessInfo::default()
+/// };
+///
+/// let mut history = ProcessHistory::new(3, 1);
+/// history.record(0, &[process(1, 50.0, 10), process(2, 1.0, 900), process(3, 1.0, 10)], None);
+/// history.record(1000, &[process(1, 60.0, 10), process(2, 1.0, 950), process(3, 1.0, 10)], Some(3));
+///
+/// // Top by CPU, top by memory, and the selected one since it was selected.
+/// assert_eq!(history.series(1, 0).unwrap().samples.len(), 2);
+/// assert_eq!(history.series(2, 0).unwrap().samples[1].memory, 950);
+/// assert_eq!(history.series(3, 0).unwrap().samples.len(), 1);
+///
+/// // Exited processes are dropped.
+/// history.record(2000, &[process(1, 60.0, 10), process(2, 1.0, 950)], None);
+/// assert!(history.series(3, 0).is_none());
+/// ```
+pub struct ProcessHistory {
+    capacity: usize,
+    top: usize,
+    tracked: HashMap<u32, TrackedProcess>,
+}
+
+struct TrackedProcess {
+    name: String,
+    start_time: u64,
+    /// Samples taken since the process was last in the top or selected.
+    cold_samples: usize,
+    samples: VecDeque<ProcessSample>,
+}
+
+impl ProcessHistory {
+    pub fn new(capacity: usize, top: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            top,
+            tracked: HashMap::new(),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        timestamp_ms: i64,
+        processes: &[LocalProcessInfo],
+        selected_pid: Option<u32>,
+    ) {
+        let mut hot: HashSet<u32> = selected_pid.into_iter().collect();
+        let mut ranked: Vec<&LocalProcessInfo> = processes.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hot.extend(ranked.iter().take(self.top).map(|p| p.pid));
+        ranked.sort_by_key(|p| std::cmp::Reverse(p.memory));
+        hot.extend(ranked.iter().take(self.top).map(|p| p.pid));
+
+        let alive: HashMap<u32, &LocalProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+        let capacity = self.capacity;
+        self.tracked.retain(|pid, tracked| {
+            alive
+                .get(pid)
+                .is_some_and(|p| p.start_time == tracked.start_time)
+                && tracked.cold_samples < capacity
+        });
+
+        for pid in hot {
+            let Some(process) = alive.get(&pid) else {
+                continue;
+            };
+            let tracked = self.tracked.entry(pid).or_insert_with(|| TrackedProcess {
+                name: process.name.clone(),
+                start_time: process.start_time,
+                cold_samples: 0,
+                samples: VecDeque::with_capacity(capacity),
+            });
+            tracked.cold_samples = 0;
+        }
+
+        for (pid, tracked) in self.tracked.iter_mut() {
+            let process = alive[pid];
+            if tracked.samples.len() == capacity {
+                tracked.samples.pop_front();
+            }
+            tracked.samples.push_back(ProcessSample {
+                timestamp_ms,
+                cpu_usage: process.cpu_usage,
+                memory: process.memory,
+                disk_read_bytes_per_sec: process.disk_read_bytes_per_sec,
+                disk_write_bytes_per_sec: process.disk_write_bytes_per_sec,
+            });
+            tracked.cold_samples += 1;
+        }
+    }
+
+    /// Samples of `pid` from `since_ms` on, oldest first.
+    pub fn series(&self, pid: u32, since_ms: i64) -> Option<ProcessSeries> {
+        let tracked = self.tracked.get(&pid)?;
+        Some(ProcessSeries {
+            pid,
+            name: tracked.name.clone(),
+            start_time: tracked.start_time,
+            samples: tracked
+                .samples
+                .iter()
+                .filter(|s| s.timestamp_ms >= since_ms)
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Pids with a history, in no particular order.
+    pub fn tracked_pids(&self) -> Vec<u32> {
+        self.tracked.keys().copied().collect()
+    }
+}
+
+impl Default for ProcessHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROCESS_HISTORY_CAPACITY, DEFAULT_TRACKED_PROCESSES)
+    }
+}
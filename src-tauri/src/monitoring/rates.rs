@@ -0,0 +1,46 @@
+use std::time::Instant;
+
+/// The last two readings of a set of monotonic counters, for turning them
+/// into per-second rates.
+pub struct RateWindow<T> {
+    previous: Option<(Instant, T)>,
+    current: Option<(Instant, T)>,
+}
+
+impl<T> RateWindow<T> {
+    pub fn new() -> Self {
+        Self {
+            previous: None,
+            current: None,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.previous = self.current.take();
+        self.current = Some((Instant::now(), value));
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.current.as_ref().map(|(_, value)| value)
+    }
+
+    /// The previous reading and the seconds elapsed since it, once there
+    /// are two readings.
+    pub fn previous(&self) -> Option<(&T, f64)> {
+        let (then, previous) = self.previous.as_ref()?;
+        let (now, _) = self.current.as_ref()?;
+        let secs = now.duration_since(*then).as_secs_f64();
+        (secs > 0.0).then_some((previous, secs))
+    }
+}
+
+impl<T> Default for RateWindow<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Per-second rate of a counter; 0 when it went backwards (reset or wrap).
+pub fn per_sec(current: u64, previous: u64, secs: f64) -> f64 {
+    current.saturating_sub(previous) as f64 / secs
+}
@@ -0,0 +1,266 @@
+use super::{
+    command_monitor::{self, CommandMonitorState},
+    file_monitor::MonitoringState,
+    system_metrics,
+};
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        events::BackendEvent, metrics::MetricsSample, process::FileWriter,
+        system_info::LocalProcessInfo,
+    },
+    process::{
+        file_writers::find_writers,
+        watch::{WatchAction, WatchTrigger},
+    },
+    state::{
+        alerts::{raise_alert, AlertsState},
+        events::{emit_backend_event, notify_desktop},
+        process::WatchState,
+        system::SystemMonitorState,
+    },
+    storage::MetricStore,
+    utils::sync::LockExt,
+};
+use chrono::Utc;
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+    sync::{atomic::Ordering, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Scanning every `/proc/<pid>/fd` is comparatively expensive and the
+/// writers of a log rarely change, so they are looked up this often.
+const WRITER_SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+struct WriterScan {
+    path: String,
+    at: Instant,
+    writers: Vec<FileWriter>,
+}
+
+/// The processes of one sample, handed to the watch thread.
+struct WatchSample {
+    timestamp_ms: i64,
+    processes: Vec<LocalProcessInfo>,
+    cores: usize,
+}
+
+/// Starts the thread that refreshes system metrics every
+/// `sample_interval_ms`, records them in the history (and the on-disk
+/// [`MetricStore`] when one is managed) and pushes each sample to the UI. Runs for the lifetime of the app.
+pub fn spawn_sampler<R: Runtime>(app: AppHandle<R>) {
+    let watch = spawn_watcher(app.clone());
+    thread::spawn(move || {
+        let state = app.state::<SystemMonitorState>();
+
+        system_metrics::refresh(&state);
+        thread::sleep(system_metrics::CPU_SAMPLE_INTERVAL);
+        let mut writer_scan = None;
+
+        loop {
+            let started = Instant::now();
+            let sample = sample_once(&state);
+            if let Some(watch) = &watch {
+                let (processes, cores) = state
+                    .latest
+                    .lock_or_recover()
+                    .as_ref()
+                    .map(|info| (info.processes.clone(), info.logical_core_count))
+                    .unwrap_or_default();
+                // Fails only if the watch thread panicked; sampling carries on.
+                let _ = watch.send(WatchSample {
+                    timestamp_ms: sample.timestamp_ms,
+                    processes,
+                    cores,
+                });
+            }
+            check_alerts(&app, &sample);
+            if let Some(store) = app.try_state::<MetricStore>() {
+                if let Err(e) = store.append(&sample) {
+                    log::warn!("Failed to persist metrics sample: {}", e);
+                }
+            }
+            emit_backend_event(
+                &app,
+                BackendEvent::MetricsSample {
+                    sample: Box::new(sample),
+                },
+            );
+            emit_file_writers(&app, &state, &mut writer_scan);
+
+            let interval = Duration::from_millis(state.sample_interval_ms.load(Ordering::Relaxed));
+            thread::sleep(interval.saturating_sub(started.elapsed()));
+        }
+    });
+}
+
+fn sample_once(state: &SystemMonitorState) -> MetricsSample {
+    system_metrics::refresh(state);
+    let info = system_metrics::collect(state, None);
+    let sample = MetricsSample::from_info(Utc::now().timestamp_millis(), &info);
+
+    state.history.lock_or_recover().push(sample.clone());
+    let selected_pid = *state.selected_pid.lock_or_recover();
+    state.process_history.lock_or_recover().record(
+        sample.timestamp_ms,
+        &info.processes,
+        selected_pid,
+    );
+    *state.latest.lock_or_recover() = Some(info);
+    sample
+}
+
+/// Sends the processes writing the monitored file when the session asked
+/// for them. Their detail is read from `/proc` at scan cadence, without the
+/// environment and sockets; each sample only refreshes its CPU and memory.
+fn emit_file_writers<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SystemMonitorState,
+    scan: &mut Option<WriterScan>,
+) {
+    let path = app.try_state::<MonitoringState>().and_then(|monitoring| {
+        let monitor = monitoring.state.lock_or_recover();
+        monitor
+            .current_file
+            .clone()
+            .filter(|_| monitor.attach_writers)
+    });
+    let Some(path) = path else {
+        *scan = None;
+        return;
+    };
+
+    let fresh = scan
+        .as_ref()
+        .is_some_and(|s| s.path == path && s.at.elapsed() < WRITER_SCAN_INTERVAL);
+    if !fresh {
+        let mut writers = find_writers(Path::new("/proc"), Path::new(&path));
+        for writer in &mut writers {
+            writer.detail =
+                system_metrics::process_detail_once(state, writer.pid).map(|mut detail| {
+                    detail.environment.clear();
+                    detail.sockets.clear();
+                    detail
+                });
+        }
+        *scan = Some(WriterScan {
+            writers,
+            path: path.clone(),
+            at: Instant::now(),
+        });
+    }
+    let Some(scan) = scan.as_ref() else {
+        return;
+    };
+
+    let mut writers = scan.writers.clone();
+    if let Some(info) = state.latest.lock_or_recover().as_ref() {
+        for writer in &mut writers {
+            let process = info.processes.iter().find(|p| p.pid == writer.pid);
+            if let (Some(detail), Some(process)) = (writer.detail.as_mut(), process) {
+                detail.cpu_usage = process.cpu_usage;
+                detail.memory = process.memory;
+            }
+        }
+    }
+    emit_backend_event(app, BackendEvent::FileWriters { path, writers });
+}
+
+/// Evaluates the metric alert rules against the sample just taken.
+fn check_alerts<R: Runtime>(app: &AppHandle<R>, sample: &MetricsSample) {
+    let Some(alerts) = app.try_state::<AlertsState>() else {
+        return;
+    };
+    let rules = alerts.metric_rules.lock_or_recover().clone();
+    let records = alerts
+        .metric_engine
+        .lock_or_recover()
+        .evaluate(&rules, sample);
+    for record in records {
+        let notify = rules.iter().any(|r| r.id == record.rule_id && r.notify);
+        raise_alert(app, record, notify);
+    }
+}
+
+/// Starts the thread that evaluates the process watch rules against each
+/// sample the sampler sends and runs their actions, so a slow script spawn
+/// or command restart never delays sampling. `None` when no [`WatchState`]
+/// is managed.
+fn spawn_watcher<R: Runtime>(app: AppHandle<R>) -> Option<mpsc::Sender<WatchSample>> {
+    app.try_state::<WatchState>()?;
+    let (sender, receiver) = mpsc::channel::<WatchSample>();
+    thread::spawn(move || {
+        for sample in receiver {
+            check_watch_rules(&app, &sample);
+        }
+    });
+    Some(sender)
+}
+
+/// Evaluates the process watch rules against the processes of `sample` and
+/// runs the actions of the rules that triggered.
+fn check_watch_rules<R: Runtime>(app: &AppHandle<R>, sample: &WatchSample) {
+    let Some(watch) = app.try_state::<WatchState>() else {
+        return;
+    };
+    let rules = watch.rules.lock_or_recover().clone();
+    let triggers =
+        watch
+            .engine
+            .lock_or_recover()
+            .evaluate(&rules, sample.timestamp_ms, &sample.processes, sample.cores);
+
+    for trigger in triggers {
+        log::info!(
+            "Watch rule {} triggered: {}",
+            trigger.rule_name,
+            trigger.message
+        );
+        let actions = rules
+            .iter()
+            .find(|r| r.id == trigger.rule_id)
+            .map_or(&[][..], |r| &r.actions[..]);
+        for action in actions {
+            if let Err(e) = run_watch_action(app, action, &trigger) {
+                log::warn!("Action of watch rule {} failed: {}", trigger.rule_name, e);
+            }
+        }
+        emit_backend_event(app, BackendEvent::WatchTriggered { trigger });
+    }
+}
+
+fn run_watch_action<R: Runtime>(
+    app: &AppHandle<R>,
+    action: &WatchAction,
+    trigger: &WatchTrigger,
+) -> AppResult<()> {
+    match action {
+        WatchAction::Notify => notify_desktop(app, &trigger.rule_name, &trigger.message),
+        WatchAction::RunScript { program, args } => {
+            let mut child = Command::new(program)
+                .args(args)
+                .env("WATCH_RULE", &trigger.rule_name)
+                .env(
+                    "WATCH_PID",
+                    trigger.pid.map(|pid| pid.to_string()).unwrap_or_default(),
+                )
+                .env("WATCH_PROCESS", &trigger.process_name)
+                .stdin(Stdio::null())
+                .spawn()
+                .map_err(|e| AppError::io_context(format!("Failed to run {}", program), e))?;
+            // Reaped in the background so a slow script does not hold up later rules.
+            thread::spawn(move || child.wait());
+        }
+        WatchAction::RestartCommand => {
+            let commands = app
+                .try_state::<CommandMonitorState>()
+                .ok_or_else(|| AppError::NotFound("no command was launched".to_string()))?;
+            command_monitor::restart(&commands.state, app.clone())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use crate::{
+    models::system_info::{SensorKind, SensorReading},
+    utils::sysfs,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// hwmon chips whose first temperature is the CPU package.
+const CPU_CHIPS: [&str; 4] = ["coretemp", "k10temp", "zenpower", "cpu_thermal"];
+
+/// Every hwmon and thermal zone sensor under the sysfs mount `root`
+/// (`/sys`). Empty off Linux.
+///
+/// Ids are built from the chip name and the device it is attached to
+/// rather than the `hwmonN` number, which can change between boots. A chip
+/// without a `device` link is told apart from others of the same name by
+/// its position among them, and so is a thermal zone, by its `type`,
+/// rather than by `thermal_zoneN`:
+///
+/// ```
+/// use app_lib::{models::system_info::SensorKind, monitoring::sensors};
+/// use std::fs;
+///
+/// let root = std::env::temp_dir().join(format!("sensors-doctest-{}", std::process::id()));
+/// let chip = root.join("class/hwmon/hwmon2");
+/// let zone = root.join("class/thermal/thermal_zone0");
+/// fs::create_dir_all(&chip).unwrap();
+/// fs::create_dir_all(&zone).unwrap();
+/// for (file, value) in [
+///     (chip.join("name"), "nct6775"),
+///     (chip.join("temp1_input"), "41500"),
+///     (chip.join("temp1_label"), "SYSTIN"),
+///     (chip.join("temp1_crit"), "95000"),
+///     (chip.join("fan2_input"), "1180"),
+///     (chip.join("in0_input"), "1032"),
+///     (zone.join("type"), "acpitz"),
+///     (zone.join("temp"), "27800"),
+///     (zone.join("trip_point_0_type"), "critical"),
+///     (zone.join("trip_point_0_temp"), "105000"),
+/// ] {
+///     fs::write(file, value).unwrap();
+/// }
+///
+/// let readings = sensors::read_sensors(&root);
+/// let ids: Vec<&str> = readings.iter().map(|r| r.id.as_str()).collect();
+/// assert_eq!(
+///     ids,
+///     ["hwmon/nct6775/0/fan2", "hwmon/nct6775/0/in0", "hwmon/nct6775/0/temp1", "thermal/acpitz/0"]
+/// );
+///
+/// let systin = &readings[2];
+/// assert_eq!(systin.kind, SensorKind::Temperature);
+/// assert_eq!(systin.label.as_deref(), Some("SYSTIN"));
+/// assert_eq!((systin.value, systin.critical), (41.5, Some(95.0)));
+/// assert_eq!(readings[1].value, 1.032);
+/// assert_eq!(readings[3].critical, Some(105.0));
+/// # fs::remove_dir_all(&root).unwrap();
+/// ```
+pub fn read_sensors(root: &Path) -> Vec<SensorReading> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    let mut readings = Vec::new();
+    // Chips without a device link seen so far, by name.
+    let mut unlinked: HashMap<String, usize> = HashMap::new();
+    for chip in subdirs(&root.join("class/hwmon"), "hwmon") {
+        let name = sysfs::read_trimmed(chip.join("name")).unwrap_or_else(|| "unknown".to_string());
+        let device = device_name(&chip).unwrap_or_else(|| {
+            let seen = unlinked.entry(name.clone()).or_default();
+            *seen += 1;
+            (*seen - 1).to_string()
+        });
+        read_hwmon(&chip, &name, &device, &mut readings);
+    }
+    // Zones seen so far, by type.
+    let mut zone_types: HashMap<String, usize> = HashMap::new();
+    for zone in subdirs(&root.join("class/thermal"), "thermal_zone") {
+        let kind = sysfs::read_trimmed(zone.join("type")).unwrap_or_else(|| "unknown".to_string());
+        let seen = zone_types.entry(kind.clone()).or_default();
+        *seen += 1;
+        readings.extend(read_thermal_zone(&zone, &kind, *seen - 1));
+    }
+    readings
+}
+
+/// The CPU package temperature from `readings`, for boards where
+/// systemstat's `cpu_temp` has nothing.
+pub fn cpu_temperature(readings: &[SensorReading]) -> Option<f32> {
+    readings
+        .iter()
+        .find(|r| r.kind == SensorKind::Temperature && CPU_CHIPS.contains(&r.chip.as_str()))
+        .map(|r| r.value as f32)
+}
+
+/// Entries of `dir` named `<prefix>N`, by `N` (`hwmon2` before `hwmon10`).
+fn subdirs(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|e| {
+            let number = e.file_name().to_str()?.strip_prefix(prefix)?.parse().ok()?;
+            Some((number, e.path()))
+        })
+        .collect();
+    dirs.sort();
+    dirs.into_iter().map(|(_, path)| path).collect()
+}
+
+/// The device the chip hangs off (`0000:03:00.0`, `coretemp.0`, ...), which
+/// is stable where the `hwmonN` number is not.
+fn device_name(chip: &Path) -> Option<String> {
+    let device = fs::canonicalize(chip.join("device")).ok()?;
+    Some(device.file_name()?.to_string_lossy().to_string())
+}
+
+fn read_hwmon(chip: &Path, name: &str, device: &str, readings: &mut Vec<SensorReading>) {
+    let Ok(entries) = fs::read_dir(chip) else {
+        return;
+    };
+
+    let mut inputs: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let file = e.file_name().to_string_lossy().to_string();
+            file.strip_suffix("_input").map(str::to_string)
+        })
+        .collect();
+    inputs.sort();
+
+    for sensor in inputs {
+        let (kind, scale) = match sensor.trim_end_matches(|c: char| c.is_ascii_digit()) {
+            "temp" => (SensorKind::Temperature, 1000.0),
+            "fan" => (SensorKind::Fan, 1.0),
+            "in" => (SensorKind::Voltage, 1000.0),
+            _ => continue,
+        };
+        let read = |attr: &str| {
+            sysfs::read_trimmed(chip.join(format!("{}_{}", sensor, attr)))
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|v| v / scale)
+        };
+        let Some(value) = read("input") else {
+            continue;
+        };
+        readings.push(SensorReading {
+            id: format!("hwmon/{}/{}/{}", name, device, sensor),
+            kind,
+            chip: name.to_string(),
+            label: sysfs::read_trimmed(chip.join(format!("{}_label", sensor))),
+            value,
+            high: read("max"),
+            critical: read("crit"),
+        });
+    }
+}
+
+/// `kind` is the zone's `type`; `index` its position among zones of that
+/// type.
+fn read_thermal_zone(zone: &Path, kind: &str, index: usize) -> Option<SensorReading> {
+    let millideg = |file: String| {
+        sysfs::read_trimmed(zone.join(file))
+            .and_then(|v| v.parse::<f64>().ok())
+            .map(|v| v / 1000.0)
+    };
+    let value = millideg("temp".to_string())?;
+
+    let mut high = None;
+    let mut critical = None;
+    for trip in 0.. {
+        let Some(trip_type) = sysfs::read_trimmed(zone.join(format!("trip_point_{}_type", trip)))
+        else {
+            break;
+        };
+        let temp = millideg(format!("trip_point_{}_temp", trip));
+        match trip_type.as_str() {
+            "critical" => critical = critical.or(temp),
+            "hot" | "passive" => high = high.or(temp),
+            _ => {}
+        }
+    }
+
+    Some(SensorReading {
+        id: format!("thermal/{}/{}", kind, index),
+        kind: SensorKind::Temperature,
+        chip: kind.to_string(),
+        label: None,
+        value,
+        high,
+        critical,
+    })
+}
+
+// Sensors are only read on Linux.
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    fn chip(root: &Path, hwmon: &str, name: &str) -> PathBuf {
+        let chip = root.join("class/hwmon").join(hwmon);
+        fs::create_dir_all(&chip).unwrap();
+        fs::write(chip.join("name"), name).unwrap();
+        fs::write(chip.join("temp1_input"), "40000").unwrap();
+        chip
+    }
+
+    fn ids(root: &Path) -> Vec<String> {
+        read_sensors(root).into_iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn ids_use_the_linked_device() {
+        let root = std::env::temp_dir().join(format!("sensors-device-{}", std::process::id()));
+        let device = root.join("devices/platform/nct6775.656");
+        fs::create_dir_all(&device).unwrap();
+        let chip = chip(&root, "hwmon7", "nct6775");
+        std::os::unix::fs::symlink(&device, chip.join("device")).unwrap();
+
+        assert_eq!(ids(&root), ["hwmon/nct6775/nct6775.656/temp1"]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn unlinked_chips_of_one_name_are_numbered_in_hwmon_order() {
+        let root = std::env::temp_dir().join(format!("sensors-unlinked-{}", std::process::id()));
+        chip(&root, "hwmon10", "acpitz");
+        chip(&root, "hwmon2", "acpitz");
+        chip(&root, "hwmon3", "nvme");
+
+        assert_eq!(
+            ids(&root),
+            [
+                "hwmon/acpitz/0/temp1",
+                "hwmon/nvme/0/temp1",
+                "hwmon/acpitz/1/temp1"
+            ]
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn thermal_zones_are_keyed_by_type() {
+        let root = std::env::temp_dir().join(format!("sensors-thermal-{}", std::process::id()));
+        for (zone, kind) in [
+            ("thermal_zone0", Some("acpitz")),
+            ("thermal_zone1", Some("x86_pkg_temp")),
+            ("thermal_zone10", Some("acpitz")),
+            ("thermal_zone2", None),
+        ] {
+            let zone = root.join("class/thermal").join(zone);
+            fs::create_dir_all(&zone).unwrap();
+            fs::write(zone.join("temp"), "30000").unwrap();
+            if let Some(kind) = kind {
+                fs::write(zone.join("type"), kind).unwrap();
+            }
+        }
+
+        assert_eq!(
+            ids(&root),
+            [
+                "thermal/acpitz/0",
+                "thermal/x86_pkg_temp/0",
+                "thermal/unknown/0",
+                "thermal/acpitz/1"
+            ]
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
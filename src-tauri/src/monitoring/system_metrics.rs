@@ -0,0 +1,304 @@
+use super::{cpu, disks, gpu::GpuProvider, memory, network, power, rates::RateWindow, sensors};
+use crate::{
+    models::system_info::{
+        GpuInfo, LocalProcessInfo, NetworkInfo, ProcessDetail, StorageInfo, SystemInfo,
+    },
+    process::{detail, filter::is_kernel_thread},
+    state::system::SystemMonitorState,
+    utils::sync::LockExt,
+};
+use std::{path::Path, time::Duration};
+use sysinfo::{CpuExt, Pid, PidExt, Process, ProcessExt, System, SystemExt, UserExt};
+use systemstat::Platform;
+
+/// Minimum time between two refreshes for CPU usage to be meaningful.
+pub const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Refreshes everything `collect` reads. CPU usage is the delta between two
+/// refreshes, so the first refresh must be followed by another one at least
+/// [`CPU_SAMPLE_INTERVAL`] later before collecting.
+pub fn refresh(state: &SystemMonitorState) {
+    {
+        let mut sys = state.sys.lock_or_recover();
+        sys.refresh_all();
+        sys.refresh_disks_list();
+    }
+    state.refreshes.lock_or_recover().push(());
+    if let Some(counters) = disks::read_block_devices(&state.stats) {
+        state.block_devices.lock_or_recover().push(counters);
+    }
+    if let Some(counters) = network::read_interfaces(&state.stats) {
+        state.network.lock_or_recover().push(counters);
+    }
+    state
+        .rapl
+        .lock_or_recover()
+        .push(power::read_rapl(Path::new("/sys")));
+}
+
+/// Builds a [`SystemInfo`] snapshot from the last [`refresh`].
+pub fn collect(state: &SystemMonitorState, selected_pid: Option<u32>) -> SystemInfo {
+    let sys = state.sys.lock_or_recover();
+
+    let cpu_cores = cpu::cores(&sys);
+    let cpu_frequency_mhz = match cpu_cores.len() {
+        0 => 0,
+        n => cpu_cores.iter().map(|c| c.frequency_mhz).sum::<u64>() / n as u64,
+    };
+    let physical_core_count = sys.physical_core_count();
+    let logical_core_count = sys.cpus().len();
+    let load_average = cpu::load_average(&sys);
+
+    let cpu_usage = sys.global_cpu_info().cpu_usage();
+    let total_memory = sys.total_memory();
+    let used_memory = sys.used_memory();
+    let memory = memory::memory(&sys);
+    let cpu_name = sys.global_cpu_info().brand().to_string();
+
+    let mut processes = Vec::new();
+    let mut selected_process_detail = None;
+    let refresh_secs = state
+        .refreshes
+        .lock_or_recover()
+        .previous()
+        .map(|(_, secs)| secs);
+    let mut kernel_threads = state.kernel_threads.lock_or_recover();
+    let mut known_kernel_threads = std::mem::take(&mut *kernel_threads);
+
+    // Every process is returned; hiding is up to the caller's filter.
+    for (pid, process) in sys.processes() {
+        let pid_value = pid.as_u32();
+        let name = process.name().to_string();
+        let key = (pid_value, process.start_time());
+        let kernel_thread = known_kernel_threads
+            .remove(&key)
+            .unwrap_or_else(|| is_kernel_thread(Path::new("/proc"), pid_value));
+        kernel_threads.insert(key, kernel_thread);
+
+        let process_info = LocalProcessInfo {
+            pid: pid_value,
+            name,
+            cpu_usage: process.cpu_usage() / (sys.cpus().len() as f32),
+            memory: process.memory(),
+            parent_pid: process.parent().map(|p| p.as_u32()),
+            user: process
+                .user_id()
+                .and_then(|uid| sys.get_user_by_id(uid))
+                .map(|user| user.name().to_string()),
+            start_time: process.start_time(),
+            threads: thread_count(process),
+            kernel_thread,
+            disk_read_bytes_per_sec: refresh_secs
+                .map_or(0.0, |secs| process.disk_usage().read_bytes as f64 / secs),
+            disk_write_bytes_per_sec: refresh_secs
+                .map_or(0.0, |secs| process.disk_usage().written_bytes as f64 / secs),
+        };
+
+        processes.push(process_info);
+
+        if Some(pid_value) == selected_pid {
+            selected_process_detail = Some((
+                build_process_detail(&sys, pid_value, process),
+                process.start_time(),
+            ));
+        }
+    }
+
+    processes.sort_by(|a, b| {
+        b.cpu_usage
+            .partial_cmp(&a.cpu_usage)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    drop(kernel_threads);
+    let mounts = disks::mounts(&sys, &state.stats);
+    let cpu_count = sys.cpus().len();
+    drop(sys);
+
+    let selected_process = selected_process_detail.map(|(mut detail, start_time)| {
+        add_proc_detail(state, &mut detail, start_time, cpu_count);
+        detail
+    });
+
+    let storage = StorageInfo {
+        mounts,
+        devices: disks::devices(&state.block_devices.lock_or_recover()),
+    };
+    let network = NetworkInfo {
+        interfaces: network::interfaces(&state.network.lock_or_recover()),
+        tcp_states: network::tcp_states(),
+    };
+    let pressure = memory::pressure();
+    let sensors = sensors::read_sensors(Path::new("/sys"));
+    let cpu_temp = state
+        .stats
+        .cpu_temp()
+        .ok()
+        .or_else(|| sensors::cpu_temperature(&sensors));
+
+    let (gpus, gpu_name, gpu_temp, gpu_usage) = read_gpus(state.gpu.as_deref());
+    let power = power::power(&state.stats, &state.rapl.lock_or_recover());
+
+    SystemInfo {
+        cpu_usage,
+        cpu_frequency_mhz,
+        cpu_cores,
+        physical_core_count,
+        logical_core_count,
+        load_average,
+        total_memory,
+        used_memory,
+        memory,
+        pressure,
+        cpu_temp,
+        sensors,
+        cpu_name: Some(cpu_name),
+        gpu_name,
+        gpu_temp,
+        gpu_usage,
+        gpus,
+        power,
+        storage,
+        network,
+        processes,
+        selected_process,
+    }
+}
+
+/// Every GPU of `provider`, with the name, temperature and usage of the
+/// first one, which the single-GPU fields of [`SystemInfo`] mirror.
+fn read_gpus(
+    provider: Option<&dyn GpuProvider>,
+) -> (Vec<GpuInfo>, Option<String>, Option<u32>, Option<u32>) {
+    let gpus = provider.map(|p| p.gpus()).unwrap_or_default();
+    let first = gpus.first();
+    let (name, temp, usage) = (
+        first.map(|g| g.name.clone()),
+        first.and_then(|g| g.temperature),
+        first.and_then(|g| g.utilization),
+    );
+    (gpus, name, temp, usage)
+}
+
+/// Detail for one pid from the last [`refresh`], without touching the rest of
+/// the snapshot.
+pub fn process_detail(state: &SystemMonitorState, pid: u32) -> Option<ProcessDetail> {
+    let sys = state.sys.lock_or_recover();
+    let process = sys.process(Pid::from_u32(pid))?;
+    let mut detail = build_process_detail(&sys, pid, process);
+    let start_time = process.start_time();
+    let cpu_count = sys.cpus().len();
+    drop(sys);
+
+    add_proc_detail(state, &mut detail, start_time, cpu_count);
+    Some(detail)
+}
+
+/// Like [`process_detail`] but without I/O and thread CPU rates, leaving the
+/// rate window of the selected process alone.
+pub fn process_detail_once(state: &SystemMonitorState, pid: u32) -> Option<ProcessDetail> {
+    let sys = state.sys.lock_or_recover();
+    let process = sys.process(Pid::from_u32(pid))?;
+    let mut detail = build_process_detail(&sys, pid, process);
+    let cpu_count = sys.cpus().len();
+    drop(sys);
+
+    if cfg!(target_os = "linux") {
+        let proc_root = Path::new("/proc");
+        let mut window = RateWindow::new();
+        window.push(detail::read_counters(proc_root, pid));
+        detail::fill(proc_root, &mut detail, &window, cpu_count);
+    }
+    Some(detail)
+}
+
+/// Adds the Linux `/proc` part of the detail. I/O and thread CPU rates are
+/// measured between two consecutive reads of the same process.
+fn add_proc_detail(
+    state: &SystemMonitorState,
+    detail: &mut ProcessDetail,
+    start_time: u64,
+    cpu_count: usize,
+) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    let proc_root = Path::new("/proc");
+    let counters = detail::read_counters(proc_root, detail.pid);
+
+    let mut selected = state.selected_counters.lock_or_recover();
+    if !matches!(&*selected, Some((pid, start, _)) if *pid == detail.pid && *start == start_time) {
+        *selected = Some((detail.pid, start_time, RateWindow::new()));
+    }
+    if let Some((_, _, window)) = selected.as_mut() {
+        window.push(counters);
+        detail::fill(proc_root, detail, window, cpu_count);
+    }
+}
+
+fn build_process_detail(sys: &System, pid: u32, process: &Process) -> ProcessDetail {
+    ProcessDetail {
+        pid,
+        name: process.name().to_string(),
+        cpu_usage: process.cpu_usage() / (sys.cpus().len() as f32),
+        memory: process.memory(),
+        status: format!("{:?}", process.status()),
+        exe_path: Some(process.exe().to_string_lossy().to_string()),
+        command_line: Some(process.cmd().join(" ")),
+        ..ProcessDetail::default()
+    }
+}
+
+/// sysinfo lists every thread but the main one as a task.
+#[cfg(target_os = "linux")]
+fn thread_count(process: &Process) -> Option<usize> {
+    Some(process.tasks.len() + 1)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thread_count(_process: &Process) -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeGpus;
+
+    impl GpuProvider for FakeGpus {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn gpus(&self) -> Vec<GpuInfo> {
+            (0..2)
+                .map(|index| GpuInfo {
+                    index,
+                    name: format!("Fake GPU {}", index),
+                    temperature: Some(40 + index),
+                    utilization: Some(10 * index),
+                    memory_used: Some(1 << 30),
+                    memory_total: Some(8 << 30),
+                    ..GpuInfo::default()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn single_gpu_fields_mirror_the_first_device() {
+        let (gpus, name, temp, usage) = read_gpus(Some(&FakeGpus));
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[1].memory_total, Some(8 << 30));
+        assert_eq!(name.as_deref(), Some("Fake GPU 0"));
+        assert_eq!(temp, Some(40));
+        assert_eq!(usage, Some(0));
+    }
+
+    #[test]
+    fn no_provider_means_no_gpu() {
+        let (gpus, name, temp, usage) = read_gpus(None);
+        assert!(gpus.is_empty());
+        assert_eq!((name, temp, usage), (None, None, None));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    error::{AppError, AppResult},
+    models::process::{ProcessAction, ProcessSignal},
+};
+#[cfg(unix)]
+use std::fs;
+
+/// Refuses pid 0, pid 1 and the app itself: signalling init or ourselves
+/// from a monitoring UI is never what the user meant. Pids past `i32::MAX`
+/// would turn negative as a `pid_t` and address process groups, or every
+/// process with `-1`.
+pub fn guard(pid: u32) -> AppResult<()> {
+    if pid > i32::MAX as u32 {
+        return Err(AppError::InvalidArgument(format!("invalid pid {}", pid)));
+    }
+    if pid <= 1 || pid == std::process::id() {
+        return Err(AppError::ProtectedProcess(pid));
+    }
+    Ok(())
+}
+
+/// Checks the arguments of `action` without touching the process.
+pub fn validate(action: &ProcessAction) -> AppResult<()> {
+    match action {
+        ProcessAction::Signal { .. } => Ok(()),
+        ProcessAction::Renice { nice } if !(-20..=19).contains(nice) => Err(
+            AppError::InvalidArgument(format!("niceness must be between -20 and 19, got {}", nice)),
+        ),
+        ProcessAction::Renice { .. } => Ok(()),
+        ProcessAction::SetAffinity { cpus } if cpus.is_empty() => Err(AppError::InvalidArgument(
+            "affinity needs at least one CPU".to_string(),
+        )),
+        ProcessAction::SetAffinity { .. } => Ok(()),
+    }
+}
+
+/// Runs `action` against `pid` after the guard and argument checks.
+pub fn apply(pid: u32, action: &ProcessAction) -> AppResult<()> {
+    guard(pid)?;
+    validate(action)?;
+    match action {
+        ProcessAction::Signal { signal } => send_signal(pid, *signal),
+        ProcessAction::Renice { nice } => renice(pid, *nice),
+        ProcessAction::SetAffinity { cpus } => set_affinity(pid, cpus),
+    }
+}
+
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: ProcessSignal) -> AppResult<()> {
+    let signo = match signal {
+        ProcessSignal::Term => libc::SIGTERM,
+        ProcessSignal::Kill => libc::SIGKILL,
+        ProcessSignal::Stop => libc::SIGSTOP,
+        ProcessSignal::Cont => libc::SIGCONT,
+        ProcessSignal::Hup => libc::SIGHUP,
+    };
+    // SAFETY: kill has no memory-safety preconditions.
+    if unsafe { libc::kill(pid as libc::pid_t, signo) } != 0 {
+        return Err(os_error("send signal to", pid));
+    }
+    log::info!("Sent SIG{:?} to {}", signal, pid);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_signal(pid: u32, signal: ProcessSignal) -> AppResult<()> {
+    use sysinfo::{Pid, PidExt, ProcessExt, ProcessRefreshKind, System, SystemExt};
+
+    if !matches!(signal, ProcessSignal::Term | ProcessSignal::Kill) {
+        return Err(AppError::Unsupported(format!("SIG{:?}", signal)));
+    }
+    let mut sys = System::new();
+    let pid = Pid::from_u32(pid);
+    sys.refresh_process_specifics(pid, ProcessRefreshKind::new());
+    let process = sys
+        .process(pid)
+        .ok_or_else(|| AppError::NotFound(format!("process {}", pid)))?;
+    if !process.kill() {
+        return Err(AppError::PermissionDenied(format!(
+            "terminate process {}",
+            pid
+        )));
+    }
+    Ok(())
+}
+
+/// On Linux niceness is per thread, so every thread of `pid` is reniced.
+#[cfg(unix)]
+fn renice(pid: u32, nice: i32) -> AppResult<()> {
+    let threads = for_each_thread(pid, "renice", |tid| {
+        // SAFETY: setpriority has no memory-safety preconditions.
+        unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, nice) }
+    })?;
+    log::info!("Set niceness of {} ({} threads) to {}", pid, threads, nice);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn renice(_pid: u32, _nice: i32) -> AppResult<()> {
+    Err(AppError::Unsupported("renice".to_string()))
+}
+
+/// `sched_setaffinity` only changes the thread it is given, so it is
+/// applied to every thread of `pid`; threads created later inherit it.
+#[cfg(target_os = "linux")]
+fn set_affinity(pid: u32, cpus: &[usize]) -> AppResult<()> {
+    let max = libc::CPU_SETSIZE as usize;
+    if let Some(cpu) = cpus.iter().find(|&&cpu| cpu >= max) {
+        return Err(AppError::InvalidArgument(format!("no such CPU {}", cpu)));
+    }
+    // SAFETY: cpu_set_t is a plain bitmask and every index is below
+    // CPU_SETSIZE.
+    let set = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        set
+    };
+    let threads = for_each_thread(pid, "set CPU affinity of", |tid| {
+        // SAFETY: the size passed matches the set.
+        unsafe {
+            libc::sched_setaffinity(
+                tid as libc::pid_t,
+                std::mem::size_of::<libc::cpu_set_t>(),
+                &set,
+            )
+        }
+    })?;
+    log::info!(
+        "Set CPU affinity of {} ({} threads) to {:?}",
+        pid,
+        threads,
+        cpus
+    );
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_pid: u32, _cpus: &[usize]) -> AppResult<()> {
+    Err(AppError::Unsupported("CPU affinity".to_string()))
+}
+
+/// Calls `call` with every thread id of `pid` (just `pid` without
+/// `/proc`) and returns how many threads it succeeded for. Threads that
+/// exit in the meantime are skipped.
+#[cfg(unix)]
+fn for_each_thread(
+    pid: u32,
+    operation: &str,
+    mut call: impl FnMut(u32) -> libc::c_int,
+) -> AppResult<usize> {
+    let mut tids: Vec<u32> = fs::read_dir(format!("/proc/{}/task", pid))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if tids.is_empty() {
+        tids.push(pid);
+    }
+
+    let mut done = 0;
+    for tid in tids {
+        if call(tid) == 0 {
+            done += 1;
+            continue;
+        }
+        let err = os_error(operation, pid);
+        if tid != pid && matches!(err, AppError::NotFound(_)) {
+            continue;
+        }
+        return Err(err);
+    }
+    Ok(done)
+}
+
+/// Maps the errno of a failed call so the UI can tell "not allowed" from
+/// "already gone".
+#[cfg(unix)]
+fn os_error(operation: &str, pid: u32) -> AppError {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => {
+            AppError::PermissionDenied(format!("{} process {}", operation, pid))
+        }
+        Some(libc::ESRCH) => AppError::NotFound(format!("process {}", pid)),
+        Some(libc::EINVAL) => {
+            AppError::InvalidArgument(format!("cannot {} process {}: {}", operation, pid, err))
+        }
+        _ => AppError::io_context(format!("Failed to {} process {}", operation, pid), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_protects_init_and_the_app_itself() {
+        for pid in [0, 1, std::process::id()] {
+            assert!(matches!(guard(pid), Err(AppError::ProtectedProcess(p)) if p == pid));
+        }
+        assert!(guard(std::process::id() + 1).is_ok());
+    }
+
+    #[test]
+    fn guard_rejects_pids_that_are_negative_as_pid_t() {
+        assert!(guard(i32::MAX as u32).is_ok());
+        for pid in [i32::MAX as u32 + 1, u32::MAX - 1, u32::MAX] {
+            assert!(matches!(guard(pid), Err(AppError::InvalidArgument(_))));
+        }
+        let kill = ProcessAction::Signal {
+            signal: ProcessSignal::Kill,
+        };
+        assert!(matches!(
+            apply(u32::MAX, &kill),
+            Err(AppError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn niceness_must_be_in_range() {
+        for nice in [-20, 0, 19] {
+            assert!(validate(&ProcessAction::Renice { nice }).is_ok());
+        }
+        for nice in [-21, 20] {
+            assert!(matches!(
+                validate(&ProcessAction::Renice { nice }),
+                Err(AppError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn affinity_needs_a_cpu() {
+        assert!(matches!(
+            validate(&ProcessAction::SetAffinity { cpus: Vec::new() }),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert!(validate(&ProcessAction::SetAffinity { cpus: vec![0] }).is_ok());
+        assert!(validate(&ProcessAction::Signal {
+            signal: ProcessSignal::Kill
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn apply_checks_before_acting() {
+        let kill = ProcessAction::Signal {
+            signal: ProcessSignal::Kill,
+        };
+        assert!(matches!(
+            apply(1, &kill),
+            Err(AppError::ProtectedProcess(1))
+        ));
+        assert!(matches!(
+            apply(std::process::id(), &kill),
+            Err(AppError::ProtectedProcess(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn actions_reach_a_child_process() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let nice = || {
+            let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+            let fields: Vec<&str> = stat
+                .rsplit_once(')')
+                .unwrap()
+                .1
+                .split_whitespace()
+                .collect();
+            // nice is field 19, the 17th after the command name.
+            fields[16].parse::<i32>().unwrap()
+        };
+
+        apply(pid, &ProcessAction::Renice { nice: 19 }).unwrap();
+        assert_eq!(nice(), 19);
+        // Some CPU this test may run on; containers do not always allow CPU 0.
+        // SAFETY: the size passed matches the set.
+        let cpu = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set);
+            (0..libc::CPU_SETSIZE as usize)
+                .find(|&cpu| libc::CPU_ISSET(cpu, &set))
+                .unwrap()
+        };
+        apply(pid, &ProcessAction::SetAffinity { cpus: vec![cpu] }).unwrap();
+        assert!(matches!(
+            apply(
+                pid,
+                &ProcessAction::SetAffinity {
+                    cpus: vec![usize::MAX]
+                }
+            ),
+            Err(AppError::InvalidArgument(_))
+        ));
+
+        apply(
+            pid,
+            &ProcessAction::Signal {
+                signal: ProcessSignal::Kill,
+            },
+        )
+        .unwrap();
+        assert!(!child.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::models::process::FileWriter;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Access mode bits of the `flags` in `/proc/<pid>/fdinfo/<fd>`
+/// (`O_WRONLY` = 1, `O_RDWR` = 2).
+const O_ACCMODE: u32 = 0o3;
+
+/// Processes that have `file` open for writing, found by resolving every
+/// `<proc_root>/<pid>/fd/*` link and checking the descriptor's access mode
+/// in `fdinfo`. Processes the app may not inspect are skipped, and without
+/// a Linux `/proc` nothing is found.
+///
+/// ```
+/// use app_lib::process::file_writers::find_writers;
+/// use std::fs;
+///
+/// let root = std::env::temp_dir().join(format!("writers-doctest-{}", std::process::id()));
+/// let log = root.join("app.log");
+/// fs::create_dir_all(&root).unwrap();
+/// fs::write(&log, "").unwrap();
+/// // pid 100 opened the log write-only, pid 200 read-only.
+/// for (pid, flags) in [(100, "0100001"), (200, "0100000")] {
+///     let dir = root.join(pid.to_string());
+///     fs::create_dir_all(dir.join("fd")).unwrap();
+///     fs::create_dir_all(dir.join("fdinfo")).unwrap();
+///     fs::write(dir.join("comm"), format!("proc{}\n", pid)).unwrap();
+///     fs::write(dir.join("fdinfo/3"), format!("pos:\t0\nflags:\t{}\n", flags)).unwrap();
+///     # #[cfg(unix)]
+///     std::os::unix::fs::symlink(&log, dir.join("fd/3")).unwrap();
+/// }
+///
+/// # #[cfg(unix)]
+/// # {
+/// let writers = find_writers(&root, &log);
+/// assert_eq!(writers.len(), 1);
+/// assert_eq!(writers[0].pid, 100);
+/// assert_eq!(writers[0].name, "proc100");
+/// assert_eq!(writers[0].fds, [3]);
+/// # }
+/// fs::remove_dir_all(&root).unwrap();
+/// ```
+pub fn find_writers(proc_root: &Path, file: &Path) -> Vec<FileWriter> {
+    let mut writers = Vec::new();
+    let target = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    // A file deleted (e.g. rotated away) while open links to "<path> (deleted)".
+    let deleted = PathBuf::from(format!("{} (deleted)", target.display()));
+    let Ok(entries) = fs::read_dir(proc_root) else {
+        return writers;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let dir = entry.path();
+        let Ok(fds) = fs::read_dir(dir.join("fd")) else {
+            continue;
+        };
+        let mut matching: Vec<u32> = fds
+            .flatten()
+            .filter(|fd| {
+                fs::read_link(fd.path()).is_ok_and(|link| link == target || link == deleted)
+            })
+            .filter_map(|fd| fd.file_name().to_str()?.parse().ok())
+            .filter(|fd| opened_for_writing(&dir, *fd))
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        matching.sort_unstable();
+        writers.push(FileWriter {
+            pid,
+            name: fs::read_to_string(dir.join("comm"))
+                .map(|comm| comm.trim().to_string())
+                .unwrap_or_default(),
+            fds: matching,
+            detail: None,
+        });
+    }
+    writers.sort_by_key(|w| w.pid);
+    writers
+}
+
+fn opened_for_writing(dir: &Path, fd: u32) -> bool {
+    fs::read_to_string(dir.join("fdinfo").join(fd.to_string()))
+        .ok()
+        .and_then(|info| {
+            let flags = info
+                .lines()
+                .find_map(|line| line.strip_prefix("flags:"))?
+                .trim();
+            u32::from_str_radix(flags, 8).ok()
+        })
+        .is_some_and(|flags| flags & O_ACCMODE != 0)
+}
@@ -0,0 +1,194 @@
+use crate::{
+    error::{AppError, AppResult},
+    models::system_info::LocalProcessInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use ts_rs::TS;
+
+/// File in the app data directory the filter is saved to.
+pub const PROCESS_FILTER_FILE: &str = "process_filter.json";
+
+/// Linux `PF_KTHREAD` bit of the flags field in `/proc/<pid>/stat`.
+const PF_KTHREAD: u64 = 0x0020_0000;
+
+/// Inclusive pid range.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+pub struct PidRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Which processes the process list hides unless "show all" is on.
+///
+/// ```
+/// use app_lib::{models::system_info::LocalProcessInfo, process::filter::ProcessFilter};
+///
+/// let filter = ProcessFilter {
+///     hidden_users: vec!["nobody".into()],
+///     hidden_names: vec!["kworker/*".into()],
+///     ..ProcessFilter::default()
+/// };
+/// let process = |name: &str, user: &str| LocalProcessInfo {
+///     pid: 4242,
+///     name: name.into(),
+///     user: Some(user.into()),
+///     ..LocalProcessInfo::default()
+/// };
+///
+/// assert!(filter.hides(&process("kworker/0:1", "root")));
+/// assert!(filter.hides(&process("dnsmasq", "nobody")));
+/// assert!(!filter.hides(&process("postgres", "postgres")));
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(default)]
+pub struct ProcessFilter {
+    /// Hides kernel threads. Only detectable on Linux.
+    pub hide_kernel_threads: bool,
+    pub hidden_users: Vec<String>,
+    /// Glob patterns (`*` and `?`) matched against the whole process name.
+    pub hidden_names: Vec<String>,
+    pub hidden_pid_ranges: Vec<PidRange>,
+}
+
+impl Default for ProcessFilter {
+    fn default() -> Self {
+        let (hidden_names, hidden_pid_ranges) = if cfg!(target_os = "windows") {
+            (
+                ["System", "Registry", "svchost.exe", "dllhost.exe"]
+                    .map(String::from)
+                    .to_vec(),
+                vec![PidRange { start: 0, end: 4 }],
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        Self {
+            hide_kernel_threads: true,
+            hidden_users: Vec::new(),
+            hidden_names,
+            hidden_pid_ranges,
+        }
+    }
+}
+
+impl ProcessFilter {
+    pub fn validate(&self) -> AppResult<()> {
+        if let Some(range) = self.hidden_pid_ranges.iter().find(|r| r.start > r.end) {
+            return Err(AppError::InvalidArgument(format!(
+                "pid range {}-{} is empty",
+                range.start, range.end
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn hides(&self, process: &LocalProcessInfo) -> bool {
+        (self.hide_kernel_threads && process.kernel_thread)
+            || self
+                .hidden_pid_ranges
+                .iter()
+                .any(|r| (r.start..=r.end).contains(&process.pid))
+            || process
+                .user
+                .as_ref()
+                .is_some_and(|user| self.hidden_users.contains(user))
+            || self
+                .hidden_names
+                .iter()
+                .any(|pattern| glob_match(pattern, &process.name))
+    }
+}
+
+/// Whether `pid` is a kernel thread according to `<proc_root>/<pid>/stat`.
+#[cfg(target_os = "linux")]
+pub fn is_kernel_thread(proc_root: &Path, pid: u32) -> bool {
+    let Ok(stat) = std::fs::read_to_string(proc_root.join(pid.to_string()).join("stat")) else {
+        return false;
+    };
+    // The command name may contain spaces and parentheses; fields resume
+    // after the last ')' with the state, and flags is the 7th from there.
+    stat.rsplit_once(')')
+        .and_then(|(_, rest)| rest.split_whitespace().nth(6))
+        .and_then(|flags| flags.parse::<u64>().ok())
+        .is_some_and(|flags| flags & PF_KTHREAD != 0)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_kernel_thread(_proc_root: &Path, _pid: u32) -> bool {
+    false
+}
+
+/// Shell-style glob over the whole string: `*` matches any run of
+/// characters, `?` exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it currently stands for.
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_patterns_match_the_whole_name() {
+        assert!(glob_match("sshd", "sshd"));
+        assert!(!glob_match("sshd", "sshd2"));
+        assert!(!glob_match("sshd", "ssh"));
+    }
+
+    #[test]
+    fn empty_pattern_matches_only_an_empty_name() {
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "init"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn stars_match_any_run() {
+        assert!(glob_match("kworker/*", "kworker/0:1H"));
+        assert!(glob_match("*d", "systemd"));
+        assert!(glob_match("*sys*", "systemd"));
+        assert!(glob_match("*sys*", "sys"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(glob_match("**", "anything"));
+    }
+
+    #[test]
+    fn question_mark_matches_exactly_one_character() {
+        assert!(glob_match("tty?", "tty1"));
+        assert!(!glob_match("tty?", "tty"));
+        assert!(!glob_match("tty?", "tty12"));
+        assert!(glob_match("?*", "x"));
+        assert!(!glob_match("?*", ""));
+        assert!(glob_match("ж?к", "жук"));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod control;
+pub mod detail;
+pub mod file_writers;
+pub mod filter;
+pub mod tree;
+pub mod watch;
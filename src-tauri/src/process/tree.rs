@@ -0,0 +1,79 @@
+use crate::models::{process::ProcessTreeNode, system_info::LocalProcessInfo};
+use std::collections::{HashMap, HashSet};
+
+/// Arranges a flat process list by parent pid, summing CPU and memory over
+/// each subtree. Processes whose parent is not in the list become roots.
+/// Siblings are ordered by subtree CPU usage, highest first.
+///
+/// ```
+/// use app_lib::{models::system_info::LocalProcessInfo, process::tree::build_tree};
+///
+/// let process = |pid, parent_pid, cpu_usage| LocalProcessInfo {
+///     pid,
+///     parent_pid,
+///     cpu_usage,
+///     memory: 100,
+///     ..LocalProcessInfo::default()
+/// };
+/// let tree = build_tree(&[
+///     process(10, Some(1), 1.0),
+///     process(11, Some(10), 5.0),
+///     process(12, Some(10), 2.0),
+///     process(20, Some(1), 3.0),
+/// ]);
+///
+/// assert_eq!(tree.len(), 2);
+/// assert_eq!(tree[0].process.pid, 10);
+/// assert_eq!(tree[0].subtree_cpu_usage, 8.0);
+/// assert_eq!(tree[0].subtree_memory, 300);
+/// assert_eq!(tree[0].subtree_count, 3);
+/// assert_eq!(tree[0].children[0].process.pid, 11);
+/// ```
+pub fn build_tree(processes: &[LocalProcessInfo]) -> Vec<ProcessTreeNode> {
+    let known: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+    let mut children: HashMap<u32, Vec<&LocalProcessInfo>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for process in processes {
+        match process.parent_pid {
+            Some(parent) if parent != process.pid && known.contains(&parent) => {
+                children.entry(parent).or_default().push(process)
+            }
+            _ => roots.push(process),
+        }
+    }
+
+    let mut tree: Vec<_> = roots.into_iter().map(|p| node(p, &children)).collect();
+    sort_by_cpu(&mut tree);
+    tree
+}
+
+fn node(
+    process: &LocalProcessInfo,
+    children: &HashMap<u32, Vec<&LocalProcessInfo>>,
+) -> ProcessTreeNode {
+    let mut kids: Vec<_> = children
+        .get(&process.pid)
+        .into_iter()
+        .flatten()
+        .map(|child| node(child, children))
+        .collect();
+    sort_by_cpu(&mut kids);
+
+    ProcessTreeNode {
+        subtree_cpu_usage: process.cpu_usage
+            + kids.iter().map(|k| k.subtree_cpu_usage).sum::<f32>(),
+        subtree_memory: process.memory + kids.iter().map(|k| k.subtree_memory).sum::<u64>(),
+        subtree_count: 1 + kids.iter().map(|k| k.subtree_count).sum::<usize>(),
+        process: process.clone(),
+        children: kids,
+    }
+}
+
+fn sort_by_cpu(nodes: &mut [ProcessTreeNode]) {
+    nodes.sort_by(|a, b| {
+        b.subtree_cpu_usage
+            .partial_cmp(&a.subtree_cpu_usage)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
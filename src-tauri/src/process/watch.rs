@@ -0,0 +1,472 @@
+use super::filter::glob_match;
+use crate::{
+    error::{AppError, AppResult},
+    models::system_info::LocalProcessInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use ts_rs::TS;
+
+/// File in the app data directory the rules are saved to.
+pub const WATCH_RULES_FILE: &str = "watch_rules.json";
+
+/// Watches the processes whose name matches `process` and acts when
+/// `condition` holds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct WatchRule {
+    /// Assigned when the rule is first saved.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Glob pattern (`*` and `?`) matched against the whole process name.
+    pub process: String,
+    pub condition: WatchCondition,
+    #[serde(default)]
+    pub actions: Vec<WatchAction>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// When a watch rule acts. CPU thresholds are per core like `top`: 100%
+/// is one core busy, and a process can reach 100% times the core count.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchCondition {
+    /// CPU usage above `percent` for `for_secs`, in percent of one core
+    /// (unlike [`LocalProcessInfo::cpu_usage`], which is a share of all).
+    CpuAbove {
+        /// Percent of one core; 100 is one core busy.
+        percent: f32,
+        #[ts(type = "number")]
+        for_secs: u64,
+    },
+    /// Resident memory above `bytes` for `for_secs`.
+    MemoryAbove {
+        #[ts(type = "number")]
+        bytes: u64,
+        #[ts(type = "number")]
+        for_secs: u64,
+    },
+    /// A matching process was running and none is anymore.
+    Disappears,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchAction {
+    /// Desktop notification.
+    Notify,
+    /// Started without waiting for it, with `WATCH_RULE`, `WATCH_PID` and
+    /// `WATCH_PROCESS` in its environment.
+    RunScript {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// Restarts the command launched as a log source.
+    RestartCommand,
+}
+
+/// A rule whose condition started to hold.
+#[derive(Serialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub struct WatchTrigger {
+    pub rule_id: String,
+    pub rule_name: String,
+    /// The offending process; none for [`WatchCondition::Disappears`].
+    pub pid: Option<u32>,
+    pub process_name: String,
+    pub message: String,
+    #[ts(type = "number")]
+    pub at_ms: i64,
+}
+
+impl WatchRule {
+    /// Checks the rule on a machine with `cores` logical CPUs.
+    pub fn validate(&self, cores: usize) -> AppResult<()> {
+        if self.process.trim().is_empty() {
+            return Err(AppError::InvalidArgument(
+                "a process name pattern is required".to_string(),
+            ));
+        }
+        if let WatchCondition::CpuAbove { percent, .. } = self.condition {
+            let max = 100.0 * cores.max(1) as f32;
+            if percent.is_nan() || percent <= 0.0 || percent >= max {
+                return Err(AppError::InvalidArgument(format!(
+                    "CPU threshold must be between 0 and {}% (100% per core), got {}",
+                    max, percent
+                )));
+            }
+        }
+        for action in &self.actions {
+            if let WatchAction::RunScript { program, .. } = action {
+                if program.trim().is_empty() {
+                    return Err(AppError::InvalidArgument(
+                        "script action needs a program".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the engine remembers about one rule between samples.
+#[derive(Default)]
+struct RuleTracker {
+    /// Since when each matching process has been over the threshold.
+    over_since: HashMap<u32, i64>,
+    /// Processes already reported for the current stretch over the threshold.
+    fired: HashSet<u32>,
+    /// Whether a matching process was running in the previous sample.
+    seen: bool,
+}
+
+/// Evaluates [`WatchRule`]s against successive process lists. Each condition
+/// triggers once when it starts to hold and again only after it stopped.
+///
+/// ```
+/// use app_lib::{
+///     models::system_info::LocalProcessInfo,
+///     process::watch::{WatchCondition, WatchEngine, WatchRule},
+/// };
+///
+/// let rule = |id: &str, condition| WatchRule {
+///     id: id.into(),
+///     name: id.into(),
+///     enabled: true,
+///     process: "server*".into(),
+///     condition,
+///     actions: Vec::new(),
+/// };
+/// let rules = [
+///     rule("hot", WatchCondition::CpuAbove { percent: 90.0, for_secs: 60 }),
+///     rule("gone", WatchCondition::Disappears),
+/// ];
+/// let server = |cpu_usage| LocalProcessInfo {
+///     pid: 42,
+///     name: "server-main".into(),
+///     cpu_usage,
+///     ..LocalProcessInfo::default()
+/// };
+///
+/// let mut engine = WatchEngine::default();
+/// // One core of eight fully busy is 12.5% of the machine and 100% CPU.
+/// let cores = 8;
+/// assert!(engine.evaluate(&rules, 0, &[server(12.0)], cores).is_empty());
+/// assert!(engine.evaluate(&rules, 30_000, &[server(12.5)], cores).is_empty());
+/// let fired = engine.evaluate(&rules, 60_000, &[server(12.0)], cores);
+/// assert_eq!(fired.len(), 1);
+/// assert_eq!((fired[0].rule_id.as_str(), fired[0].pid), ("hot", Some(42)));
+/// // Still hot: not reported again.
+/// assert!(engine.evaluate(&rules, 61_000, &[server(12.0)], cores).is_empty());
+///
+/// let fired = engine.evaluate(&rules, 62_000, &[], cores);
+/// assert_eq!(fired.len(), 1);
+/// assert_eq!(fired[0].rule_id, "gone");
+/// ```
+#[derive(Default)]
+pub struct WatchEngine {
+    trackers: HashMap<String, RuleTracker>,
+}
+
+impl WatchEngine {
+    /// Checks `processes`, sampled at `now_ms` on a machine with `cores`
+    /// logical CPUs, and returns the rules that triggered. Disabled rules are
+    /// skipped and forget their progress.
+    pub fn evaluate(
+        &mut self,
+        rules: &[WatchRule],
+        now_ms: i64,
+        processes: &[LocalProcessInfo],
+        cores: usize,
+    ) -> Vec<WatchTrigger> {
+        self.trackers
+            .retain(|id, _| rules.iter().any(|r| r.enabled && &r.id == id));
+        let mut triggers = Vec::new();
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let tracker = self.trackers.entry(rule.id.clone()).or_default();
+            let matching: Vec<&LocalProcessInfo> = processes
+                .iter()
+                .filter(|p| glob_match(&rule.process, &p.name))
+                .collect();
+
+            let for_secs = match rule.condition {
+                WatchCondition::CpuAbove { for_secs, .. }
+                | WatchCondition::MemoryAbove { for_secs, .. } => for_secs,
+                WatchCondition::Disappears => {
+                    if tracker.seen && matching.is_empty() {
+                        triggers.push(WatchTrigger {
+                            rule_id: rule.id.clone(),
+                            rule_name: rule.name.clone(),
+                            pid: None,
+                            process_name: rule.process.clone(),
+                            message: format!("no process matching {} is running", rule.process),
+                            at_ms: now_ms,
+                        });
+                    }
+                    tracker.seen = !matching.is_empty();
+                    continue;
+                }
+            };
+
+            let hot: Vec<&LocalProcessInfo> = matching
+                .into_iter()
+                .filter(|p| over_threshold(&rule.condition, p, cores))
+                .collect();
+            tracker
+                .over_since
+                .retain(|pid, _| hot.iter().any(|p| p.pid == *pid));
+            tracker
+                .fired
+                .retain(|pid| tracker.over_since.contains_key(pid));
+
+            for process in hot {
+                let since = *tracker.over_since.entry(process.pid).or_insert(now_ms);
+                if now_ms - since >= for_secs as i64 * 1000 && tracker.fired.insert(process.pid) {
+                    triggers.push(WatchTrigger {
+                        rule_id: rule.id.clone(),
+                        rule_name: rule.name.clone(),
+                        pid: Some(process.pid),
+                        process_name: process.name.clone(),
+                        message: describe(&rule.condition, process, cores),
+                        at_ms: now_ms,
+                    });
+                }
+            }
+        }
+        triggers
+    }
+
+    /// Forgets the progress of a rule, e.g. after it was edited.
+    pub fn reset(&mut self, rule_id: &str) {
+        self.trackers.remove(rule_id);
+    }
+}
+
+/// `cpu_usage` (a share of all `cores`) in percent of one core.
+fn per_core_usage(process: &LocalProcessInfo, cores: usize) -> f32 {
+    process.cpu_usage * cores as f32
+}
+
+fn over_threshold(condition: &WatchCondition, process: &LocalProcessInfo, cores: usize) -> bool {
+    match *condition {
+        WatchCondition::CpuAbove { percent, .. } => per_core_usage(process, cores) > percent,
+        WatchCondition::MemoryAbove { bytes, .. } => process.memory > bytes,
+        WatchCondition::Disappears => false,
+    }
+}
+
+fn describe(condition: &WatchCondition, process: &LocalProcessInfo, cores: usize) -> String {
+    match condition {
+        WatchCondition::CpuAbove { percent, for_secs } => format!(
+            "{} ({}) at {:.0}% CPU, above {}% for {} s",
+            process.name,
+            process.pid,
+            per_core_usage(process, cores),
+            percent,
+            for_secs
+        ),
+        WatchCondition::MemoryAbove { bytes, for_secs } => format!(
+            "{} ({}) uses {} MiB, above {} MiB for {} s",
+            process.name,
+            process.pid,
+            process.memory / (1024 * 1024),
+            bytes / (1024 * 1024),
+            for_secs
+        ),
+        WatchCondition::Disappears => format!("{} ({}) exited", process.name, process.pid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(condition: WatchCondition) -> WatchRule {
+        WatchRule {
+            id: "rule".into(),
+            name: "rule".into(),
+            enabled: true,
+            process: "worker?".into(),
+            condition,
+            actions: Vec::new(),
+        }
+    }
+
+    fn worker(pid: u32, memory: u64) -> LocalProcessInfo {
+        LocalProcessInfo {
+            pid,
+            name: format!("worker{}", pid),
+            memory,
+            ..LocalProcessInfo::default()
+        }
+    }
+
+    fn fired_pids(triggers: Vec<WatchTrigger>) -> Vec<Option<u32>> {
+        triggers.into_iter().map(|t| t.pid).collect()
+    }
+
+    const MEMORY: WatchCondition = WatchCondition::MemoryAbove {
+        bytes: 100,
+        for_secs: 10,
+    };
+
+    #[test]
+    fn condition_fires_once_it_held_for_the_whole_duration() {
+        let rules = [rule(MEMORY)];
+        let mut engine = WatchEngine::default();
+        assert!(engine.evaluate(&rules, 0, &[worker(1, 200)], 1).is_empty());
+        assert!(engine
+            .evaluate(&rules, 9_999, &[worker(1, 200)], 1)
+            .is_empty());
+        assert_eq!(
+            fired_pids(engine.evaluate(&rules, 10_000, &[worker(1, 200)], 1)),
+            [Some(1)]
+        );
+    }
+
+    #[test]
+    fn dropping_below_restarts_the_stretch_and_allows_a_new_trigger() {
+        let rules = [rule(MEMORY)];
+        let mut engine = WatchEngine::default();
+        engine.evaluate(&rules, 0, &[worker(1, 200)], 1);
+        assert_eq!(
+            engine.evaluate(&rules, 10_000, &[worker(1, 200)], 1).len(),
+            1
+        );
+
+        // Exactly at the threshold is not above it.
+        assert!(engine
+            .evaluate(&rules, 11_000, &[worker(1, 100)], 1)
+            .is_empty());
+        assert!(engine
+            .evaluate(&rules, 12_000, &[worker(1, 200)], 1)
+            .is_empty());
+        assert!(engine
+            .evaluate(&rules, 21_000, &[worker(1, 200)], 1)
+            .is_empty());
+        assert_eq!(
+            engine.evaluate(&rules, 22_000, &[worker(1, 200)], 1).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn each_matching_process_is_tracked_on_its_own() {
+        let rules = [rule(MEMORY)];
+        let mut engine = WatchEngine::default();
+        engine.evaluate(&rules, 0, &[worker(1, 200), worker(2, 50)], 1);
+        engine.evaluate(&rules, 5_000, &[worker(1, 200), worker(2, 200)], 1);
+        assert_eq!(
+            fired_pids(engine.evaluate(&rules, 10_000, &[worker(1, 200), worker(2, 200)], 1)),
+            [Some(1)]
+        );
+        assert_eq!(
+            fired_pids(engine.evaluate(&rules, 15_000, &[worker(1, 200), worker(2, 200)], 1)),
+            [Some(2)]
+        );
+    }
+
+    #[test]
+    fn disabling_or_resetting_a_rule_forgets_its_progress() {
+        let mut rules = [rule(MEMORY)];
+        let mut engine = WatchEngine::default();
+        engine.evaluate(&rules, 0, &[worker(1, 200)], 1);
+
+        rules[0].enabled = false;
+        assert!(engine
+            .evaluate(&rules, 10_000, &[worker(1, 200)], 1)
+            .is_empty());
+        rules[0].enabled = true;
+        assert!(engine
+            .evaluate(&rules, 11_000, &[worker(1, 200)], 1)
+            .is_empty());
+        assert_eq!(
+            engine.evaluate(&rules, 21_000, &[worker(1, 200)], 1).len(),
+            1
+        );
+
+        engine.reset("rule");
+        assert!(engine
+            .evaluate(&rules, 22_000, &[worker(1, 200)], 1)
+            .is_empty());
+        assert_eq!(
+            engine.evaluate(&rules, 32_000, &[worker(1, 200)], 1).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn cpu_threshold_is_per_core() {
+        let rules = [rule(WatchCondition::CpuAbove {
+            percent: 90.0,
+            for_secs: 0,
+        })];
+        let busy_core = LocalProcessInfo {
+            pid: 1,
+            name: "worker1".into(),
+            // One of eight cores.
+            cpu_usage: 12.5,
+            ..LocalProcessInfo::default()
+        };
+        let mut engine = WatchEngine::default();
+        assert!(engine
+            .evaluate(&rules, 0, std::slice::from_ref(&busy_core), 1)
+            .is_empty());
+        let fired = engine.evaluate(&rules, 1_000, &[busy_core], 8);
+        assert_eq!(fired.len(), 1);
+        assert!(
+            fired[0].message.contains("at 100% CPU"),
+            "{}",
+            fired[0].message
+        );
+    }
+
+    #[test]
+    fn disappearing_needs_a_process_seen_before() {
+        let rules = [rule(WatchCondition::Disappears)];
+        let mut engine = WatchEngine::default();
+        assert!(engine.evaluate(&rules, 0, &[], 1).is_empty());
+        assert!(engine
+            .evaluate(&rules, 1_000, &[worker(1, 0)], 1)
+            .is_empty());
+        assert_eq!(fired_pids(engine.evaluate(&rules, 2_000, &[], 1)), [None]);
+        assert!(engine.evaluate(&rules, 3_000, &[], 1).is_empty());
+    }
+
+    #[test]
+    fn rules_are_validated() {
+        let cpu = |percent| {
+            rule(WatchCondition::CpuAbove {
+                percent,
+                for_secs: 0,
+            })
+        };
+        assert!(cpu(50.0).validate(1).is_ok());
+        assert!(cpu(350.0).validate(4).is_ok());
+        assert!(cpu(100.0).validate(1).is_err());
+        for percent in [0.0, -5.0, 400.0, f32::NAN] {
+            assert!(cpu(percent).validate(4).is_err(), "{} accepted", percent);
+        }
+
+        let mut blank = rule(MEMORY);
+        blank.process = " ".into();
+        assert!(blank.validate(1).is_err());
+
+        let mut script = rule(MEMORY);
+        script.actions = vec![WatchAction::RunScript {
+            program: String::new(),
+            args: Vec::new(),
+        }];
+        assert!(script.validate(1).is_err());
+    }
+}
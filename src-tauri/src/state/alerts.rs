@@ -0,0 +1,98 @@
+use crate::alerts::{
+    logs::{LogAlertEngine, LogAlertRule},
+    metric::{MetricAlertEngine, MetricAlertRule},
+    AlertHistory,
+};
+use std::sync::Mutex;
+#[cfg(feature = "gui")]
+use {
+    super::events::{emit_backend_event, notify_desktop},
+    crate::{
+        alerts::{AlertRecord, AlertStatus},
+        models::{events::BackendEvent, log_entry::LogEntry},
+        utils::sync::LockExt,
+    },
+    chrono::Utc,
+    tauri::{AppHandle, Manager, Runtime},
+};
+
+/// Alert rules, their evaluation state and the history. Metric rules are
+/// checked by the sampler after every sample, log rules by the tail pipeline
+/// for every batch of lines.
+pub struct AlertsState {
+    pub metric_rules: Mutex<Vec<MetricAlertRule>>,
+    pub metric_engine: Mutex<MetricAlertEngine>,
+    pub log_rules: Mutex<Vec<LogAlertRule>>,
+    pub log_engine: Mutex<LogAlertEngine>,
+    /// Replaced by the file-backed history at startup when there is an app
+    /// data directory.
+    pub history: Mutex<AlertHistory>,
+}
+
+impl AlertsState {
+    pub fn new() -> Self {
+        Self {
+            metric_rules: Mutex::new(Vec::new()),
+            metric_engine: Mutex::new(MetricAlertEngine::default()),
+            log_rules: Mutex::new(Vec::new()),
+            log_engine: Mutex::new(LogAlertEngine::default()),
+            history: Mutex::new(AlertHistory::in_memory()),
+        }
+    }
+}
+
+impl Default for AlertsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Records `record` in the history and sends it to the UI. Firing and
+/// resolved alerts also show a desktop notification when `notify` is set.
+#[cfg(feature = "gui")]
+pub fn raise_alert<R: Runtime>(app_handle: &AppHandle<R>, record: AlertRecord, notify: bool) {
+    log::info!(
+        "Alert {} {:?}: {}",
+        record.rule_name,
+        record.status,
+        record.message
+    );
+    if let Some(alerts) = app_handle.try_state::<AlertsState>() {
+        if let Err(e) = alerts.history.lock_or_recover().push(record.clone()) {
+            log::warn!("Failed to persist alert: {}", e);
+        }
+    }
+    if notify {
+        let title = match record.status {
+            AlertStatus::Pending => None,
+            AlertStatus::Firing => Some(format!("Alert: {}", record.rule_name)),
+            AlertStatus::Resolved => Some(format!("Resolved: {}", record.rule_name)),
+        };
+        if let Some(title) = title {
+            notify_desktop(app_handle, &title, &record.message);
+        }
+    }
+    emit_backend_event(app_handle, BackendEvent::Alert { record });
+}
+
+/// Evaluates the log alert rules against freshly tailed `entries` and raises
+/// the alerts that fired.
+#[cfg(feature = "gui")]
+pub fn check_log_alerts<R: Runtime>(app_handle: &AppHandle<R>, entries: &[LogEntry]) {
+    let Some(alerts) = app_handle.try_state::<AlertsState>() else {
+        return;
+    };
+    let rules = alerts.log_rules.lock_or_recover().clone();
+    if rules.is_empty() {
+        return;
+    }
+    let records = alerts.log_engine.lock_or_recover().evaluate(
+        &rules,
+        Utc::now().timestamp_millis(),
+        entries,
+    );
+    for record in records {
+        let notify = rules.iter().any(|r| r.id == record.rule_id && r.notify);
+        raise_alert(app_handle, record, notify);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    models::{
+        events::{BackendEvent, EventEnvelope},
+        log_entry::LogEntry,
+    },
+    utils::sync::LockExt,
+};
+use std::{
+    process,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+#[cfg(feature = "gui")]
+use {
+    super::alerts::check_log_alerts,
+    crate::models::events::BACKEND_EVENT,
+    tauri::{AppHandle, Emitter, Manager, Runtime},
+    tauri_plugin_notification::NotificationExt,
+};
+
+pub struct EventSequencer {
+    pub session_id: String,
+    next_seq: Mutex<u64>,
+}
+
+impl EventSequencer {
+    pub fn new() -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        Self {
+            session_id: format!("{:x}-{:x}", started, process::id()),
+            next_seq: Mutex::new(0),
+        }
+    }
+
+    /// Stamps `event` with the next seq and passes it to `emit` while still
+    /// holding the counter, so envelopes from concurrent threads (loader,
+    /// file monitor, sampler, command supervisor) leave in seq order.
+    pub fn emit_in_order(&self, event: BackendEvent, emit: impl FnOnce(EventEnvelope)) {
+        let mut next_seq = self.next_seq.lock_or_recover();
+        emit(EventEnvelope::new(
+            self.session_id.clone(),
+            *next_seq,
+            event,
+        ));
+        *next_seq += 1;
+    }
+}
+
+impl Default for EventSequencer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Destination for [`BackendEvent`]s produced by the monitoring and loading
+/// pipelines: the Tauri frontend, or a terminal in headless mode.
+pub trait EventSink {
+    fn send(&self, event: BackendEvent);
+
+    /// Called with every batch of tailed lines before it is sent, for the
+    /// log alert rules. Sinks without alerting ignore it.
+    fn check_log_alerts(&self, _entries: &[LogEntry]) {}
+}
+
+#[cfg(feature = "gui")]
+impl<R: Runtime> EventSink for AppHandle<R> {
+    fn send(&self, event: BackendEvent) {
+        emit_backend_event(self, event);
+    }
+
+    fn check_log_alerts(&self, entries: &[LogEntry]) {
+        check_log_alerts(self, entries);
+    }
+}
+
+#[cfg(feature = "gui")]
+pub fn emit_backend_event<R: Runtime>(app_handle: &AppHandle<R>, event: BackendEvent) {
+    app_handle
+        .state::<EventSequencer>()
+        .emit_in_order(event, |envelope| {
+            if let Err(e) = app_handle.emit(BACKEND_EVENT, envelope) {
+                log::warn!("Failed to emit backend event: {}", e);
+            }
+        });
+}
+
+/// Shows a desktop notification; failures are only logged.
+#[cfg(feature = "gui")]
+pub fn notify_desktop<R: Runtime>(app_handle: &AppHandle<R>, title: &str, body: &str) {
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
@@ -1,13 +1,22 @@
-use serde::Serialize;
-use tokio_util::sync::CancellationToken;
 use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 pub struct LoadingState {
     pub cancel_token: Mutex<Option<CancellationToken>>,
     pub is_loading: Mutex<bool>,
 }
-#[derive(Serialize, Clone)]
-pub struct LoadProgress {
-    pub(crate) current: usize,
-    pub(crate) total: usize,
-}
\ No newline at end of file
+
+impl LoadingState {
+    pub fn new() -> Self {
+        Self {
+            cancel_token: Mutex::new(None),
+            is_loading: Mutex::new(false),
+        }
+    }
+}
+
+impl Default for LoadingState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod alerts;
+pub mod events;
+pub mod logs;
+pub mod process;
 pub mod system;
-pub mod logs;
\ No newline at end of file
@@ -0,0 +1,84 @@
+use crate::{
+    models::process::ProcessAction,
+    process::watch::{WatchEngine, WatchRule},
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long a confirmation token stays valid.
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(30);
+
+/// An action requested but not yet confirmed.
+pub struct PendingAction {
+    pub pid: u32,
+    /// Start time of the process when the action was requested, so a pid
+    /// reused in the meantime is not hit by mistake.
+    pub start_time: u64,
+    pub action: ProcessAction,
+    pub expires_at_ms: i64,
+}
+
+pub struct ProcessControlState {
+    pub pending: Mutex<HashMap<String, PendingAction>>,
+    counter: AtomicU64,
+}
+
+impl ProcessControlState {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// A fresh token that cannot be predicted from earlier ones.
+    pub fn next_token(&self) -> String {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let mut hasher = Sha256::new();
+        hasher.update(nanos.to_le_bytes());
+        hasher.update(self.counter.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+        hasher.update(std::process::id().to_le_bytes());
+        hasher.finalize()[..12]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+impl Default for ProcessControlState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The process watch rules and their evaluation progress. The rules are
+/// checked by the sampler after every sample.
+pub struct WatchState {
+    pub rules: Mutex<Vec<WatchRule>>,
+    pub engine: Mutex<WatchEngine>,
+}
+
+impl WatchState {
+    pub fn new() -> Self {
+        Self {
+            rules: Mutex::new(Vec::new()),
+            engine: Mutex::new(WatchEngine::default()),
+        }
+    }
+}
+
+impl Default for WatchState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
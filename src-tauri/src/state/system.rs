@@ -1,8 +1,88 @@
-use sysinfo::System;
-use std::sync::{Arc, Mutex};
+use crate::{
+    models::system_info::SystemInfo,
+    monitoring::{
+        disks::BlockDeviceCounters,
+        gpu::{self, GpuProvider},
+        history::MetricsHistory,
+        network::NetworkCounters,
+        power::RaplCounters,
+        process_history::ProcessHistory,
+        rates::RateWindow,
+    },
+    process::{detail::ProcessCounters, filter::ProcessFilter},
+};
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicU64, Mutex},
+};
+use sysinfo::{CpuRefreshKind, RefreshKind, System, SystemExt};
+use systemstat::Platform;
+
+/// Default time between two background samples.
+pub const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
 
 pub struct SystemMonitorState {
     pub sys: Mutex<System>,
     pub stats: systemstat::System,
-    pub nvml: Option<Arc<nvml_wrapper::Nvml>>,
-}
\ No newline at end of file
+    pub gpu: Option<Box<dyn GpuProvider>>,
+    pub block_devices: Mutex<RateWindow<BlockDeviceCounters>>,
+    pub network: Mutex<RateWindow<NetworkCounters>>,
+    pub rapl: Mutex<RateWindow<RaplCounters>>,
+    /// When the last two refreshes happened, to turn sysinfo's per-refresh
+    /// process disk usage into rates.
+    pub refreshes: Mutex<RateWindow<()>>,
+    /// Latest snapshot taken by the background sampler.
+    pub latest: Mutex<Option<SystemInfo>>,
+    pub history: Mutex<MetricsHistory>,
+    pub process_history: Mutex<ProcessHistory>,
+    /// Pid last asked for by the UI; its history is kept even when idle.
+    pub selected_pid: Mutex<Option<u32>>,
+    pub sample_interval_ms: AtomicU64,
+    pub process_filter: Mutex<ProcessFilter>,
+    /// Kernel-thread flag by (pid, start time); it never changes for the
+    /// lifetime of a process so `/proc/<pid>/stat` is read once.
+    pub kernel_threads: Mutex<HashMap<(u32, u64), bool>>,
+    /// I/O and thread counters of the process whose detail was last read,
+    /// by (pid, start time).
+    pub selected_counters: Mutex<Option<(u32, u64, RateWindow<ProcessCounters>)>>,
+}
+
+impl SystemMonitorState {
+    pub fn new() -> Self {
+        Self::with_gpu_provider(gpu::detect())
+    }
+
+    pub fn with_gpu_provider(gpu: Option<Box<dyn GpuProvider>>) -> Self {
+        if let Some(provider) = &gpu {
+            log::info!("Using {} for GPU metrics", provider.name());
+        }
+        Self {
+            // Users rarely change, loading them once is enough to name owners.
+            sys: Mutex::new(System::new_with_specifics(
+                RefreshKind::new()
+                    .with_cpu(CpuRefreshKind::everything())
+                    .with_users_list(),
+            )),
+            stats: systemstat::System::new(),
+            gpu,
+            block_devices: Mutex::new(RateWindow::new()),
+            network: Mutex::new(RateWindow::new()),
+            rapl: Mutex::new(RateWindow::new()),
+            refreshes: Mutex::new(RateWindow::new()),
+            latest: Mutex::new(None),
+            history: Mutex::new(MetricsHistory::default()),
+            process_history: Mutex::new(ProcessHistory::default()),
+            selected_pid: Mutex::new(None),
+            sample_interval_ms: AtomicU64::new(DEFAULT_SAMPLE_INTERVAL_MS),
+            process_filter: Mutex::new(ProcessFilter::default()),
+            kernel_threads: Mutex::new(HashMap::new()),
+            selected_counters: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for SystemMonitorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
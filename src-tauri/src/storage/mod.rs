@@ -0,0 +1,416 @@
+//! Embedded on-disk time-series store for the sampled system metrics.
+//!
+//! Every sample is rolled up into 1m and 1h averages (with min/max). Only
+//! the machine-wide series are also kept at 1s resolution; per-core,
+//! per-GPU, per-sensor and per-mount series would multiply its size by the
+//! hardware count. Each resolution lives in its own directory of
+//! append-only segment files and has its own retention.
+
+mod rollup;
+mod segment;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::metrics::{MetricPoint, MetricsSample, Resolution},
+    utils::sync::LockExt,
+};
+use rollup::Rollup;
+use segment::{Record, SegmentWriter};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+/// Most points a query picks an automatic resolution for.
+const MAX_AUTO_POINTS: i64 = 2000;
+
+/// Whether `metric` is one of several per-device series (`cpu.core.3.usage`,
+/// `gpu.1.temp`, `sensor[...]`, `disk.used[/home]`), which are not stored at
+/// 1s.
+fn is_per_device(metric: &str) -> bool {
+    let indexed = |prefix: &str| {
+        metric
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split('.').next())
+            .is_some_and(|index| index.parse::<u32>().is_ok())
+    };
+    indexed("cpu.core.") || indexed("gpu.") || metric.ends_with(']')
+}
+
+/// How long each resolution is kept.
+#[derive(Clone, Copy, Debug)]
+pub struct Retention {
+    pub second: Duration,
+    pub minute: Duration,
+    pub hour: Duration,
+}
+
+impl Retention {
+    fn millis(&self, resolution: Resolution) -> i64 {
+        let duration = match resolution {
+            Resolution::Second => self.second,
+            Resolution::Minute => self.minute,
+            Resolution::Hour => self.hour,
+        };
+        duration.as_millis() as i64
+    }
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        const DAY: u64 = 24 * 60 * 60;
+        Self {
+            second: Duration::from_secs(DAY),
+            minute: Duration::from_secs(30 * DAY),
+            hour: Duration::from_secs(365 * DAY),
+        }
+    }
+}
+
+struct Writers {
+    second: SegmentWriter,
+    minute: SegmentWriter,
+    hour: SegmentWriter,
+    minute_rollup: Rollup,
+    hour_rollup: Rollup,
+    names: BTreeSet<String>,
+}
+
+/// Persistent metrics history, queried by metric name and time range.
+///
+/// ```
+/// use app_lib::{
+///     models::metrics::{MetricsSample, Resolution},
+///     storage::{MetricStore, Retention},
+/// };
+///
+/// let dir = std::env::temp_dir().join(format!("metric-store-doctest-{}", std::process::id()));
+/// let store = MetricStore::open(&dir, Retention::default()).unwrap();
+/// for (i, cpu) in [10.0, 30.0].into_iter().enumerate() {
+///     store.append(&MetricsSample {
+///         timestamp_ms: 1_700_000_000_000 + i as i64 * 1000,
+///         cpu_usage: cpu,
+///         ..MetricsSample::default()
+///     })
+///     .unwrap();
+/// }
+///
+/// let points = store
+///     .query("cpu.usage", 1_700_000_000_000, 1_700_000_010_000, Some(Resolution::Second))
+///     .unwrap();
+/// assert_eq!(points.iter().map(|p| p.value).collect::<Vec<_>>(), [10.0, 30.0]);
+/// assert!(store.metric_names().contains(&"memory.used".to_string()));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct MetricStore {
+    root: PathBuf,
+    retention: Retention,
+    writers: Mutex<Writers>,
+}
+
+impl MetricStore {
+    /// Opens (or creates) the store under `root` and rebuilds the 1m/1h
+    /// buckets that were still open when the app last stopped.
+    pub fn open(root: impl AsRef<Path>, retention: Retention) -> AppResult<Self> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)
+            .map_err(|e| AppError::io_context(format!("Failed to create {}", root.display()), e))?;
+
+        let writer = |resolution: Resolution| {
+            SegmentWriter::new(
+                root.join(resolution.as_str()),
+                resolution,
+                retention.millis(resolution),
+            )
+        };
+        let store = Self {
+            writers: Mutex::new(Writers {
+                second: writer(Resolution::Second),
+                minute: writer(Resolution::Minute),
+                hour: writer(Resolution::Hour),
+                minute_rollup: Rollup::new(Resolution::Minute),
+                hour_rollup: Rollup::new(Resolution::Hour),
+                names: BTreeSet::new(),
+            }),
+            root,
+            retention,
+        };
+        store.recover()?;
+        Ok(store)
+    }
+
+    /// Replays the 1s records newer than the last written rollup of each
+    /// resolution, so restarts do not leave holes in the 1m/1h series. The
+    /// per-device series of the buckets open at the restart only cover the
+    /// samples after it.
+    fn recover(&self) -> AppResult<()> {
+        let mut writers = self.writers.lock_or_recover();
+        let Writers {
+            minute,
+            hour,
+            minute_rollup,
+            hour_rollup,
+            names,
+            ..
+        } = &mut *writers;
+
+        for (resolution, writer, rollup) in [
+            (Resolution::Minute, minute, minute_rollup),
+            (Resolution::Hour, hour, hour_rollup),
+        ] {
+            let last = segment::last_record(&self.dir(resolution))
+                .map_err(|e| AppError::io_context("Failed to read metrics store", e))?;
+            let from = last.map_or(i64::MIN, |r| r.t + resolution.millis());
+            let raw = segment::read_range(
+                &self.dir(Resolution::Second),
+                Resolution::Second,
+                from,
+                i64::MAX,
+            )
+            .map_err(|e| AppError::io_context("Failed to read metrics store", e))?;
+
+            for record in &raw {
+                if let Some(rolled) = rollup.add(record.t, &record.v) {
+                    writer
+                        .append(&rolled)
+                        .map_err(|e| AppError::io_context("Failed to write metrics store", e))?;
+                }
+            }
+            if let Some(record) = raw.last() {
+                names.extend(record.v.keys().cloned());
+            }
+        }
+        Ok(())
+    }
+
+    fn dir(&self, resolution: Resolution) -> PathBuf {
+        self.root.join(resolution.as_str())
+    }
+
+    pub fn append(&self, sample: &MetricsSample) -> AppResult<()> {
+        let record = Record {
+            t: sample.timestamp_ms,
+            v: sample.named_values(),
+            ..Record::default()
+        };
+        let second = Record {
+            t: record.t,
+            v: record
+                .v
+                .iter()
+                .filter(|(name, _)| !is_per_device(name))
+                .map(|(name, value)| (name.clone(), *value))
+                .collect(),
+            ..Record::default()
+        };
+        let mut writers = self.writers.lock_or_recover();
+        let write_err = |e| AppError::io_context("Failed to write metrics store", e);
+
+        writers.second.append(&second).map_err(write_err)?;
+        if let Some(rolled) = writers.minute_rollup.add(record.t, &record.v) {
+            writers.minute.append(&rolled).map_err(write_err)?;
+        }
+        if let Some(rolled) = writers.hour_rollup.add(record.t, &record.v) {
+            writers.hour.append(&rolled).map_err(write_err)?;
+        }
+        writers.names.extend(record.v.into_keys());
+        Ok(())
+    }
+
+    /// Names of the series in the most recent samples.
+    pub fn metric_names(&self) -> Vec<String> {
+        self.writers
+            .lock_or_recover()
+            .names
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Points of `metric` with `from_ms <= timestamp <= to_ms`. Without a
+    /// `resolution` the finest one that still holds `from_ms` and returns at
+    /// most a couple of thousand points is used. Per-device series are read
+    /// at 1m when 1s is asked for.
+    pub fn query(
+        &self,
+        metric: &str,
+        from_ms: i64,
+        to_ms: i64,
+        resolution: Option<Resolution>,
+    ) -> AppResult<Vec<MetricPoint>> {
+        if from_ms > to_ms {
+            return Err(AppError::InvalidArgument(format!(
+                "empty time range {}..{}",
+                from_ms, to_ms
+            )));
+        }
+        let resolution = match resolution {
+            Some(Resolution::Second) if is_per_device(metric) => Resolution::Minute,
+            Some(resolution) => resolution,
+            None => self.auto_resolution(metric, from_ms, to_ms),
+        };
+        let records = segment::read_range(&self.dir(resolution), resolution, from_ms, to_ms)
+            .map_err(|e| AppError::io_context("Failed to read metrics store", e))?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|r| {
+                let value = *r.v.get(metric)?;
+                Some(MetricPoint {
+                    timestamp_ms: r.t,
+                    value,
+                    min: r.min.get(metric).copied().unwrap_or(value),
+                    max: r.max.get(metric).copied().unwrap_or(value),
+                })
+            })
+            .collect())
+    }
+
+    fn auto_resolution(&self, metric: &str, from_ms: i64, to_ms: i64) -> Resolution {
+        let now = chrono::Utc::now().timestamp_millis();
+        Resolution::ALL
+            .into_iter()
+            .filter(|&r| r != Resolution::Second || !is_per_device(metric))
+            .find(|&r| {
+                from_ms >= now - self.retention.millis(r)
+                    && (to_ms - from_ms) / r.millis() <= MAX_AUTO_POINTS
+            })
+            .unwrap_or(Resolution::Hour)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const T0: i64 = 1_700_000_040_000;
+
+    fn sample(timestamp_ms: i64, cpu_usage: f32) -> MetricsSample {
+        MetricsSample {
+            timestamp_ms,
+            cpu_usage,
+            ..MetricsSample::default()
+        }
+    }
+
+    fn values(store: &MetricStore, resolution: Resolution) -> Vec<(i64, f64)> {
+        store
+            .query("cpu.usage", 0, i64::MAX, Some(resolution))
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.timestamp_ms, p.value))
+            .collect()
+    }
+
+    #[test]
+    fn reopening_resumes_the_open_rollup_bucket() {
+        let dir = std::env::temp_dir().join(format!("metric-store-recover-{}", std::process::id()));
+        let minute = T0.div_euclid(60_000) * 60_000;
+
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        store.append(&sample(minute, 10.0)).unwrap();
+        store.append(&sample(minute + 1000, 20.0)).unwrap();
+        drop(store);
+
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        assert!(store.metric_names().contains(&"cpu.usage".to_string()));
+        store.append(&sample(minute + 2000, 30.0)).unwrap();
+        store.append(&sample(minute + 60_000, 40.0)).unwrap();
+
+        assert_eq!(values(&store, Resolution::Minute), [(minute, 20.0)]);
+        assert_eq!(values(&store, Resolution::Second).len(), 4);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reopening_does_not_duplicate_written_rollups() {
+        let dir = std::env::temp_dir().join(format!("metric-store-rollups-{}", std::process::id()));
+        let minute = T0.div_euclid(60_000) * 60_000;
+
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        store.append(&sample(minute, 10.0)).unwrap();
+        store.append(&sample(minute + 60_000, 50.0)).unwrap();
+        drop(store);
+
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        store.append(&sample(minute + 120_000, 70.0)).unwrap();
+        assert_eq!(
+            values(&store, Resolution::Minute),
+            [(minute, 10.0), (minute + 60_000, 50.0)]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn inverted_range_is_rejected() {
+        let dir = std::env::temp_dir().join(format!("metric-store-range-{}", std::process::id()));
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        assert!(matches!(
+            store.query("cpu.usage", 2, 1, None),
+            Err(AppError::InvalidArgument(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn per_device_series_are_only_rolled_up() {
+        let dir = std::env::temp_dir().join(format!("metric-store-devices-{}", std::process::id()));
+        let minute = T0.div_euclid(60_000) * 60_000;
+        let store = MetricStore::open(&dir, Retention::default()).unwrap();
+        for (i, core) in [10.0, 30.0, 50.0].into_iter().enumerate() {
+            store
+                .append(&MetricsSample {
+                    timestamp_ms: minute + i as i64 * 30_000,
+                    core_usage: vec![core],
+                    ..MetricsSample::default()
+                })
+                .unwrap();
+        }
+
+        let seconds = segment::read_range(
+            &store.dir(Resolution::Second),
+            Resolution::Second,
+            0,
+            i64::MAX,
+        )
+        .unwrap();
+        assert_eq!(seconds.len(), 3);
+        assert!(seconds.iter().all(|r| r.v.contains_key("cpu.usage")));
+        assert!(seconds
+            .iter()
+            .all(|r| !r.v.contains_key("cpu.core.0.usage")));
+
+        let points = store
+            .query("cpu.core.0.usage", 0, i64::MAX, Some(Resolution::Second))
+            .unwrap();
+        let points: Vec<(i64, f64)> = points.iter().map(|p| (p.timestamp_ms, p.value)).collect();
+        assert_eq!(points, [(minute, 20.0)]);
+        assert!(store
+            .metric_names()
+            .contains(&"cpu.core.0.usage".to_string()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn per_device_series_are_recognized() {
+        for metric in [
+            "cpu.core.3.usage",
+            "gpu.1.temp",
+            "sensor[hwmon/nct6775/0/fan2]",
+            "disk.used[/home]",
+        ] {
+            assert!(is_per_device(metric), "{}", metric);
+        }
+        for metric in [
+            "cpu.usage",
+            "gpu.temp",
+            "memory.used",
+            "net.rx_bytes_per_sec",
+        ] {
+            assert!(!is_per_device(metric), "{}", metric);
+        }
+    }
+}
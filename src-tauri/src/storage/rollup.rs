@@ -0,0 +1,128 @@
+use super::segment::Record;
+use crate::models::metrics::Resolution;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy)]
+struct Stats {
+    sum: f64,
+    min: f64,
+    max: f64,
+    count: u32,
+}
+
+/// Aggregates samples into one record per `resolution` bucket.
+pub struct Rollup {
+    resolution: Resolution,
+    bucket: Option<i64>,
+    stats: BTreeMap<String, Stats>,
+}
+
+impl Rollup {
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            bucket: None,
+            stats: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a sample; returns the previous bucket's record once a sample
+    /// lands in another bucket. A clock stepped back starts a new bucket
+    /// rather than folding the samples into the later one.
+    pub fn add(&mut self, t: i64, values: &BTreeMap<String, f64>) -> Option<Record> {
+        let res = self.resolution.millis();
+        let bucket = t.div_euclid(res) * res;
+        let finished = match self.bucket {
+            Some(current) if current != bucket => self.flush(),
+            _ => None,
+        };
+        self.bucket = Some(bucket);
+
+        for (name, &value) in values {
+            self.stats
+                .entry(name.clone())
+                .and_modify(|s| {
+                    s.sum += value;
+                    s.min = s.min.min(value);
+                    s.max = s.max.max(value);
+                    s.count += 1;
+                })
+                .or_insert(Stats {
+                    sum: value,
+                    min: value,
+                    max: value,
+                    count: 1,
+                });
+        }
+        finished
+    }
+
+    fn flush(&mut self) -> Option<Record> {
+        let t = self.bucket.take()?;
+        let stats = std::mem::take(&mut self.stats);
+        if stats.is_empty() {
+            return None;
+        }
+
+        let mut record = Record {
+            t,
+            ..Record::default()
+        };
+        for (name, s) in stats {
+            record.v.insert(name.clone(), s.sum / s.count as f64);
+            record.min.insert(name.clone(), s.min);
+            record.max.insert(name, s.max);
+        }
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60_000;
+
+    fn values(cpu: f64) -> BTreeMap<String, f64> {
+        BTreeMap::from([("cpu.usage".to_string(), cpu)])
+    }
+
+    #[test]
+    fn bucket_is_emitted_with_mean_min_and_max_once_the_next_starts() {
+        let mut rollup = Rollup::new(Resolution::Minute);
+        assert!(rollup.add(MINUTE, &values(10.0)).is_none());
+        assert!(rollup.add(MINUTE + 30_000, &values(30.0)).is_none());
+
+        let record = rollup.add(2 * MINUTE, &values(50.0)).expect("first bucket");
+        assert_eq!(record.t, MINUTE);
+        assert_eq!(record.v["cpu.usage"], 20.0);
+        assert_eq!(record.min["cpu.usage"], 10.0);
+        assert_eq!(record.max["cpu.usage"], 30.0);
+    }
+
+    #[test]
+    fn clock_going_back_starts_a_new_bucket() {
+        let mut rollup = Rollup::new(Resolution::Minute);
+        rollup.add(5 * MINUTE, &values(90.0));
+
+        let later = rollup.add(2 * MINUTE, &values(10.0)).expect("later bucket");
+        assert_eq!(later.t, 5 * MINUTE);
+        assert_eq!(later.v["cpu.usage"], 90.0);
+
+        let earlier = rollup
+            .add(3 * MINUTE, &values(20.0))
+            .expect("earlier bucket");
+        assert_eq!(earlier.t, 2 * MINUTE);
+        assert_eq!(earlier.v["cpu.usage"], 10.0);
+    }
+
+    #[test]
+    fn negative_timestamps_round_down() {
+        let mut rollup = Rollup::new(Resolution::Minute);
+        rollup.add(-1, &values(1.0));
+        let record = rollup
+            .add(0, &values(2.0))
+            .expect("bucket before the epoch");
+        assert_eq!(record.t, -MINUTE);
+    }
+}
@@ -0,0 +1,296 @@
+//! Append-only segment files: one JSON record per line, one file per
+//! `segment_span` of time, named after the segment's start in Unix ms.
+
+use crate::models::metrics::Resolution;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+const EXTENSION: &str = "jsonl";
+
+/// One line of a segment file. Rollup records also carry the extremes of
+/// the samples they aggregate.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Record {
+    pub t: i64,
+    pub v: BTreeMap<String, f64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub min: BTreeMap<String, f64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max: BTreeMap<String, f64>,
+}
+
+/// How much time one segment file covers at `resolution`.
+pub fn segment_span(resolution: Resolution) -> i64 {
+    match resolution {
+        Resolution::Second => Resolution::Hour.millis(),
+        Resolution::Minute => 24 * Resolution::Hour.millis(),
+        Resolution::Hour => 30 * 24 * Resolution::Hour.millis(),
+    }
+}
+
+fn segment_start(resolution: Resolution, t: i64) -> i64 {
+    let span = segment_span(resolution);
+    t.div_euclid(span) * span
+}
+
+/// Segment files in `dir` with their start times, oldest first.
+fn segments(dir: &Path) -> io::Result<Vec<(i64, PathBuf)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut segments = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+            continue;
+        }
+        if let Some(start) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse().ok())
+        {
+            segments.push((start, path));
+        }
+    }
+    segments.sort_by_key(|(start, _)| *start);
+    Ok(segments)
+}
+
+fn read_segment(path: &Path, mut keep: impl FnMut(&Record) -> bool) -> io::Result<Vec<Record>> {
+    let mut records = Vec::new();
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        // A crash can leave a half-written or garbled line; skip it.
+        let text = match std::str::from_utf8(&line) {
+            Ok(text) => text.trim_end(),
+            Err(e) => {
+                log::debug!("Skipping bad record in {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        match serde_json::from_str::<Record>(text) {
+            Ok(record) if keep(&record) => records.push(record),
+            Ok(_) => {}
+            Err(e) if !text.is_empty() => {
+                log::debug!("Skipping bad record in {}: {}", path.display(), e)
+            }
+            Err(_) => {}
+        }
+    }
+    Ok(records)
+}
+
+/// Records with `from <= t <= to`, oldest first.
+pub fn read_range(
+    dir: &Path,
+    resolution: Resolution,
+    from: i64,
+    to: i64,
+) -> io::Result<Vec<Record>> {
+    let span = segment_span(resolution);
+    let mut records = Vec::new();
+    for (start, path) in segments(dir)? {
+        if start > to || start.saturating_add(span) <= from {
+            continue;
+        }
+        records.extend(read_segment(&path, |r| r.t >= from && r.t <= to)?);
+    }
+    Ok(records)
+}
+
+/// The newest record in `dir`, if any.
+pub fn last_record(dir: &Path) -> io::Result<Option<Record>> {
+    for (_, path) in segments(dir)?.into_iter().rev() {
+        if let Some(record) = read_segment(&path, |_| true)?.pop() {
+            return Ok(Some(record));
+        }
+    }
+    Ok(None)
+}
+
+/// Deletes segments that ended before `cutoff`.
+pub fn prune(dir: &Path, resolution: Resolution, cutoff: i64) -> io::Result<()> {
+    let span = segment_span(resolution);
+    for (start, path) in segments(dir)? {
+        if start.saturating_add(span) <= cutoff {
+            log::info!("Removing expired metrics segment {}", path.display());
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Appends records of one resolution to the segment they fall into, opening
+/// a new file whenever a record crosses into the next segment.
+pub struct SegmentWriter {
+    dir: PathBuf,
+    resolution: Resolution,
+    retention_ms: i64,
+    current: Option<(i64, File)>,
+}
+
+impl SegmentWriter {
+    pub fn new(dir: PathBuf, resolution: Resolution, retention_ms: i64) -> Self {
+        Self {
+            dir,
+            resolution,
+            retention_ms,
+            current: None,
+        }
+    }
+
+    pub fn append(&mut self, record: &Record) -> io::Result<()> {
+        let start = segment_start(self.resolution, record.t);
+        let file = match &mut self.current {
+            Some((current, file)) if *current == start => file,
+            _ => {
+                let file = self.open(start)?;
+                prune(&self.dir, self.resolution, record.t - self.retention_ms)?;
+                &mut self.current.insert((start, file)).1
+            }
+        };
+
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        file.write_all(&line)
+    }
+
+    fn open(&self, start: i64) -> io::Result<File> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.{}", start, EXTENSION));
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+
+        // Terminate a line left half-written by a crash so the next record
+        // starts on its own line.
+        if file.metadata()?.len() > 0 {
+            let mut last = [0u8];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    fn record(t: i64, cpu: f64) -> Record {
+        Record {
+            t,
+            v: BTreeMap::from([("cpu.usage".to_string(), cpu)]),
+            ..Record::default()
+        }
+    }
+
+    fn times(records: &[Record]) -> Vec<i64> {
+        records.iter().map(|r| r.t).collect()
+    }
+
+    #[test]
+    fn half_written_line_is_skipped_and_terminated() {
+        let dir = std::env::temp_dir().join(format!("segment-torn-{}", std::process::id()));
+        let mut writer = SegmentWriter::new(dir.clone(), Resolution::Second, 24 * HOUR);
+        writer.append(&record(1000, 1.0)).unwrap();
+        drop(writer);
+
+        let path = dir.join(format!("0.{}", EXTENSION));
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"t":2000,"v":{"cpu.us"#)
+            .unwrap();
+        assert_eq!(
+            times(&read_range(&dir, Resolution::Second, 0, HOUR).unwrap()),
+            [1000]
+        );
+
+        let mut writer = SegmentWriter::new(dir.clone(), Resolution::Second, 24 * HOUR);
+        writer.append(&record(3000, 3.0)).unwrap();
+        assert_eq!(
+            times(&read_range(&dir, Resolution::Second, 0, HOUR).unwrap()),
+            [1000, 3000]
+        );
+        assert_eq!(last_record(&dir).unwrap().map(|r| r.t), Some(3000));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_utf8_is_skipped_like_any_bad_line() {
+        let dir = std::env::temp_dir().join(format!("segment-utf8-{}", std::process::id()));
+        let mut writer = SegmentWriter::new(dir.clone(), Resolution::Second, 24 * HOUR);
+        writer.append(&record(1000, 1.0)).unwrap();
+        drop(writer);
+
+        let path = dir.join(format!("0.{}", EXTENSION));
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"t\":1500,\"v\":{\"\xff\xfe\":1}}\n")
+            .unwrap();
+        file.write_all(br#"{"t":2000,"v":{"cpu.usage":2.0}}"#)
+            .unwrap();
+        file.write_all(b"\n{\"t\":3000,\"v\":{\"cpu\xc3").unwrap();
+        drop(file);
+
+        assert_eq!(
+            times(&read_range(&dir, Resolution::Second, 0, HOUR).unwrap()),
+            [1000, 2000]
+        );
+        assert_eq!(last_record(&dir).unwrap().map(|r| r.t), Some(2000));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn segments_past_retention_are_pruned_on_rollover() {
+        let dir = std::env::temp_dir().join(format!("segment-retention-{}", std::process::id()));
+        let mut writer = SegmentWriter::new(dir.clone(), Resolution::Second, 2 * HOUR);
+        writer.append(&record(0, 1.0)).unwrap();
+        writer.append(&record(HOUR + 1, 2.0)).unwrap();
+        writer.append(&record(2 * HOUR + 1, 3.0)).unwrap();
+        assert_eq!(segments(&dir).unwrap().len(), 3);
+
+        // The first segment ended at 1h, exactly the new cutoff.
+        writer.append(&record(3 * HOUR, 4.0)).unwrap();
+        let starts: Vec<i64> = segments(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect();
+        assert_eq!(starts, [HOUR, 2 * HOUR, 3 * HOUR]);
+        assert_eq!(
+            times(&read_range(&dir, Resolution::Second, i64::MIN, i64::MAX).unwrap()),
+            [HOUR + 1, 2 * HOUR + 1, 3 * HOUR]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_directory_is_empty() {
+        let dir = std::env::temp_dir().join(format!("segment-missing-{}", std::process::id()));
+        assert!(read_range(&dir, Resolution::Second, 0, HOUR)
+            .unwrap()
+            .is_empty());
+        assert!(last_record(&dir).unwrap().is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::error::{AppError, AppResult};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, path::Path};
+
+/// Reads a JSON settings file, falling back to the default when it is
+/// missing or cannot be parsed.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Writes `value` next to `path` first and renames it over, so a crash never
+/// leaves a truncated file behind.
+pub fn save<T: Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| AppError::io_context(format!("Failed to create {}", dir.display()), e))?;
+    }
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::Parse(format!("Failed to serialize settings: {}", e)))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json)
+        .map_err(|e| AppError::io_context(format!("Failed to write {}", tmp.display()), e))?;
+    fs::rename(&tmp, path)
+        .map_err(|e| AppError::io_context(format!("Failed to replace {}", path.display()), e))
+}
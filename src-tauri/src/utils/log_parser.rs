@@ -98,22 +98,3 @@ pub fn count_lines(file_path: &str) -> std::io::Result<usize> {
     }
     Ok(count)
 }
-
-pub fn is_system_process(pid: u32, name: &str) -> bool {
-    if cfg!(target_os = "windows") {
-        pid < 100
-            || name == "System"
-            || name == "Registry"
-            || name.contains("svchost")
-            || name.contains("dllhost")
-    } else if cfg!(target_os = "linux") {
-        pid < 1000
-            || name == "systemd"
-            || name == "kthreadd"
-            || name == "ksoftirqd"
-            || name.contains("rcu")
-            || name == "migration"
-    } else {
-        false
-    }
-}
\ No newline at end of file
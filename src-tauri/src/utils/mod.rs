@@ -1,3 +1,6 @@
 pub mod encoding;
 pub mod hashing;
-pub mod log_parser;
\ No newline at end of file
+pub mod json_file;
+pub mod log_parser;
+pub mod sync;
+pub mod sysfs;
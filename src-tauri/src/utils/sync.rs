@@ -0,0 +1,17 @@
+use std::sync::{Mutex, MutexGuard};
+
+pub trait LockExt<T> {
+    /// Locks the mutex, taking the data back out of a poisoned lock instead of
+    /// panicking. The state guarded here is plain bookkeeping that stays usable
+    /// even if a worker thread panicked while holding it.
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| {
+            log::warn!("Recovering from a poisoned lock");
+            poisoned.into_inner()
+        })
+    }
+}
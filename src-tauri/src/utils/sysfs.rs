@@ -0,0 +1,13 @@
+use std::{fs, path::Path};
+
+/// Reads a small `/proc` or `/sys` attribute file, trimmed. `None` if the
+/// file is missing, unreadable or empty.
+pub fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let trimmed = content.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+pub fn read_u64(path: impl AsRef<Path>) -> Option<u64> {
+    read_trimmed(path)?.parse().ok()
+}
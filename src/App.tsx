@@ -3,10 +3,11 @@ import { LogViewer } from './components/LogViewer'
 import { SortedLogs } from './components/SortedLogs'
 import { SystemMonitor } from './components/SystemMonitor';
 import { FileSelector } from './components/FileSelector'
+import { CommandLauncher } from './components/CommandLauncher'
 import { ThemeProvider, useTheme } from './components/ThemeProvider'
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event'
-import { LogEntry } from './types'
+import { LogEntry, EventEnvelope, BACKEND_EVENT, EVENT_PROTOCOL_VERSION, CommandStatus, FileWriter, errorMessage } from './types'
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { LogicalSize } from '@tauri-apps/api/window'
 import { AnimatePresence, motion } from 'framer-motion'
@@ -148,6 +149,9 @@ function AppContent() {
   const [loadProgress, setLoadProgress] = useState(0);
   const [totalLines, setTotalLines] = useState(0);
   const [loadedLines, setLoadedLines] = useState(0);
+  const [attachWriters, setAttachWriters] = useState(false);
+  const [fileWriters, setFileWriters] = useState<FileWriter[]>([]);
+  const [commandStatus, setCommandStatus] = useState<CommandStatus | null>(null);
   const MAX_LOG_ENTRIES = 100000;
   //const [logFilePath, /*setLogFilePath*/] = useState('C:/Users/Артём/Desktop/flog_0.4.4_windows_amd64/generated.log')
   //const [logFilePath, setLogFilePath] = useState('C:/Users/Артём/AppData/Roaming/.minecraft/logs/latest.log')
@@ -159,15 +163,6 @@ function AppContent() {
       dispatch({ type: 'SET_LOGS', payload: logs.slice(-MAX_LOG_ENTRIES) });
     }
   }, [logs]);
-  useEffect(() => {
-    const unlistenTruncate = listen('file_truncated', async () => {
-      await clearLogs();
-    });
-
-    return () => {
-      unlistenTruncate.then(f => f());
-    };
-  }, [clearLogs]);
   useEffect(() => {
     const fetchCurrentFile = async () => {
       try {
@@ -181,98 +176,127 @@ function AppContent() {
     }
 
     fetchCurrentFile()
+    invoke<CommandStatus | null>('get_command_status')
+      .then(setCommandStatus)
+      .catch(err => console.error('Error fetching command status:', errorMessage(err)))
   }, [])
   useEffect(() => {
-    let unsub: (() => void) | null = null;
+    let lastSeq: { sessionId: string; seq: number } | null = null;
 
-    const setupListener = async () => {
-      try {
-        const unlisten = await listen('file_cleared', async () => {
+    const handleEvent = async (envelope: EventEnvelope) => {
+      if (envelope.version !== EVENT_PROTOCOL_VERSION) {
+        console.warn(`Unsupported backend event version ${envelope.version}`);
+        return;
+      }
+      // Gaps and reordering are only reported: every event still gets applied.
+      if (lastSeq && lastSeq.sessionId === envelope.session_id && envelope.seq !== lastSeq.seq + 1) {
+        if (envelope.seq <= lastSeq.seq) {
+          console.warn(`Backend event ${envelope.seq} arrived after ${lastSeq.seq}`);
+        } else {
+          console.warn(`Missed backend events ${lastSeq.seq + 1}..${envelope.seq - 1}`);
+        }
+      }
+      if (!lastSeq || lastSeq.sessionId !== envelope.session_id || envelope.seq > lastSeq.seq) {
+        lastSeq = { sessionId: envelope.session_id, seq: envelope.seq };
+      }
+
+      switch (envelope.type) {
+        case 'logs_batch':
+          if (envelope.entries.length > 0) {
+            dispatch({ type: 'ADD_BATCH', payload: envelope.entries });
+          }
+          break;
+
+        case 'load_progress':
+          setLoadedLines(envelope.current);
+          setTotalLines(envelope.total);
+          setLoadProgress(envelope.total > 0 ? Math.round((envelope.current / envelope.total) * 100) : 100);
+          break;
+
+        case 'loading_success':
+          console.log("✅ [Frontend] loading_success — отключаю isLoading");
+          setIsLoading(false); // 🟢 ОБЯЗАТЕЛЬНО
+          try {
+            await invoke('start_file_monitoring', { filePath: logFilePath });
+            setIsMonitoring(true);
+          } catch (err) {
+            console.error("Error starting monitoring:", err);
+          }
+          break;
+
+        case 'loading_already_loaded':
+          console.log("[Frontend] loading_already_loaded — новых строк нет");
+          break;
+
+        case 'loading_cancelled':
+          console.log("❌ [Frontend] loading_cancelled — отключаю isLoading");
+          setIsLoading(false);
+          try {
+            await invoke('stop_file_monitoring');
+            setIsMonitoring(false);
+            setLoadProgress(0);
+            setTotalLines(0);
+            setLoadedLines(0);
+          } catch (err) {
+            console.error("Error stopping monitoring after cancel:", err);
+          }
+          await clearLogs();
+          break;
+
+        case 'loading_error':
+          console.error(`❗ [Frontend] loading_error (${envelope.code}):`, envelope.message);
+          setIsLoading(false);
+          try {
+            await invoke('stop_file_monitoring');
+            setIsMonitoring(false);
+          } catch (err) {
+            console.error("Error stopping monitoring after error:", err);
+          }
+          alert(`Error loading file: ${envelope.message}`);
+          break;
+
+        case 'file_cleared':
           await clearLogs();
           console.log('Log file was cleared, clearing UI logs');
-        });
-        unsub = unlisten;
-      } catch (error) {
-        console.error('Error setting up file_cleared listener:', error);
-      }
-    };
+          break;
 
-    setupListener();
+        case 'file_truncated':
+          await clearLogs();
+          break;
 
-    return () => {
-      if (unsub) unsub();
-    };
-  }, [clearLogs]); // Добавляем clearLogs в зависимости
-  useEffect(() => {
-    const seenLogs = new Set<string>();
+        case 'monitoring_error':
+          console.error(`❗ [Frontend] monitoring_error (${envelope.code}):`, envelope.message);
+          break;
 
-    const handleNewLogs = (event: { payload: LogEntry[] }) => {
-      const newLogs = event.payload || [];
-      if (newLogs.length > 0) {
-        dispatch({ type: 'ADD_BATCH', payload: newLogs });
+        case 'file_writers':
+          setFileWriters(envelope.writers);
+          break;
+
+        case 'command_started':
+        case 'command_exited':
+          setCommandStatus(envelope.status);
+          break;
       }
     };
 
-    const unlistenPromises = [
-      listen('new_logs_batch', handleNewLogs),
-
-      listen('load_progress', (event) => {
-        const progress = event.payload as { current: number, total: number };
-        setLoadedLines(progress.current);
-        setTotalLines(progress.total);
-        setLoadProgress(Math.round((progress.current / progress.total) * 100));
-      }),
-
-      listen('loading_success', async () => {
-        console.log("✅ [Frontend] loading_success — отключаю isLoading");
-        setIsLoading(false); // 🟢 ОБЯЗАТЕЛЬНО
-        try {
-          await invoke('start_file_monitoring', { filePath: logFilePath });
-          setIsMonitoring(true);
-        } catch (err) {
-          console.error("Error starting monitoring:", err);
-        }
-      }),
-
-      listen('loading_cancelled', async () => {
-        console.log("❌ [Frontend] loading_cancelled — отключаю isLoading");
-        setIsLoading(false);
-        try {
-          await invoke('stop_file_monitoring');
-          setIsMonitoring(false);
-          setLoadProgress(0);
-          setTotalLines(0);
-          setLoadedLines(0);
-        } catch (err) {
-          console.error("Error stopping monitoring after cancel:", err);
-        }
-        await clearLogs();
-      }),
-
-      listen('loading_error', async (event) => {
-        const errorMessage = event.payload as string;
-        console.error("❗ [Frontend] loading_error:", errorMessage);
-        setIsLoading(false);
-        try {
-          await invoke('stop_file_monitoring');
-          setIsMonitoring(false);
-        } catch (err) {
-          console.error("Error stopping monitoring after error:", err);
-        }
-        alert(`Error loading file: ${errorMessage}`);
-      }),
-    ];
-
-    const unsubs: (() => void)[] = [];
-
-    Promise.all(unlistenPromises).then((fns) => {
-      fns.forEach((fn) => unsubs.push(fn));
+    const unlisten = listen<EventEnvelope>(BACKEND_EVENT, (event) => {
+      handleEvent(event.payload);
     });
 
     return () => {
-      unsubs.forEach((fn) => fn());
+      unlisten.then(f => f());
     };
-  }, [logFilePath]);
+  }, [logFilePath, clearLogs]);
+  const toggleAttachWriters = async () => {
+    const next = !attachWriters;
+    try {
+      await invoke('set_attach_file_writers', { attach: next });
+      setAttachWriters(next);
+      if (!next) setFileWriters([]);
+    } catch (err) {
+      console.error('Error attaching file writers:', errorMessage(err));
+    }
+  };
   const switchFile = async (path: string) => {
     if (isMonitoring) {
       await invoke('stop_file_monitoring');
@@ -285,6 +309,7 @@ function AppContent() {
     }
 
     setLogFilePath(path);
+    setFileWriters([]);
     await invoke('set_current_file', { path }); // <- ГАРАНТИЯ сброса стейта
 
     await clearLogs();
@@ -326,7 +351,7 @@ function AppContent() {
     } catch (err) {
       console.error('Error invoking start_file_loading:', err);
       setIsLoading(false);
-      alert(`Failed to start loading: ${err}`);
+      alert(`Failed to start loading: ${errorMessage(err)}`);
     }
   };
 
@@ -455,6 +480,7 @@ function AppContent() {
             >
               Copy Logs
             </button>
+            <CommandLauncher status={commandStatus} />
             <div className="text-sm truncate max-w-xs group relative">
               <span className="truncate inline-block max-w-full">
                 Current file: {logFilePath.split('/').pop() || logFilePath}
@@ -465,6 +491,21 @@ function AppContent() {
                 </span>
               )}
             </div>
+            <label className="flex items-center space-x-1 text-sm">
+              <input type="checkbox" checked={attachWriters} onChange={toggleAttachWriters} />
+              <span>Writers</span>
+            </label>
+            {attachWriters && (
+              <div className="text-xs text-win-text-secondary truncate max-w-md">
+                {fileWriters.length === 0
+                  ? 'No process has this file open for writing'
+                  : fileWriters
+                    .map(w => `${w.name} (${w.pid})` + (w.detail
+                      ? ` ${w.detail.cpu_usage.toFixed(1)}% CPU, ${(w.detail.memory / 1048576).toFixed(0)} MB`
+                      : ''))
+                    .join(', ')}
+              </div>
+            )}
           </div>
         </div>
         <div className="bg-win-bg-secondary rounded border border-win-border flex-1 flex flex-col overflow-hidden">
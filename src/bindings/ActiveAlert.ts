@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlertStatus } from "./AlertStatus";
+
+/**
+ * A rule that is currently pending or firing.
+ */
+export type ActiveAlert = { rule_id: string, rule_name: string, status: AlertStatus, 
+/**
+ * When the threshold was first crossed.
+ */
+since_ms: number, value: number, };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlertStatus } from "./AlertStatus";
+import type { LogEntry } from "./LogEntry";
+
+/**
+ * One state change of an alert.
+ */
+export type AlertRecord = { rule_id: string, rule_name: string, status: AlertStatus, message: string, 
+/**
+ * The metric value that caused the change, or the number of matching
+ * lines for a log alert.
+ */
+value: number | null, at_ms: number, 
+/**
+ * The log lines that matched, for log alerts.
+ */
+entries: Array<LogEntry>, };
@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlertRecord } from "./AlertRecord";
+import type { CommandStatus } from "./CommandStatus";
+import type { FileWriter } from "./FileWriter";
+import type { LogEntry } from "./LogEntry";
+import type { MetricsSample } from "./MetricsSample";
+import type { WatchTrigger } from "./WatchTrigger";
+
+/**
+ * Everything the backend pushes to the UI.
+ *
+ * Serialized with a `type` tag next to the variant fields:
+ *
+ * ```
+ * use app_lib::models::events::BackendEvent;
+ *
+ * let json = serde_json::to_value(BackendEvent::LoadProgress { current: 10, total: 20 }).unwrap();
+ * assert_eq!(json, serde_json::json!({ "type": "load_progress", "current": 10, "total": 20 }));
+ *
+ * let json = serde_json::to_value(BackendEvent::FileTruncated).unwrap();
+ * assert_eq!(json, serde_json::json!({ "type": "file_truncated" }));
+ *
+ * let err = app_lib::error::AppError::PermissionDenied("/var/log/syslog".into());
+ * let json = serde_json::to_value(BackendEvent::loading_error(&err)).unwrap();
+ * assert_eq!(
+ *     json,
+ *     serde_json::json!({
+ *         "type": "loading_error",
+ *         "code": "permission_denied",
+ *         "message": "permission denied: /var/log/syslog",
+ *     })
+ * );
+ * ```
+ */
+export type BackendEvent = { "type": "logs_batch", entries: Array<LogEntry>, } | { "type": "load_progress", current: number, total: number, } | { "type": "loading_success" } | { "type": "loading_already_loaded" } | { "type": "loading_cancelled" } | { "type": "loading_error", code: string, message: string, } | { "type": "file_cleared" } | { "type": "file_truncated" } | { "type": "monitoring_error", code: string, message: string, } | { "type": "metrics_sample", sample: MetricsSample, } | { "type": "file_writers", path: string, writers: Array<FileWriter>, } | { "type": "command_started", status: CommandStatus, } | { "type": "command_exited", status: CommandStatus, } | { "type": "watch_triggered", trigger: WatchTrigger, } | { "type": "alert", record: AlertRecord, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type BatteryInfo = { 
+/**
+ * `charging`, `discharging`, `full`, `not_charging` or `unknown`.
+ */
+state: string, charge_percent: number, time_remaining_secs: number | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Throughput of one block device since the previous refresh.
+ */
+export type BlockDeviceIo = { name: string, 
+/**
+ * Backed by hardware rather than stacked on other devices (dm, md, ...).
+ */
+physical: boolean, read_bytes_per_sec: number, write_bytes_per_sec: number, read_iops: number, write_iops: number, read_bytes_total: number, write_bytes_total: number, };
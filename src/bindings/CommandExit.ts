@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a launched command ended. `signal` is set instead of `code` when it
+ * was killed by a signal (Unix).
+ */
+export type CommandExit = { code: number | null, signal: number | null, at_ms: number, };
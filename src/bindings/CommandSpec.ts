@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A command launched from the app whose output is followed like a log file.
+ */
+export type CommandSpec = { program: string, args: Array<string>, 
+/**
+ * Working directory, the app's own when unset.
+ */
+cwd: string | null, };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommandExit } from "./CommandExit";
+import type { CommandSpec } from "./CommandSpec";
+
+export type CommandStatus = { spec: CommandSpec, command_line: string, 
+/**
+ * Set while the command runs.
+ */
+pid: number | null, started_at_ms: number, exit: CommandExit | null, 
+/**
+ * How often the command was restarted from the app.
+ */
+restarts: number, };
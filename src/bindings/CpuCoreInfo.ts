@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CpuCoreInfo = { index: number, name: string, usage: number, frequency_mhz: number, min_frequency_mhz: number | null, max_frequency_mhz: number | null, };
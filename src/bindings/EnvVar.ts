@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EnvVar = { name: string, value: string, 
+/**
+ * The value looked like a secret and was replaced.
+ */
+masked: boolean, };
@@ -0,0 +1,41 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlertRecord } from "./AlertRecord";
+import type { CommandStatus } from "./CommandStatus";
+import type { FileWriter } from "./FileWriter";
+import type { LogEntry } from "./LogEntry";
+import type { MetricsSample } from "./MetricsSample";
+import type { WatchTrigger } from "./WatchTrigger";
+
+/**
+ * A [`BackendEvent`] stamped with the protocol version, the backend session
+ * it came from and its position in that session.
+ *
+ * The event fields are flattened into the envelope:
+ *
+ * ```
+ * use app_lib::models::{
+ *     events::{BackendEvent, EventEnvelope, EVENT_PROTOCOL_VERSION},
+ *     log_entry::LogEntry,
+ * };
+ *
+ * let entry = LogEntry {
+ *     timestamp: "2025-06-20 22:01:05".into(),
+ *     level: "ERROR".into(),
+ *     message: "boom".into(),
+ *     stream: None,
+ * };
+ * let envelope = EventEnvelope::new("abc", 7, BackendEvent::LogsBatch { entries: vec![entry] });
+ *
+ * assert_eq!(
+ *     serde_json::to_value(&envelope).unwrap(),
+ *     serde_json::json!({
+ *         "version": EVENT_PROTOCOL_VERSION,
+ *         "session_id": "abc",
+ *         "seq": 7,
+ *         "type": "logs_batch",
+ *         "entries": [{ "timestamp": "2025-06-20 22:01:05", "level": "ERROR", "message": "boom" }],
+ *     })
+ * );
+ * ```
+ */
+export type EventEnvelope = { version: number, session_id: string, seq: number, } & ({ "type": "logs_batch", entries: Array<LogEntry>, } | { "type": "load_progress", current: number, total: number, } | { "type": "loading_success" } | { "type": "loading_already_loaded" } | { "type": "loading_cancelled" } | { "type": "loading_error", code: string, message: string, } | { "type": "file_cleared" } | { "type": "file_truncated" } | { "type": "monitoring_error", code: string, message: string, } | { "type": "metrics_sample", sample: MetricsSample, } | { "type": "file_writers", path: string, writers: Array<FileWriter>, } | { "type": "command_started", status: CommandStatus, } | { "type": "command_exited", status: CommandStatus, } | { "type": "watch_triggered", trigger: WatchTrigger, } | { "type": "alert", record: AlertRecord, });
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProcessDetail } from "./ProcessDetail";
+
+/**
+ * A process holding the monitored log file open for writing.
+ */
+export type FileWriter = { pid: number, name: string, 
+/**
+ * Descriptors open on the file.
+ */
+fds: Array<number>, 
+/**
+ * Attached when requested, without rates. In `file_writers` events it
+ * also leaves out the environment and sockets.
+ */
+detail: ProcessDetail | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GpuProcessInfo } from "./GpuProcessInfo";
+
+/**
+ * One GPU. Fields a driver does not report are `None`.
+ */
+export type GpuInfo = { index: number, name: string, vendor: string | null, uuid: string | null, 
+/**
+ * PCI address, e.g. `0000:01:00.0`.
+ */
+pci_bus_id: string | null, temperature: number | null, utilization: number | null, memory_used: number | null, memory_total: number | null, power_draw_mw: number | null, power_limit_mw: number | null, fan_speed_percent: number | null, graphics_clock_mhz: number | null, memory_clock_mhz: number | null, sm_clock_mhz: number | null, encoder_utilization: number | null, decoder_utilization: number | null, 
+/**
+ * Active clock throttle reasons, e.g. `sw_power_cap`.
+ */
+throttle_reasons: Array<string>, processes: Array<GpuProcessInfo>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GpuSample = { 
+/**
+ * The `index` of the GPU in `SystemInfo::gpus`.
+ */
+index: number, usage: number | null, temp: number | null, memory_used: number | null, };
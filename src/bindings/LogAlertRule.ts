@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Alerts when at least `min_matches` tailed lines within `window_secs`
+ * match `level` and `pattern`, e.g. more than 50 `ERROR` lines a minute or
+ * any line matching `OutOfMemory`.
+ */
+export type LogAlertRule = { 
+/**
+ * Assigned when the rule is first saved.
+ */
+id: string, name: string, enabled: boolean, 
+/**
+ * Level the line must have (e.g. `ERROR`, case-insensitive); any level
+ * when absent.
+ */
+level: string | null, 
+/**
+ * Regular expression searched in the line; any line when absent.
+ */
+pattern: string | null, min_matches: number, window_secs: number, 
+/**
+ * After firing, the rule does not fire again for this long; the lines
+ * it would have fired for are only counted.
+ */
+cooldown_secs: number, 
+/**
+ * Desktop notification when the alert fires.
+ */
+notify: boolean, };
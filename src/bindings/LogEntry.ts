@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogStream } from "./LogStream";
+
+export type LogEntry = { timestamp: string, level: string, message: string, 
+/**
+ * Output stream the line came from when the source is a launched
+ * command; absent for files.
+ */
+stream?: LogStream, };
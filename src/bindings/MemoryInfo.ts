@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Memory in bytes. `available` estimates what can be allocated without
+ * swapping, i.e. `free` plus reclaimable cache.
+ */
+export type MemoryInfo = { total: number, used: number, free: number, available: number, 
+/**
+ * Linux only.
+ */
+buffers: number | null, 
+/**
+ * Page cache plus reclaimable slab. Linux only.
+ */
+cached: number | null, swap_total: number, swap_used: number, swap_free: number, };
@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Comparison } from "./Comparison";
+
+/**
+ * Alerts when `metric` is past `threshold` for `for_secs`.
+ *
+ * `metric` is any series name of [`MetricsSample::named_values`] (e.g.
+ * `cpu.usage`, `cpu.temp`, `gpu.temp`, `sensor[...]`) or one of the derived
+ * `memory.used_percent`, `disk.free[<mount>]` and
+ * `disk.free_percent[<mount>]`.
+ */
+export type MetricAlertRule = { 
+/**
+ * Assigned when the rule is first saved.
+ */
+id: string, name: string, enabled: boolean, metric: string, comparison: Comparison, threshold: number, 
+/**
+ * Where a firing alert resolves again, for hysteresis. Defaults to
+ * `threshold`.
+ */
+clear_threshold: number | null, 
+/**
+ * How long the threshold must be crossed before the alert fires.
+ */
+for_secs: number, 
+/**
+ * Desktop notification when the alert fires and resolves.
+ */
+notify: boolean, };
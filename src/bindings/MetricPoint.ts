@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One point of a stored metric series. `min`/`max` cover the samples rolled
+ * up into the point and equal `value` at 1s resolution.
+ */
+export type MetricPoint = { timestamp_ms: number, value: number, min: number, max: number, };
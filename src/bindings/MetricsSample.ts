@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GpuSample } from "./GpuSample";
+import type { MountSample } from "./MountSample";
+import type { SensorSample } from "./SensorSample";
+
+/**
+ * The numeric part of a [`SystemInfo`] snapshot kept in the metrics history.
+ */
+export type MetricsSample = { 
+/**
+ * Unix time in milliseconds.
+ */
+timestamp_ms: number, cpu_usage: number, core_usage: Array<number>, memory_used: number, memory_total: number, memory_available: number, swap_used: number, 
+/**
+ * `some` avg10 stall percentages from PSI (Linux).
+ */
+cpu_pressure: number | null, memory_pressure: number | null, io_pressure: number | null, cpu_temp: number | null, 
+/**
+ * Of the first GPU; every GPU is in `gpus`.
+ */
+gpu_usage: number | null, gpu_temp: number | null, gpus: Array<GpuSample>, 
+/**
+ * Summed over physical block devices.
+ */
+disk_read_bytes_per_sec: number, disk_write_bytes_per_sec: number, disk_read_iops: number, disk_write_iops: number, mounts: Array<MountSample>, 
+/**
+ * Summed over non-loopback interfaces.
+ */
+net_rx_bytes_per_sec: number, net_tx_bytes_per_sec: number, sensors: Array<SensorSample>, battery_percent: number | null, 
+/**
+ * Summed over RAPL package zones.
+ */
+package_power_watts: number | null, };
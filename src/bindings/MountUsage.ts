@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type MountUsage = { mount_point: string, device: string, file_system: string, total_bytes: number, used_bytes: number, available_bytes: number, inodes_total: number | null, inodes_used: number | null, inodes_available: number | null, is_removable: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkInterfaceInfo } from "./NetworkInterfaceInfo";
+
+export type NetworkInfo = { interfaces: Array<NetworkInterfaceInfo>, 
+/**
+ * Open TCP sockets (IPv4 and IPv6) by state, e.g. `established`,
+ * `time_wait`. Linux only.
+ */
+tcp_states: { [key in string]?: number } | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Counters of one interface since boot, with rates since the previous
+ * refresh.
+ */
+export type NetworkInterfaceInfo = { name: string, addresses: Array<string>, is_loopback: boolean, rx_bytes: number, tx_bytes: number, rx_packets: number, tx_packets: number, rx_errors: number, tx_errors: number, rx_dropped: number | null, tx_dropped: number | null, rx_bytes_per_sec: number, tx_bytes_per_sec: number, rx_packets_per_sec: number, tx_packets_per_sec: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type OpenFile = { fd: number, 
+/**
+ * Link target, e.g. a path, `pipe:[123]` or `socket:[456]`.
+ */
+target: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProcessAction } from "./ProcessAction";
+
+/**
+ * Returned by `request_process_action`; the action only runs once `token`
+ * is passed to `confirm_process_action` before `expires_at_ms`.
+ */
+export type PendingProcessAction = { token: string, pid: number, name: string, action: ProcessAction, expires_at_ms: number, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BatteryInfo } from "./BatteryInfo";
+import type { RaplZone } from "./RaplZone";
+
+export type PowerInfo = { battery: BatteryInfo | null, ac_online: boolean | null, rapl: Array<RaplZone>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PressureStat } from "./PressureStat";
+
+/**
+ * Linux pressure stall information per resource.
+ */
+export type PressureInfo = { cpu: PressureStat | null, memory: PressureStat | null, io: PressureStat | null, };
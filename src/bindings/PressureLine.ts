@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stall percentages over the last 10s/60s/300s, plus the total stall time.
+ */
+export type PressureLine = { avg10: number, avg60: number, avg300: number, total_us: number, };
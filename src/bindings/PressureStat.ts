@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PressureLine } from "./PressureLine";
+
+export type PressureStat = { 
+/**
+ * Share of time at least one task was stalled.
+ */
+some: PressureLine, 
+/**
+ * Share of time all non-idle tasks were stalled at once.
+ */
+full: PressureLine | null, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProcessSignal } from "./ProcessSignal";
+
+/**
+ * A control action waiting for, or carried by, a confirmation.
+ *
+ * Serialized with a `type` tag:
+ *
+ * ```
+ * use app_lib::models::process::{ProcessAction, ProcessSignal};
+ *
+ * let json = serde_json::to_value(ProcessAction::Signal { signal: ProcessSignal::Term }).unwrap();
+ * assert_eq!(json, serde_json::json!({ "type": "signal", "signal": "TERM" }));
+ *
+ * let action: ProcessAction =
+ *     serde_json::from_value(serde_json::json!({ "type": "set_affinity", "cpus": [0, 2] })).unwrap();
+ * assert_eq!(action, ProcessAction::SetAffinity { cpus: vec![0, 2] });
+ * ```
+ */
+export type ProcessAction = { "type": "signal", signal: ProcessSignal, } | { "type": "renice", nice: number, } | { "type": "set_affinity", cpus: Array<number>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EnvVar } from "./EnvVar";
+import type { OpenFile } from "./OpenFile";
+import type { ProcessIo } from "./ProcessIo";
+import type { ProcessMemory } from "./ProcessMemory";
+import type { ProcessSocket } from "./ProcessSocket";
+import type { ThreadInfo } from "./ThreadInfo";
+
+/**
+ * Everything known about the selected process. Apart from the first few
+ * fields this is read from `/proc/<pid>` and only filled in on Linux, as far
+ * as the app is allowed to read the process.
+ */
+export type ProcessDetail = { pid: number, name: string, cpu_usage: number, memory: number, status: string, exe_path: string | null, command_line: string | null, cwd: string | null, uid: number | null, gid: number | null, cgroup: string | null, memory_breakdown: ProcessMemory | null, io: ProcessIo | null, fd_count: number | null, 
+/**
+ * At most [`crate::process::detail::MAX_OPEN_FILES`] entries.
+ */
+open_files: Array<OpenFile>, sockets: Array<ProcessSocket>, threads: Array<ThreadInfo>, environment: Array<EnvVar>, };
@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PidRange } from "./PidRange";
+
+/**
+ * Which processes the process list hides unless "show all" is on.
+ *
+ * ```
+ * use app_lib::{models::system_info::LocalProcessInfo, process::filter::ProcessFilter};
+ *
+ * let filter = ProcessFilter {
+ *     hidden_users: vec!["nobody".into()],
+ *     hidden_names: vec!["kworker/*".into()],
+ *     ..ProcessFilter::default()
+ * };
+ * let process = |name: &str, user: &str| LocalProcessInfo {
+ *     pid: 4242,
+ *     name: name.into(),
+ *     user: Some(user.into()),
+ *     ..LocalProcessInfo::default()
+ * };
+ *
+ * assert!(filter.hides(&process("kworker/0:1", "root")));
+ * assert!(filter.hides(&process("dnsmasq", "nobody")));
+ * assert!(!filter.hides(&process("postgres", "postgres")));
+ * ```
+ */
+export type ProcessFilter = { 
+/**
+ * Hides kernel threads. Only detectable on Linux.
+ */
+hide_kernel_threads: boolean, hidden_users: Array<string>, 
+/**
+ * Glob patterns (`*` and `?`) matched against the whole process name.
+ */
+hidden_names: Array<string>, hidden_pid_ranges: Array<PidRange>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Storage I/O of the process, with rates since the previous detail read.
+ */
+export type ProcessIo = { read_bytes: number, write_bytes: number, read_bytes_per_sec: number | null, write_bytes_per_sec: number | null, };
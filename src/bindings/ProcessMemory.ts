@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Bytes, from `/proc/<pid>/status`.
+ */
+export type ProcessMemory = { rss: number, vms: number, 
+/**
+ * File-backed and shared-memory resident pages.
+ */
+shared: number, swap: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ProcessSample = { timestamp_ms: number, cpu_usage: number, memory: number, disk_read_bytes_per_sec: number, disk_write_bytes_per_sec: number, };
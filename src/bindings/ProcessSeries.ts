@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProcessSample } from "./ProcessSample";
+
+/**
+ * Rolling history of one process, as returned by `get_process_history`.
+ */
+export type ProcessSeries = { pid: number, name: string, start_time: number, samples: Array<ProcessSample>, };
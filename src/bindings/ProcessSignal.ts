@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Signals the UI can send to a process.
+ */
+export type ProcessSignal = "TERM" | "KILL" | "STOP" | "CONT" | "HUP";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ProcessSocket = { fd: number, 
+/**
+ * `tcp`, `tcp6`, `udp`, `udp6` or `unix`.
+ */
+protocol: string, local_address: string | null, remote_address: string | null, 
+/**
+ * TCP state, e.g. `listen`; `None` for other protocols.
+ */
+state: string | null, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One process with its descendants, as returned by `get_process_tree`.
+ */
+export type ProcessTreeNode = { 
+/**
+ * CPU usage of this process and all its descendants.
+ */
+subtree_cpu_usage: number, subtree_memory: number, 
+/**
+ * Number of processes in the subtree, this one included.
+ */
+subtree_count: number, children: Array<ProcessTreeNode>, pid: number, name: string, cpu_usage: number, memory: number, parent_pid: number | null, user: string | null, 
+/**
+ * Seconds since the Unix epoch.
+ */
+start_time: number, 
+/**
+ * Only known on Linux.
+ */
+threads: number | null, 
+/**
+ * Linux kernel thread, see [`crate::process::filter::is_kernel_thread`].
+ */
+kernel_thread: boolean, disk_read_bytes_per_sec: number, disk_write_bytes_per_sec: number, };
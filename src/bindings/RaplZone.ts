@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One RAPL power domain, e.g. `package-0` or its `core` subzone.
+ */
+export type RaplZone = { zone: string, name: string, is_package: boolean, energy_uj: number, 
+/**
+ * Average since the previous refresh.
+ */
+power_watts: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Resolution tier of the on-disk metrics store.
+ */
+export type Resolution = "1s" | "1m" | "1h";
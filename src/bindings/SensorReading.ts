@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SensorKind } from "./SensorKind";
+
+/**
+ * One hardware sensor. Values are in °C, RPM or volts depending on `kind`.
+ */
+export type SensorReading = { 
+/**
+ * Stable across reboots, e.g. `hwmon/k10temp/0000:00:18.3/temp1`.
+ */
+id: string, kind: SensorKind, 
+/**
+ * hwmon chip name or thermal zone type.
+ */
+chip: string, label: string | null, value: number, high: number | null, critical: number | null, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockDeviceIo } from "./BlockDeviceIo";
+import type { MountUsage } from "./MountUsage";
+
+export type StorageInfo = { mounts: Array<MountUsage>, devices: Array<BlockDeviceIo>, };
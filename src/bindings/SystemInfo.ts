@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CpuCoreInfo } from "./CpuCoreInfo";
+import type { GpuInfo } from "./GpuInfo";
+import type { LoadAverage } from "./LoadAverage";
+import type { LocalProcessInfo } from "./LocalProcessInfo";
+import type { MemoryInfo } from "./MemoryInfo";
+import type { NetworkInfo } from "./NetworkInfo";
+import type { PowerInfo } from "./PowerInfo";
+import type { PressureInfo } from "./PressureInfo";
+import type { ProcessDetail } from "./ProcessDetail";
+import type { SensorReading } from "./SensorReading";
+import type { StorageInfo } from "./StorageInfo";
+
+export type SystemInfo = { cpu_usage: number, cpu_frequency_mhz: number, cpu_cores: Array<CpuCoreInfo>, physical_core_count: number | null, logical_core_count: number, load_average: LoadAverage | null, total_memory: number, used_memory: number, memory: MemoryInfo, pressure: PressureInfo | null, cpu_temp: number | null, sensors: Array<SensorReading>, cpu_name: string | null, gpu_name: string | null, gpu_temp: number | null, gpu_usage: number | null, gpus: Array<GpuInfo>, power: PowerInfo | null, storage: StorageInfo, network: NetworkInfo, processes: Array<LocalProcessInfo>, selected_process: ProcessDetail | null, };
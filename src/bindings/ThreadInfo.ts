@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadInfo = { tid: number, name: string, 
+/**
+ * Single-letter state from `/proc/<pid>/task/<tid>/stat`, e.g. `R`, `S`.
+ */
+state: string, 
+/**
+ * Share of total CPU like [`ProcessDetail::cpu_usage`]; `None` on the
+ * first read.
+ */
+cpu_usage: number | null, };
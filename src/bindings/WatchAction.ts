@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WatchAction = { "kind": "notify" } | { "kind": "run_script", program: string, args: Array<string>, } | { "kind": "restart_command" };
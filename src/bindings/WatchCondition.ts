@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * When a watch rule acts. CPU thresholds are per core like `top`: 100%
+ * is one core busy, and a process can reach 100% times the core count.
+ */
+export type WatchCondition = { "kind": "cpu_above", 
+/**
+ * Percent of one core; 100 is one core busy.
+ */
+percent: number, for_secs: number, } | { "kind": "memory_above", bytes: number, for_secs: number, } | { "kind": "disappears" };
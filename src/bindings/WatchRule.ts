@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WatchAction } from "./WatchAction";
+import type { WatchCondition } from "./WatchCondition";
+
+/**
+ * Watches the processes whose name matches `process` and acts when
+ * `condition` holds.
+ */
+export type WatchRule = { 
+/**
+ * Assigned when the rule is first saved.
+ */
+id: string, name: string, enabled: boolean, 
+/**
+ * Glob pattern (`*` and `?`) matched against the whole process name.
+ */
+process: string, condition: WatchCondition, actions: Array<WatchAction>, };
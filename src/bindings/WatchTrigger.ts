@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A rule whose condition started to hold.
+ */
+export type WatchTrigger = { rule_id: string, rule_name: string, 
+/**
+ * The offending process; none for [`WatchCondition::Disappears`].
+ */
+pid: number | null, process_name: string, message: string, at_ms: number, };
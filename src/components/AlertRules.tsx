@@ -0,0 +1,211 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import {
+    ActiveAlert,
+    AlertRecord,
+    BACKEND_EVENT,
+    EVENT_PROTOCOL_VERSION,
+    EventEnvelope,
+    MetricAlertRule,
+    errorMessage,
+} from '../types';
+
+const HISTORY_LIMIT = 50;
+
+// Metrics worth suggesting; any name from list_metrics works too.
+const SUGGESTED_METRICS = [
+    'cpu.usage',
+    'memory.used_percent',
+    'cpu.temp',
+    'gpu.temp',
+    'disk.free_percent[/]',
+    'swap.used',
+];
+
+const STATUS_COLORS: Record<AlertRecord['status'], string> = {
+    pending: 'text-yellow-500',
+    firing: 'text-red-500',
+    resolved: 'text-green-500',
+};
+
+// Metric threshold alerts: rules, what is pending or firing now, and the history.
+export function AlertRules() {
+    const [rules, setRules] = useState<MetricAlertRule[]>([]);
+    const [active, setActive] = useState<ActiveAlert[]>([]);
+    const [history, setHistory] = useState<AlertRecord[]>([]);
+    const [metrics, setMetrics] = useState<string[]>(SUGGESTED_METRICS);
+    const [error, setError] = useState<string | null>(null);
+    const [name, setName] = useState('');
+    const [metric, setMetric] = useState('cpu.usage');
+    const [comparison, setComparison] = useState<MetricAlertRule['comparison']>('above');
+    const [threshold, setThreshold] = useState(90);
+    const [clearThreshold, setClearThreshold] = useState('');
+    const [forSecs, setForSecs] = useState(30);
+    const [notify, setNotify] = useState(true);
+
+    useEffect(() => {
+        invoke<MetricAlertRule[]>('get_alert_rules').then(setRules).catch(err => setError(errorMessage(err)));
+        invoke<ActiveAlert[]>('get_active_alerts').then(setActive).catch(() => { });
+        invoke<AlertRecord[]>('get_alert_history', { limit: HISTORY_LIMIT }).then(setHistory).catch(() => { });
+        invoke<string[]>('list_metrics')
+            .then(names => setMetrics([...new Set([...SUGGESTED_METRICS, ...names])]))
+            .catch(() => { });
+
+        const unlisten = listen<EventEnvelope>(BACKEND_EVENT, event => {
+            const envelope = event.payload;
+            if (envelope.version === EVENT_PROTOCOL_VERSION && envelope.type === 'alert') {
+                setHistory(prev => [envelope.record, ...prev].slice(0, HISTORY_LIMIT));
+                invoke<ActiveAlert[]>('get_active_alerts').then(setActive).catch(() => { });
+            }
+        });
+        return () => {
+            unlisten.then(f => f());
+        };
+    }, []);
+
+    const save = async (rule: MetricAlertRule) => {
+        setError(null);
+        try {
+            const saved = await invoke<MetricAlertRule>('save_alert_rule', { rule });
+            setRules(prev =>
+                prev.some(r => r.id === saved.id) ? prev.map(r => (r.id === saved.id ? saved : r)) : [...prev, saved]
+            );
+            return true;
+        } catch (err) {
+            setError(errorMessage(err));
+            return false;
+        }
+    };
+
+    const remove = async (id: string) => {
+        setError(null);
+        try {
+            await invoke('delete_alert_rule', { id });
+            setRules(prev => prev.filter(r => r.id !== id));
+            setActive(prev => prev.filter(a => a.rule_id !== id));
+        } catch (err) {
+            setError(errorMessage(err));
+        }
+    };
+
+    const add = async () => {
+        const added = await save({
+            id: '',
+            name: name.trim() || `${metric} ${comparison} ${threshold}`,
+            enabled: true,
+            metric: metric.trim(),
+            comparison,
+            threshold,
+            clear_threshold: clearThreshold.trim() === '' ? null : Number(clearThreshold),
+            for_secs: forSecs,
+            notify,
+        });
+        if (added) {
+            setName('');
+            setClearThreshold('');
+        }
+    };
+
+    const inputClass = 'px-1 py-1 text-sm rounded border border-win-border bg-win-control text-win-text';
+
+    return (
+        <div className="bg-win-bg-secondary p-4 rounded border border-win-border mt-4">
+            <h3 className="text-lg font-medium mb-2 text-win-text">Alerts</h3>
+            {active.length > 0 && (
+                <div className="mb-3 text-sm space-y-0.5">
+                    {active.map(a => (
+                        <div key={a.rule_id} className={STATUS_COLORS[a.status]}>
+                            {a.status.toUpperCase()} {a.rule_name}: {a.value.toFixed(1)} since{' '}
+                            {new Date(a.since_ms).toLocaleTimeString()}
+                        </div>
+                    ))}
+                </div>
+            )}
+            {rules.length === 0 && <div className="text-sm text-win-text-secondary">No alert rules yet.</div>}
+            {rules.map(rule => (
+                <div key={rule.id} className="flex items-center gap-2 text-sm text-win-text py-1">
+                    <input
+                        type="checkbox"
+                        checked={rule.enabled}
+                        onChange={() => save({ ...rule, enabled: !rule.enabled })}
+                    />
+                    <span className="font-medium">{rule.name}</span>
+                    <span className="text-win-text-secondary">
+                        {rule.metric} {rule.comparison} {rule.threshold}
+                        {rule.clear_threshold !== null && ` (clears at ${rule.clear_threshold})`}
+                        {rule.for_secs > 0 && ` for ${rule.for_secs} s`}
+                        {rule.notify && ', notify'}
+                    </span>
+                    <button className="ml-auto px-2 py-0.5 rounded border border-win-border" onClick={() => remove(rule.id)}>
+                        Delete
+                    </button>
+                </div>
+            ))}
+            <div className="flex flex-wrap items-center gap-2 mt-3 text-sm text-win-text">
+                <input className={inputClass} placeholder="Name" value={name} onChange={e => setName(e.target.value)} />
+                <input className={inputClass} list="alert-metrics" value={metric} onChange={e => setMetric(e.target.value)} />
+                <datalist id="alert-metrics">
+                    {metrics.map(m => <option key={m} value={m} />)}
+                </datalist>
+                <select
+                    className={inputClass}
+                    value={comparison}
+                    onChange={e => setComparison(e.target.value as MetricAlertRule['comparison'])}
+                >
+                    <option value="above">above</option>
+                    <option value="below">below</option>
+                </select>
+                <input
+                    type="number"
+                    className={`${inputClass} w-24`}
+                    value={threshold}
+                    onChange={e => setThreshold(Number(e.target.value))}
+                />
+                <input
+                    type="number"
+                    className={`${inputClass} w-24`}
+                    placeholder="clears at"
+                    value={clearThreshold}
+                    onChange={e => setClearThreshold(e.target.value)}
+                />
+                <span>for</span>
+                <input
+                    type="number"
+                    min={0}
+                    className={`${inputClass} w-16`}
+                    value={forSecs}
+                    onChange={e => setForSecs(Number(e.target.value))}
+                />
+                <span>s</span>
+                <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={notify} onChange={() => setNotify(!notify)} /> Notify
+                </label>
+                <button
+                    className="px-2 py-1 rounded border border-win-border bg-win-control disabled:opacity-50"
+                    disabled={!metric.trim()}
+                    onClick={add}
+                >
+                    Add alert
+                </button>
+            </div>
+            {error && <div className="mt-2 text-sm text-red-500">{error}</div>}
+            {history.length > 0 && (
+                <div className="mt-3 text-xs text-win-text-secondary space-y-0.5 max-h-40 overflow-auto">
+                    {history.map(r => (
+                        <div key={`${r.rule_id}-${r.at_ms}-${r.status}`}>
+                            {new Date(r.at_ms).toLocaleString()}{' '}
+                            <span className={STATUS_COLORS[r.status]}>{r.status}</span>{' '}
+                            <span className="font-medium">{r.rule_name}</span>: {r.message}
+                            {r.entries && r.entries.length > 0 && (
+                                <div className="ml-4 font-mono truncate" title={r.entries.map(e => e.message).join('\n')}>
+                                    {r.entries[r.entries.length - 1].message}
+                                </div>
+                            )}
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
@@ -0,0 +1,88 @@
+import { useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { CommandStatus, errorMessage } from '../types'
+
+interface CommandLauncherProps {
+  status: CommandStatus | null
+}
+
+// Splits a command line on whitespace, keeping "double" or 'single' quoted parts together.
+function splitCommandLine(line: string): string[] {
+  const parts: string[] = []
+  const re = /"([^"]*)"|'([^']*)'|(\S+)/g
+  let match
+  while ((match = re.exec(line)) !== null) {
+    parts.push(match[1] ?? match[2] ?? match[3])
+  }
+  return parts
+}
+
+function describeExit(exit: NonNullable<CommandStatus['exit']>): string {
+  if (exit.signal !== null) return `killed by signal ${exit.signal}`
+  if (exit.code !== null) return `exited with code ${exit.code}`
+  return 'exited'
+}
+
+export function CommandLauncher({ status }: CommandLauncherProps) {
+  const [commandLine, setCommandLine] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const running = status?.pid != null
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null)
+    try {
+      await action()
+    } catch (err) {
+      setError(errorMessage(err))
+    }
+  }
+
+  const launch = () => {
+    const [program, ...args] = splitCommandLine(commandLine)
+    if (!program) return
+    run(() => invoke('launch_command', { spec: { program, args, cwd: null } }))
+  }
+
+  return (
+    <div className="flex items-center space-x-2 text-sm">
+      <input
+        type="text"
+        value={commandLine}
+        onChange={e => setCommandLine(e.target.value)}
+        onKeyDown={e => e.key === 'Enter' && !running && launch()}
+        placeholder="Command to run, e.g. cargo run"
+        className="px-2 py-1 w-64 rounded border bg-win-control border-win-border focus:outline-none focus:border-win-border-focus"
+      />
+      {running ? (
+        <button
+          onClick={() => run(() => invoke('stop_command'))}
+          className="px-3 py-1.5 rounded border bg-red-600 text-white hover:bg-red-700 border-win-border"
+        >
+          Stop
+        </button>
+      ) : (
+        <button
+          onClick={launch}
+          className="px-3 py-1.5 rounded border bg-win-control hover:bg-win-control-hover border-win-border"
+        >
+          Run
+        </button>
+      )}
+      {status && (
+        <button
+          onClick={() => run(() => invoke('restart_command'))}
+          className="px-3 py-1.5 rounded border bg-win-control hover:bg-win-control-hover border-win-border"
+        >
+          Restart
+        </button>
+      )}
+      {status && (
+        <span className="text-xs text-win-text-secondary truncate max-w-xs" title={status.command_line}>
+          {running ? `running (pid ${status.pid})` : status.exit ? describeExit(status.exit) : ''}
+          {status.restarts > 0 && `, ${status.restarts} restart${status.restarts === 1 ? '' : 's'}`}
+        </span>
+      )}
+      {error && <span className="text-xs text-red-500">{error}</span>}
+    </div>
+  )
+}
@@ -0,0 +1,149 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { LogAlertRule, errorMessage } from '../types';
+
+const LEVELS = ['ERROR', 'WARNING', 'INFO', 'DEBUG', 'TRACE'];
+
+function describeRule(rule: LogAlertRule): string {
+    const lines = rule.level ? `${rule.level} lines` : 'lines';
+    const matching = rule.pattern ? ` matching /${rule.pattern}/` : '';
+    return `≥ ${rule.min_matches} ${lines}${matching} in ${rule.window_secs} s, cooldown ${rule.cooldown_secs} s`;
+}
+
+// Log alert rules checked against the tailed lines; their alerts show up in the alert history.
+export function LogAlertRules() {
+    const [rules, setRules] = useState<LogAlertRule[]>([]);
+    const [error, setError] = useState<string | null>(null);
+    const [name, setName] = useState('');
+    const [level, setLevel] = useState('ERROR');
+    const [pattern, setPattern] = useState('');
+    const [minMatches, setMinMatches] = useState(1);
+    const [windowSecs, setWindowSecs] = useState(60);
+    const [cooldownSecs, setCooldownSecs] = useState(300);
+    const [notify, setNotify] = useState(true);
+
+    useEffect(() => {
+        invoke<LogAlertRule[]>('get_log_alert_rules')
+            .then(setRules)
+            .catch(err => setError(errorMessage(err)));
+    }, []);
+
+    const save = async (rule: LogAlertRule) => {
+        setError(null);
+        try {
+            const saved = await invoke<LogAlertRule>('save_log_alert_rule', { rule });
+            setRules(prev =>
+                prev.some(r => r.id === saved.id) ? prev.map(r => (r.id === saved.id ? saved : r)) : [...prev, saved]
+            );
+            return true;
+        } catch (err) {
+            setError(errorMessage(err));
+            return false;
+        }
+    };
+
+    const remove = async (id: string) => {
+        setError(null);
+        try {
+            await invoke('delete_log_alert_rule', { id });
+            setRules(prev => prev.filter(r => r.id !== id));
+        } catch (err) {
+            setError(errorMessage(err));
+        }
+    };
+
+    const add = async () => {
+        const rule: LogAlertRule = {
+            id: '',
+            name: '',
+            enabled: true,
+            level: level || null,
+            pattern: pattern.trim() || null,
+            min_matches: minMatches,
+            window_secs: windowSecs,
+            cooldown_secs: cooldownSecs,
+            notify,
+        };
+        rule.name = name.trim() || describeRule(rule);
+        if (await save(rule)) {
+            setName('');
+            setPattern('');
+        }
+    };
+
+    const inputClass = 'px-1 py-1 text-sm rounded border border-win-border bg-win-control text-win-text';
+
+    return (
+        <div className="bg-win-bg-secondary p-4 rounded border border-win-border mt-4">
+            <h3 className="text-lg font-medium mb-2 text-win-text">Log alerts</h3>
+            {rules.length === 0 && <div className="text-sm text-win-text-secondary">No log alert rules yet.</div>}
+            {rules.map(rule => (
+                <div key={rule.id} className="flex items-center gap-2 text-sm text-win-text py-1">
+                    <input
+                        type="checkbox"
+                        checked={rule.enabled}
+                        onChange={() => save({ ...rule, enabled: !rule.enabled })}
+                    />
+                    <span className="font-medium">{rule.name}</span>
+                    <span className="text-win-text-secondary">
+                        {describeRule(rule)}
+                        {rule.notify && ', notify'}
+                    </span>
+                    <button className="ml-auto px-2 py-0.5 rounded border border-win-border" onClick={() => remove(rule.id)}>
+                        Delete
+                    </button>
+                </div>
+            ))}
+            <div className="flex flex-wrap items-center gap-2 mt-3 text-sm text-win-text">
+                <input className={inputClass} placeholder="Name" value={name} onChange={e => setName(e.target.value)} />
+                <span>at least</span>
+                <input
+                    type="number"
+                    min={1}
+                    className={`${inputClass} w-20`}
+                    value={minMatches}
+                    onChange={e => setMinMatches(Number(e.target.value))}
+                />
+                <select className={inputClass} value={level} onChange={e => setLevel(e.target.value)}>
+                    <option value="">any</option>
+                    {LEVELS.map(l => <option key={l} value={l}>{l}</option>)}
+                </select>
+                <span>lines matching</span>
+                <input
+                    className={inputClass}
+                    placeholder="Regex, e.g. OutOfMemory (optional)"
+                    value={pattern}
+                    onChange={e => setPattern(e.target.value)}
+                />
+                <span>in</span>
+                <input
+                    type="number"
+                    min={1}
+                    className={`${inputClass} w-16`}
+                    value={windowSecs}
+                    onChange={e => setWindowSecs(Number(e.target.value))}
+                />
+                <span>s, cooldown</span>
+                <input
+                    type="number"
+                    min={0}
+                    className={`${inputClass} w-16`}
+                    value={cooldownSecs}
+                    onChange={e => setCooldownSecs(Number(e.target.value))}
+                />
+                <span>s</span>
+                <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={notify} onChange={() => setNotify(!notify)} /> Notify
+                </label>
+                <button
+                    className="px-2 py-1 rounded border border-win-border bg-win-control disabled:opacity-50"
+                    disabled={!level && !pattern.trim()}
+                    onClick={add}
+                >
+                    Add log alert
+                </button>
+            </div>
+            {error && <div className="mt-2 text-sm text-red-500">{error}</div>}
+        </div>
+    );
+}
@@ -103,6 +103,11 @@ const width = useTransform(value, v => `${Math.round(v * 100)}%`);
           <span className="font-semibold w-20 flex-shrink-0">
             [{log.level}]
           </span>
+          {log.stream && (
+            <span className={`w-14 flex-shrink-0 text-xs ${log.stream === 'stderr' ? 'text-red-500' : 'text-win-text-secondary'}`}>
+              {log.stream}
+            </span>
+          )}
           <span className="flex-1 whitespace-nowrap">
             {log.message}
           </span>
@@ -1,17 +1,25 @@
-import React, { useState, useMemo } from 'react'
+import React, { useState, useMemo, useEffect } from 'react'
 import { AnimatePresence, motion } from "motion/react"
+import { invoke } from '@tauri-apps/api/core'
 import { useTheme } from './ThemeProvider'
+import { ProcessTreeNode } from '../types'
 
 export interface ProcessInfo {
     pid: number;
     name: string;
     cpu_usage: number;
     memory: number;
+    user?: string | null;
+    threads?: number | null;
 }
 
 interface ProcessTableModalProps {
     processes: ProcessInfo[];
     visible: boolean;
+    showAll: boolean;
+    onShowAllChange: (showAll: boolean) => void;
+    selectedPid: number | null;
+    onSelect: (pid: number) => void;
     onClose: () => void;
 }
 interface SortableThProps {
@@ -21,12 +29,57 @@ interface SortableThProps {
   onClick: () => void;
 }
 type SortOrder = 'asc' | 'desc' | undefined;
-export const ProcessTableModal: React.FC<ProcessTableModalProps> = ({ processes, visible, onClose }) => {
+export const ProcessTableModal: React.FC<ProcessTableModalProps> = ({ processes, visible, showAll, onShowAllChange, selectedPid, onSelect, onClose }) => {
     const [sortBy, setSortBy] = useState<'pid' | 'name' | 'cpu_usage' | 'memory'>('pid')
     const [sortOrder, setSortOrder] = useState<'asc' | 'desc'>('asc')
     const [searchOpen, setSearchOpen] = useState(false)
     const [search, setSearch] = useState('')
+    const [treeMode, setTreeMode] = useState(false)
+    const [tree, setTree] = useState<ProcessTreeNode[]>([])
+    const [collapsed, setCollapsed] = useState<Set<number>>(new Set())
     const { currentTheme } = useTheme()
+
+    useEffect(() => {
+        if (!visible || !treeMode) return
+        const fetchTree = () => {
+            invoke<ProcessTreeNode[]>('get_process_tree', { showAll })
+                .then(setTree)
+                .catch(err => console.error('Error getting process tree:', err))
+        }
+        fetchTree()
+        const interval = setInterval(fetchTree, 1000)
+        return () => clearInterval(interval)
+    }, [visible, treeMode, showAll])
+
+    const toggleCollapsed = (pid: number) => {
+        setCollapsed(prev => {
+            const next = new Set(prev)
+            if (next.has(pid)) next.delete(pid)
+            else next.add(pid)
+            return next
+        })
+    }
+
+    // Flattens the tree into rows; a search keeps every node that matches or
+    // has a matching descendant.
+    const treeRows = useMemo(() => {
+        const q = search.toLowerCase()
+        const matches = (node: ProcessTreeNode): boolean =>
+            !q ||
+            node.pid.toString().includes(q) ||
+            node.name.toLowerCase().includes(q) ||
+            node.children.some(matches)
+        const rows: { node: ProcessTreeNode; depth: number }[] = []
+        const walk = (nodes: ProcessTreeNode[], depth: number) => {
+            for (const node of nodes) {
+                if (!matches(node)) continue
+                rows.push({ node, depth })
+                if (!collapsed.has(node.pid)) walk(node.children, depth + 1)
+            }
+        }
+        walk(tree, 0)
+        return rows
+    }, [tree, collapsed, search])
     
     const filteredProcesses = useMemo(() => {
         let arr = [...processes]
@@ -120,6 +173,19 @@ export const ProcessTableModal: React.FC<ProcessTableModalProps> = ({ processes,
                 />
               )}
             </AnimatePresence>
+            <button
+              onClick={() => setTreeMode(v => !v)}
+              className={`px-3 py-1 rounded text-sm text-win-text transition-colors ${
+                treeMode ? "bg-win-control-active" : "bg-win-control"
+              }`}
+              title="Group processes by parent"
+            >
+              Tree
+            </button>
+            <label className="flex items-center gap-1 text-sm text-win-text" title="Include processes hidden by the process filter">
+              <input type="checkbox" checked={showAll} onChange={e => onShowAllChange(e.target.checked)} />
+              Show all
+            </label>
           </div>
           <button
             className="px-4 py-2 bg-red-600 text-white rounded hover:bg-red-700 transition-colors"
@@ -156,22 +222,63 @@ export const ProcessTableModal: React.FC<ProcessTableModalProps> = ({ processes,
                   order={sortOrder}
                   onClick={() => handleSort('memory')}
                 />
+                <th className="px-4 py-2 text-left">User</th>
+                <th className="px-4 py-2 text-left">Threads</th>
               </tr>
             </thead>
             <tbody>
-              {filteredProcesses.length === 0 ? (
+              {treeMode ? (
+                treeRows.map(({ node, depth }) => (
+                  <tr
+                    key={node.pid}
+                    className={`hover:bg-win-control-hover cursor-pointer ${node.pid === selectedPid ? "bg-win-control-active" : ""}`}
+                    onClick={() => onSelect(node.pid)}
+                  >
+                    <td className="py-2 px-3">{node.pid}</td>
+                    <td className="py-2 px-3" style={{ paddingLeft: `${0.75 + depth * 1.25}rem` }}>
+                      {node.children.length > 0 ? (
+                        <button
+                          className="mr-1 w-4"
+                          onClick={e => {
+                            e.stopPropagation()
+                            toggleCollapsed(node.pid)
+                          }}
+                        >
+                          {collapsed.has(node.pid) ? '▸' : '▾'}
+                        </button>
+                      ) : (
+                        <span className="mr-1 inline-block w-4" />
+                      )}
+                      {node.name}
+                      {node.subtree_count > 1 && (
+                        <span className="ml-1 text-win-text-secondary">({node.subtree_count})</span>
+                      )}
+                    </td>
+                    <td className="py-2 px-3">{node.subtree_cpu_usage.toFixed(1)}%</td>
+                    <td className="py-2 px-3">{formatMemory(node.subtree_memory)}</td>
+                    <td className="py-2 px-3">{node.user ?? ''}</td>
+                    <td className="py-2 px-3">{node.threads ?? ''}</td>
+                  </tr>
+                ))
+              ) : filteredProcesses.length === 0 ? (
                 <tr>
-                  <td colSpan={4} className="py-4 text-center text-win-text-secondary">
+                  <td colSpan={6} className="py-4 text-center text-win-text-secondary">
                     No processes found.
                   </td>
                 </tr>
               ) : (
                 filteredProcesses.map(proc => (
-                  <tr key={proc.pid} className="hover:bg-win-control-hover">
+                  <tr
+                    key={proc.pid}
+                    className={`hover:bg-win-control-hover cursor-pointer ${proc.pid === selectedPid ? "bg-win-control-active" : ""}`}
+                    onClick={() => onSelect(proc.pid)}
+                  >
                     <td className="py-2 px-3">{proc.pid}</td>
                     <td className="py-2 px-3">{proc.name}</td>
                     <td className="py-2 px-3">{proc.cpu_usage.toFixed(1)}%</td>
                     <td className="py-2 px-3">{formatMemory(proc.memory)}</td>
+                    <td className="py-2 px-3">{proc.user ?? ''}</td>
+                    <td className="py-2 px-3">{proc.threads ?? ''}</td>
                   </tr>
                 ))
               )}
@@ -2,38 +2,38 @@ import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { ProcessTableModal } from './ProcessTableModal';
+import { WatchRules } from './WatchRules';
+import { AlertRules } from './AlertRules';
+import { LogAlertRules } from './LogAlertRules';
 import { AnimatePresence, motion } from 'motion/react';
-interface SystemInfo {
-    cpu_usage: number;
-    total_memory: number;
-    used_memory: number;
-    cpu_temp?: number | null;
-    cpu_name?: string | null;
-    gpu_name?: string | null;
-    gpu_temp?: number | null;
-    gpu_usage?: number | null;
-    processes: LocalProcessInfo[];
-    selected_process?: ProcessDetail | null;
-}
+import {
+    errorMessage,
+    LocalProcessInfo,
+    PendingProcessAction,
+    ProcessAction,
+    ProcessDetail,
+    ProcessSeries,
+    ProcessSignal,
+    SystemInfo,
+} from '../types';
 
-interface LocalProcessInfo {
-    pid: number;
-    name: string;
-    cpu_usage: number;
-    memory: number;
+// Minimal inline chart of one series, scaled to its own min/max.
+function Sparkline({ values, color }: { values: number[]; color: string }) {
+    if (values.length < 2) return <div className="h-8" />;
+    const min = Math.min(...values);
+    const max = Math.max(...values);
+    const span = max - min || 1;
+    const points = values
+        .map((v, i) => `${(i / (values.length - 1)) * 100},${30 - ((v - min) / span) * 28 - 1}`)
+        .join(' ');
+    return (
+        <svg viewBox="0 0 100 30" preserveAspectRatio="none" className="w-full h-8">
+            <polyline points={points} fill="none" stroke={color} strokeWidth="1.5" vectorEffect="non-scaling-stroke" />
+        </svg>
+    );
 }
 
 
-interface ProcessDetail {
-    pid: number;
-    name: string;
-    cpu_usage: number;
-    memory: number;
-    status: string;
-    exe_path?: string | null;
-    command_line?: string | null;
-}
-
 export function SystemMonitor() {
     const [showProcessTable, setShowProcessTable] = useState(false);
     const [processes, setProcesses] = useState<LocalProcessInfo[]>([]);
@@ -41,6 +41,11 @@ export function SystemMonitor() {
     const [selectedPid, setSelectedPid] = useState<number | null>(null);
     const [error, setError] = useState<string | null>(null);
     const [windowSize, setWindowSize] = useState({ width: 0, height: 0 });
+    const [pendingAction, setPendingAction] = useState<PendingProcessAction | null>(null);
+    const [actionError, setActionError] = useState<string | null>(null);
+    const [nice, setNice] = useState(0);
+    const [showAll, setShowAll] = useState(false);
+    const [processHistory, setProcessHistory] = useState<ProcessSeries | null>(null);
     useEffect(() => {
         if (!systemInfo) return;
         // Обновляем процессы по PID: если процесс уже есть — обновляем, если нет — добавляем
@@ -80,7 +85,8 @@ export function SystemMonitor() {
         const fetchSystemInfo = async () => {
             try {
                 const info = await invoke<SystemInfo>('get_system_info', {
-                    selectedPid: selectedPid || undefined
+                    selectedPid: selectedPid || undefined,
+                    showAll
                 });
                 setSystemInfo(info);
                 setError(null);
@@ -93,9 +99,66 @@ export function SystemMonitor() {
         fetchSystemInfo();
         const interval = setInterval(fetchSystemInfo, 1000);
         return () => clearInterval(interval);
-    }, [selectedPid]);
+    }, [selectedPid, showAll]);
     const selectedProcess = systemInfo?.selected_process;
 
+    useEffect(() => {
+        setProcessHistory(null);
+        if (selectedPid === null) return;
+        const fetchHistory = () => {
+            invoke<ProcessSeries>('get_process_history', { pid: selectedPid })
+                .then(setProcessHistory)
+                // No history yet until the sampler has seen the selection.
+                .catch(() => setProcessHistory(null));
+        };
+        fetchHistory();
+        const interval = setInterval(fetchHistory, 1000);
+        return () => clearInterval(interval);
+    }, [selectedPid]);
+
+    // Every action is a request/confirm pair: the backend hands out a token
+    // and only acts once it comes back.
+    const requestAction = async (pid: number, action: ProcessAction) => {
+        setActionError(null);
+        try {
+            setPendingAction(await invoke<PendingProcessAction>('request_process_action', { pid, action }));
+        } catch (err) {
+            setActionError(errorMessage(err));
+        }
+    };
+
+    const confirmAction = async () => {
+        if (!pendingAction) return;
+        try {
+            await invoke('confirm_process_action', { token: pendingAction.token });
+        } catch (err) {
+            setActionError(errorMessage(err));
+        }
+        setPendingAction(null);
+    };
+
+    const cancelAction = async () => {
+        if (!pendingAction) return;
+        await invoke('cancel_process_action', { token: pendingAction.token });
+        setPendingAction(null);
+    };
+
+    const describeAction = (action: ProcessAction): string => {
+        switch (action.type) {
+            case 'signal': return `send SIG${action.signal} to`;
+            case 'renice': return `set niceness ${action.nice} on`;
+            case 'set_affinity': return `pin to CPUs ${action.cpus.join(',')}`;
+        }
+    };
+
+    const signalButtons: { signal: ProcessSignal; label: string }[] = [
+        { signal: 'TERM', label: 'Terminate' },
+        { signal: 'KILL', label: 'Kill' },
+        { signal: 'STOP', label: 'Suspend' },
+        { signal: 'CONT', label: 'Resume' },
+        { signal: 'HUP', label: 'Hangup' },
+    ];
+
     const formatMemory = (bytes: number): string => {
         const units = ['B', 'KB', 'MB', 'GB'];
         let value = bytes;
@@ -179,6 +242,32 @@ export function SystemMonitor() {
                         style={{ width: `${systemInfo.cpu_usage}%` }}
                     ></div>
                 </div>
+                <div className="flex justify-between items-center mt-2 text-xs text-win-text-secondary">
+                    <span>
+                        Cores: {systemInfo.physical_core_count ?? '?'} / {systemInfo.logical_core_count} · {systemInfo.cpu_frequency_mhz} MHz
+                    </span>
+                    {systemInfo.load_average && (
+                        <span>
+                            Load: {systemInfo.load_average.one.toFixed(2)} {systemInfo.load_average.five.toFixed(2)} {systemInfo.load_average.fifteen.toFixed(2)}
+                        </span>
+                    )}
+                </div>
+                <div className="grid grid-cols-4 md:grid-cols-8 gap-1 mt-2">
+                    {systemInfo.cpu_cores.map(core => (
+                        <div
+                            key={core.index}
+                            title={`${core.name}: ${core.frequency_mhz} MHz${core.max_frequency_mhz ? ` (max ${core.max_frequency_mhz} MHz)` : ''}`}
+                        >
+                            <div className="w-full bg-win-control rounded h-1.5">
+                                <div
+                                    className={`h-1.5 rounded ${core.usage > 90 ? 'bg-red-500' : 'bg-blue-500'}`}
+                                    style={{ width: `${Math.min(core.usage, 100)}%` }}
+                                ></div>
+                            </div>
+                            <div className="text-[10px] text-win-text-secondary text-center">{core.usage.toFixed(0)}%</div>
+                        </div>
+                    ))}
+                </div>
             </div>
 
             {/* Memory */}
@@ -200,24 +289,43 @@ export function SystemMonitor() {
                         }}
                     ></div>
                 </div>
+                <div className="flex justify-between items-center mt-2 text-xs text-win-text-secondary">
+                    <span>
+                        Available: {formatMemory(systemInfo.memory.available)} · Free: {formatMemory(systemInfo.memory.free)}
+                        {systemInfo.memory.cached != null && ` · Cache: ${formatMemory(systemInfo.memory.cached + (systemInfo.memory.buffers ?? 0))}`}
+                    </span>
+                    <span>
+                        Swap: {systemInfo.memory.swap_total > 0
+                            ? `${formatMemory(systemInfo.memory.swap_used)} / ${formatMemory(systemInfo.memory.swap_total)}`
+                            : 'off'}
+                    </span>
+                </div>
+                {systemInfo.pressure && (
+                    <div className="text-xs text-win-text-secondary mt-1" title="Share of time tasks stalled waiting for the resource (avg over 10s / 60s / 300s)">
+                        Pressure: {([['CPU', systemInfo.pressure.cpu], ['Memory', systemInfo.pressure.memory], ['IO', systemInfo.pressure.io]] as const)
+                            .filter(([, stat]) => stat)
+                            .map(([name, stat]) => `${name} ${stat!.some.avg10.toFixed(1)}% / ${stat!.some.avg60.toFixed(1)}% / ${stat!.some.avg300.toFixed(1)}%`)
+                            .join(' · ')}
+                    </div>
+                )}
             </div>
 
             {/* GPU */}
-            {systemInfo.gpu_name && (
-                <div className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
-                    <h3 className="text-lg font-medium mb-2 text-win-text">GPU: {systemInfo.gpu_name}</h3>
+            {systemInfo.gpus.map(gpu => (
+                <div key={gpu.uuid ?? gpu.index} className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
+                    <h3 className="text-lg font-medium mb-2 text-win-text">GPU {gpu.index}: {gpu.name}</h3>
                     <div className="grid grid-cols-2 gap-4">
                         <div>
                             <div className="flex justify-between items-center mb-1">
                                 <span className="text-sm font-medium text-win-text-secondary">Usage</span>
                                 <span className="text-sm text-win-text-secondary font-medium">
-                                    {(systemInfo.gpu_usage || 0).toFixed(1) ?? " "}%
+                                    {(gpu.utilization || 0).toFixed(1)}%
                                 </span>
                             </div>
                             <div className="w-full bg-win-control rounded-full h-2.5">
                                 <div
                                     className="bg-purple-500 h-2.5 rounded-full"
-                                    style={{ width: `${systemInfo.gpu_usage || 0}%` }}
+                                    style={{ width: `${gpu.utilization || 0}%` }}
                                 ></div>
                             </div>
                         </div>
@@ -225,14 +333,150 @@ export function SystemMonitor() {
                             <div className="flex justify-between items-center">
                                 <span className="text-sm font-medium text-win-text-secondary">Temperature</span>
                                 <span className="text-win-text font-medium">
-                                    {formatTemp(systemInfo.gpu_temp) ?? " "}
+                                    {formatTemp(gpu.temperature)}
                                 </span>
                             </div>
                         </div>
                     </div>
+                    <div className="grid grid-cols-2 gap-x-4 mt-2 text-xs text-win-text-secondary">
+                        {gpu.memory_total != null && (
+                            <span>Memory: {formatMemory(gpu.memory_used ?? 0)} / {formatMemory(gpu.memory_total)}</span>
+                        )}
+                        {gpu.power_draw_mw != null && (
+                            <span>
+                                Power: {(gpu.power_draw_mw / 1000).toFixed(0)} W
+                                {gpu.power_limit_mw != null && ` / ${(gpu.power_limit_mw / 1000).toFixed(0)} W`}
+                            </span>
+                        )}
+                        {gpu.fan_speed_percent != null && <span>Fan: {gpu.fan_speed_percent}%</span>}
+                        {gpu.graphics_clock_mhz != null && (
+                            <span>Clocks: {gpu.graphics_clock_mhz} / {gpu.memory_clock_mhz ?? '?'} MHz</span>
+                        )}
+                        {(gpu.encoder_utilization != null || gpu.decoder_utilization != null) && (
+                            <span>Enc / Dec: {gpu.encoder_utilization ?? '?'}% / {gpu.decoder_utilization ?? '?'}%</span>
+                        )}
+                        {gpu.processes.length > 0 && <span>Processes: {gpu.processes.length}</span>}
+                    </div>
+                    {gpu.throttle_reasons.length > 0 && (
+                        <div className="text-xs text-yellow-500 mt-1">Throttled: {gpu.throttle_reasons.join(', ')}</div>
+                    )}
+                </div>
+            ))}
+
+            {/* Power */}
+            {systemInfo.power && (
+                <div className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
+                    <h3 className="text-lg font-medium mb-2 text-win-text">Power</h3>
+                    {systemInfo.power.battery && (
+                        <>
+                            <div className="flex justify-between items-center mb-1">
+                                <span className="text-sm text-win-text-secondary">
+                                    Battery: {systemInfo.power.battery.state}
+                                    {systemInfo.power.battery.time_remaining_secs != null &&
+                                        ` · ${Math.floor(systemInfo.power.battery.time_remaining_secs / 3600)}h ${Math.floor(systemInfo.power.battery.time_remaining_secs / 60) % 60}m left`}
+                                </span>
+                                <span className="text-sm text-win-text-secondary font-medium">
+                                    {systemInfo.power.battery.charge_percent.toFixed(0)}%
+                                </span>
+                            </div>
+                            <div className="w-full bg-win-control rounded-full h-2.5 mb-2">
+                                <div
+                                    className={`h-2.5 rounded-full ${systemInfo.power.battery.charge_percent < 20 ? 'bg-red-500' : 'bg-green-500'}`}
+                                    style={{ width: `${systemInfo.power.battery.charge_percent}%` }}
+                                ></div>
+                            </div>
+                        </>
+                    )}
+                    <div className="flex flex-wrap gap-x-4 text-xs text-win-text-secondary">
+                        {systemInfo.power.ac_online != null && <span>AC: {systemInfo.power.ac_online ? 'connected' : 'disconnected'}</span>}
+                        {systemInfo.power.rapl.filter(zone => zone.power_watts != null).map(zone => (
+                            <span key={zone.zone}>{zone.name}: {zone.power_watts!.toFixed(1)} W</span>
+                        ))}
+                    </div>
+                </div>
+            )}
+
+            {/* Sensors */}
+            {systemInfo.sensors.length > 0 && (
+                <div className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
+                    <h3 className="text-lg font-medium mb-2 text-win-text">Sensors</h3>
+                    <div className="grid grid-cols-1 md:grid-cols-2 gap-x-4">
+                        {systemInfo.sensors.map(sensor => {
+                            const unit = sensor.kind === 'temperature' ? '°C' : sensor.kind === 'fan' ? ' RPM' : ' V';
+                            const digits = sensor.kind === 'voltage' ? 3 : sensor.kind === 'fan' ? 0 : 1;
+                            const critical = sensor.critical != null && sensor.value >= sensor.critical;
+                            const high = sensor.high != null && sensor.value >= sensor.high;
+                            return (
+                                <div key={sensor.id} className="flex justify-between text-xs text-win-text-secondary" title={sensor.id}>
+                                    <span>{sensor.chip}{sensor.label ? ` · ${sensor.label}` : ''}</span>
+                                    <span className={critical ? 'text-red-500' : high ? 'text-yellow-500' : ''}>
+                                        {sensor.value.toFixed(digits)}{unit}
+                                        {sensor.critical != null && ` (crit ${sensor.critical.toFixed(digits)}${unit})`}
+                                    </span>
+                                </div>
+                            );
+                        })}
+                    </div>
                 </div>
             )}
 
+            {/* Network */}
+            <div className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
+                <h3 className="text-lg font-medium mb-2 text-win-text">Network</h3>
+                {systemInfo.network.interfaces.filter(iface => !iface.is_loopback).map(iface => (
+                    <div key={iface.name} className="flex justify-between text-sm text-win-text-secondary mb-1">
+                        <span title={iface.addresses.join(', ')}>{iface.name}</span>
+                        <span>
+                            ↓ {formatMemory(iface.rx_bytes_per_sec)}/s · ↑ {formatMemory(iface.tx_bytes_per_sec)}/s
+                            {(iface.rx_errors + iface.tx_errors + (iface.rx_dropped ?? 0) + (iface.tx_dropped ?? 0)) > 0 && (
+                                <span className="text-red-500">
+                                    {' '}· err {iface.rx_errors + iface.tx_errors} · drop {(iface.rx_dropped ?? 0) + (iface.tx_dropped ?? 0)}
+                                </span>
+                            )}
+                        </span>
+                    </div>
+                ))}
+                {systemInfo.network.tcp_states && (
+                    <div className="text-xs text-win-text-secondary mt-2">
+                        TCP: {Object.entries(systemInfo.network.tcp_states).map(([state, count]) => `${state} ${count}`).join(' · ')}
+                    </div>
+                )}
+            </div>
+
+            {/* Storage */}
+            <div className="bg-win-bg-secondary p-4 rounded border border-win-border md:col-span-2">
+                <h3 className="text-lg font-medium mb-2 text-win-text">Storage</h3>
+                {systemInfo.storage.mounts.map(mount => {
+                    const percent = mount.total_bytes > 0 ? mount.used_bytes / mount.total_bytes * 100 : 0;
+                    return (
+                        <div key={mount.mount_point} className="mb-2">
+                            <div className="flex justify-between items-center mb-1">
+                                <span className="text-sm text-win-text-secondary" title={`${mount.device} (${mount.file_system})`}>
+                                    {mount.mount_point}
+                                </span>
+                                <span className="text-sm text-win-text-secondary">
+                                    {formatMemory(mount.used_bytes)} / {formatMemory(mount.total_bytes)} · {percent.toFixed(1)}%
+                                    {mount.inodes_total ? ` · inodes ${((mount.inodes_used ?? 0) / mount.inodes_total * 100).toFixed(1)}%` : ''}
+                                </span>
+                            </div>
+                            <div className="w-full bg-win-control rounded-full h-2.5">
+                                <div
+                                    className={`h-2.5 rounded-full ${percent > 90 ? 'bg-red-500' : 'bg-yellow-500'}`}
+                                    style={{ width: `${percent}%` }}
+                                ></div>
+                            </div>
+                        </div>
+                    );
+                })}
+                {systemInfo.storage.devices.map(device => (
+                    <div key={device.name} className="flex justify-between text-xs text-win-text-secondary">
+                        <span>{device.name}</span>
+                        <span>
+                            R {formatMemory(device.read_bytes_per_sec)}/s ({device.read_iops.toFixed(0)} IOPS) · W {formatMemory(device.write_bytes_per_sec)}/s ({device.write_iops.toFixed(0)} IOPS)
+                        </span>
+                    </div>
+                ))}
+            </div>
 
             {/* Выбор процесса */}
             <div className="mb-4">
@@ -257,6 +501,10 @@ export function SystemMonitor() {
                         <ProcessTableModal
                             processes={processes}
                             visible={showProcessTable}
+                            showAll={showAll}
+                            onShowAllChange={setShowAll}
+                            selectedPid={selectedPid}
+                            onSelect={setSelectedPid}
                             onClose={() => setShowProcessTable(false)}
                         />
                     </motion.div>
@@ -294,10 +542,166 @@ export function SystemMonitor() {
                                 <h4 className="text-sm font-medium text-win-text-secondary">Memory</h4>
                                 <div className="text-win-text">{formatMemory(selectedProcess.memory)}</div>
                             </div>
+                            {selectedProcess.memory_breakdown && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">RSS / Virtual / Shared / Swap</h4>
+                                    <div className="text-win-text">
+                                        {formatMemory(selectedProcess.memory_breakdown.rss)} / {formatMemory(selectedProcess.memory_breakdown.vms)} / {formatMemory(selectedProcess.memory_breakdown.shared)} / {formatMemory(selectedProcess.memory_breakdown.swap)}
+                                    </div>
+                                </div>
+                            )}
+                            {selectedProcess.io && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Disk I/O (read / write)</h4>
+                                    <div className="text-win-text">
+                                        {formatMemory(selectedProcess.io.read_bytes_per_sec ?? 0)}/s / {formatMemory(selectedProcess.io.write_bytes_per_sec ?? 0)}/s
+                                        <span className="ml-2 text-win-text-secondary">
+                                            ({formatMemory(selectedProcess.io.read_bytes)} / {formatMemory(selectedProcess.io.write_bytes)} total)
+                                        </span>
+                                    </div>
+                                </div>
+                            )}
+                            {selectedProcess.cwd && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Working directory</h4>
+                                    <div className="text-win-text break-all">{selectedProcess.cwd}</div>
+                                </div>
+                            )}
+                            {selectedProcess.uid !== null && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">UID / GID</h4>
+                                    <div className="text-win-text">{selectedProcess.uid} / {selectedProcess.gid ?? '?'}</div>
+                                </div>
+                            )}
+                            {selectedProcess.cgroup && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Cgroup</h4>
+                                    <div className="text-win-text break-all">{selectedProcess.cgroup}</div>
+                                </div>
+                            )}
+                            {selectedProcess.fd_count !== null && (
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Open files / sockets / threads</h4>
+                                    <div className="text-win-text">
+                                        {selectedProcess.fd_count} / {selectedProcess.sockets.length} / {selectedProcess.threads.length}
+                                    </div>
+                                </div>
+                            )}
                         </div>
+                        {processHistory && processHistory.samples.length > 1 && (
+                            <div className="grid grid-cols-3 gap-4 mt-3">
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">CPU history</h4>
+                                    <Sparkline values={processHistory.samples.map(s => s.cpu_usage)} color="#3b82f6" />
+                                </div>
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Memory history</h4>
+                                    <Sparkline values={processHistory.samples.map(s => s.memory)} color="#22c55e" />
+                                </div>
+                                <div>
+                                    <h4 className="text-sm font-medium text-win-text-secondary">Disk I/O history</h4>
+                                    <Sparkline
+                                        values={processHistory.samples.map(s => s.disk_read_bytes_per_sec + s.disk_write_bytes_per_sec)}
+                                        color="#f59e0b"
+                                    />
+                                </div>
+                            </div>
+                        )}
+                        {selectedProcess.threads.length > 0 && (
+                            <details className="mt-3 text-sm text-win-text">
+                                <summary className="cursor-pointer text-win-text-secondary">Threads</summary>
+                                {selectedProcess.threads.map(t => (
+                                    <div key={t.tid} className="flex gap-3">
+                                        <span className="w-16">{t.tid}</span>
+                                        <span className="flex-1">{t.name}</span>
+                                        <span className="w-6">{t.state}</span>
+                                        <span className="w-16 text-right">{t.cpu_usage !== null ? `${t.cpu_usage.toFixed(1)}%` : ''}</span>
+                                    </div>
+                                ))}
+                            </details>
+                        )}
+                        {selectedProcess.sockets.length > 0 && (
+                            <details className="mt-2 text-sm text-win-text">
+                                <summary className="cursor-pointer text-win-text-secondary">Sockets</summary>
+                                {selectedProcess.sockets.map(s => (
+                                    <div key={s.fd} className="flex gap-3">
+                                        <span className="w-10">{s.fd}</span>
+                                        <span className="w-12">{s.protocol}</span>
+                                        <span className="flex-1 break-all">
+                                            {s.local_address ?? ''}{s.remote_address ? ` → ${s.remote_address}` : ''}
+                                        </span>
+                                        <span>{s.state ?? ''}</span>
+                                    </div>
+                                ))}
+                            </details>
+                        )}
+                        {selectedProcess.open_files.length > 0 && (
+                            <details className="mt-2 text-sm text-win-text">
+                                <summary className="cursor-pointer text-win-text-secondary">Open files</summary>
+                                {selectedProcess.open_files.map(f => (
+                                    <div key={f.fd} className="flex gap-3">
+                                        <span className="w-10">{f.fd}</span>
+                                        <span className="flex-1 break-all">{f.target}</span>
+                                    </div>
+                                ))}
+                            </details>
+                        )}
+                        {selectedProcess.environment.length > 0 && (
+                            <details className="mt-2 text-sm text-win-text">
+                                <summary className="cursor-pointer text-win-text-secondary">Environment</summary>
+                                {selectedProcess.environment.map(e => (
+                                    <div key={e.name} className="break-all">
+                                        <span className="font-medium">{e.name}</span>=
+                                        <span className={e.masked ? 'text-win-text-secondary' : ''}>{e.value}</span>
+                                    </div>
+                                ))}
+                            </details>
+                        )}
+                        <div className="flex flex-wrap items-center gap-2 mt-4">
+                            {signalButtons.map(({ signal, label }) => (
+                                <button
+                                    key={signal}
+                                    className="px-2 py-1 text-sm rounded border border-win-border bg-win-control text-win-text"
+                                    onClick={() => requestAction(selectedProcess.pid, { type: 'signal', signal })}
+                                >
+                                    {label}
+                                </button>
+                            ))}
+                            <input
+                                type="number"
+                                min={-20}
+                                max={19}
+                                value={nice}
+                                onChange={e => setNice(Number(e.target.value))}
+                                className="w-16 px-1 py-1 text-sm rounded border border-win-border bg-win-control text-win-text"
+                            />
+                            <button
+                                className="px-2 py-1 text-sm rounded border border-win-border bg-win-control text-win-text"
+                                onClick={() => requestAction(selectedProcess.pid, { type: 'renice', nice })}
+                            >
+                                Renice
+                            </button>
+                        </div>
+                        {pendingAction && (
+                            <div className="flex items-center gap-2 mt-3 text-sm text-win-text">
+                                <span>
+                                    Really {describeAction(pendingAction.action)} {pendingAction.name} ({pendingAction.pid})?
+                                </span>
+                                <button className="px-2 py-1 rounded bg-red-600 text-white" onClick={confirmAction}>
+                                    Confirm
+                                </button>
+                                <button className="px-2 py-1 rounded border border-win-border" onClick={cancelAction}>
+                                    Cancel
+                                </button>
+                            </div>
+                        )}
+                        {actionError && <div className="mt-2 text-sm text-red-500">{actionError}</div>}
                     </div>
                 )
             }
+            <AlertRules />
+            <LogAlertRules />
+            <WatchRules />
         </div >
     );
 }
\ No newline at end of file
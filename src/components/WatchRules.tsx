@@ -0,0 +1,209 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import {
+    BACKEND_EVENT,
+    EVENT_PROTOCOL_VERSION,
+    EventEnvelope,
+    WatchAction,
+    WatchCondition,
+    WatchRule,
+    WatchTrigger,
+    errorMessage,
+} from '../types';
+
+const MAX_TRIGGERS = 20;
+
+function describeCondition(condition: WatchCondition): string {
+    switch (condition.kind) {
+        case 'cpu_above':
+            return `CPU > ${condition.percent}% (per core) for ${condition.for_secs} s`;
+        case 'memory_above':
+            return `memory > ${Math.round(condition.bytes / 1048576)} MiB for ${condition.for_secs} s`;
+        case 'disappears':
+            return 'disappears';
+    }
+}
+
+function describeAction(action: WatchAction): string {
+    switch (action.kind) {
+        case 'notify':
+            return 'notify';
+        case 'run_script':
+            return `run ${action.program}`;
+        case 'restart_command':
+            return 'restart command';
+    }
+}
+
+// Process watch rules: list, add, enable/disable, delete, and the latest triggers.
+export function WatchRules() {
+    const [rules, setRules] = useState<WatchRule[]>([]);
+    const [triggers, setTriggers] = useState<WatchTrigger[]>([]);
+    const [error, setError] = useState<string | null>(null);
+    const [name, setName] = useState('');
+    const [pattern, setPattern] = useState('');
+    const [kind, setKind] = useState<WatchCondition['kind']>('cpu_above');
+    const [threshold, setThreshold] = useState(90);
+    const [forSecs, setForSecs] = useState(60);
+    const [notify, setNotify] = useState(true);
+    const [restart, setRestart] = useState(false);
+    const [script, setScript] = useState('');
+
+    useEffect(() => {
+        invoke<WatchRule[]>('get_watch_rules')
+            .then(setRules)
+            .catch(err => setError(errorMessage(err)));
+
+        const unlisten = listen<EventEnvelope>(BACKEND_EVENT, event => {
+            const envelope = event.payload;
+            if (envelope.version === EVENT_PROTOCOL_VERSION && envelope.type === 'watch_triggered') {
+                setTriggers(prev => [envelope.trigger, ...prev].slice(0, MAX_TRIGGERS));
+            }
+        });
+        return () => {
+            unlisten.then(f => f());
+        };
+    }, []);
+
+    const save = async (rule: WatchRule) => {
+        setError(null);
+        try {
+            const saved = await invoke<WatchRule>('save_watch_rule', { rule });
+            setRules(prev =>
+                prev.some(r => r.id === saved.id) ? prev.map(r => (r.id === saved.id ? saved : r)) : [...prev, saved]
+            );
+            return true;
+        } catch (err) {
+            setError(errorMessage(err));
+            return false;
+        }
+    };
+
+    const remove = async (id: string) => {
+        setError(null);
+        try {
+            await invoke('delete_watch_rule', { id });
+            setRules(prev => prev.filter(r => r.id !== id));
+        } catch (err) {
+            setError(errorMessage(err));
+        }
+    };
+
+    const add = async () => {
+        const condition: WatchCondition =
+            kind === 'cpu_above'
+                ? { kind, percent: threshold, for_secs: forSecs }
+                : kind === 'memory_above'
+                    ? { kind, bytes: threshold * 1048576, for_secs: forSecs }
+                    : { kind };
+        const actions: WatchAction[] = [];
+        if (notify) actions.push({ kind: 'notify' });
+        if (restart) actions.push({ kind: 'restart_command' });
+        if (script.trim()) {
+            const [program, ...args] = script.trim().split(/\s+/);
+            actions.push({ kind: 'run_script', program, args });
+        }
+        const added = await save({
+            id: '',
+            name: name.trim() || `${pattern} ${kind.replace('_', ' ')}`,
+            enabled: true,
+            process: pattern.trim(),
+            condition,
+            actions,
+        });
+        if (added) {
+            setName('');
+            setPattern('');
+        }
+    };
+
+    const inputClass = 'px-1 py-1 text-sm rounded border border-win-border bg-win-control text-win-text';
+
+    return (
+        <div className="bg-win-bg-secondary p-4 rounded border border-win-border mt-4">
+            <h3 className="text-lg font-medium mb-2 text-win-text">Watch rules</h3>
+            {rules.length === 0 && <div className="text-sm text-win-text-secondary">No rules yet.</div>}
+            {rules.map(rule => (
+                <div key={rule.id} className="flex items-center gap-2 text-sm text-win-text py-1">
+                    <input
+                        type="checkbox"
+                        checked={rule.enabled}
+                        onChange={() => save({ ...rule, enabled: !rule.enabled })}
+                    />
+                    <span className="font-medium">{rule.name}</span>
+                    <span className="text-win-text-secondary">
+                        {rule.process}: {describeCondition(rule.condition)}
+                        {rule.actions.length > 0 && ` → ${rule.actions.map(describeAction).join(', ')}`}
+                    </span>
+                    <button className="ml-auto px-2 py-0.5 rounded border border-win-border" onClick={() => remove(rule.id)}>
+                        Delete
+                    </button>
+                </div>
+            ))}
+            <div className="flex flex-wrap items-center gap-2 mt-3 text-sm text-win-text">
+                <input className={inputClass} placeholder="Name" value={name} onChange={e => setName(e.target.value)} />
+                <input
+                    className={inputClass}
+                    placeholder="Process name, e.g. java*"
+                    value={pattern}
+                    onChange={e => setPattern(e.target.value)}
+                />
+                <select className={inputClass} value={kind} onChange={e => setKind(e.target.value as WatchCondition['kind'])}>
+                    <option value="cpu_above">CPU above (% of one core, like top)</option>
+                    <option value="memory_above">Memory above (MiB)</option>
+                    <option value="disappears">Disappears</option>
+                </select>
+                {kind !== 'disappears' && (
+                    <>
+                        <input
+                            type="number"
+                            min={1}
+                            className={`${inputClass} w-20`}
+                            value={threshold}
+                            onChange={e => setThreshold(Number(e.target.value))}
+                        />
+                        <span>for</span>
+                        <input
+                            type="number"
+                            min={0}
+                            className={`${inputClass} w-16`}
+                            value={forSecs}
+                            onChange={e => setForSecs(Number(e.target.value))}
+                        />
+                        <span>s</span>
+                    </>
+                )}
+                <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={notify} onChange={() => setNotify(!notify)} /> Notify
+                </label>
+                <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={restart} onChange={() => setRestart(!restart)} /> Restart command
+                </label>
+                <input
+                    className={inputClass}
+                    placeholder="Script to run (optional)"
+                    value={script}
+                    onChange={e => setScript(e.target.value)}
+                />
+                <button
+                    className="px-2 py-1 rounded border border-win-border bg-win-control disabled:opacity-50"
+                    disabled={!pattern.trim()}
+                    onClick={add}
+                >
+                    Add rule
+                </button>
+            </div>
+            {error && <div className="mt-2 text-sm text-red-500">{error}</div>}
+            {triggers.length > 0 && (
+                <div className="mt-3 text-xs text-win-text-secondary space-y-0.5">
+                    {triggers.map(t => (
+                        <div key={`${t.rule_id}-${t.at_ms}-${t.pid}`}>
+                            {new Date(t.at_ms).toLocaleTimeString()} <span className="font-medium">{t.rule_name}</span>: {t.message}
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
@@ -1,5 +1,92 @@
-export interface LogEntry {
-  timestamp: string;
-  level: string;
+// The IPC types are generated from the Rust types by ts-rs into ./bindings:
+// run `cargo test` in src-tauri after changing them, and bump
+// EVENT_PROTOCOL_VERSION when the shape of BackendEvent changes.
+export type { ActiveAlert } from './bindings/ActiveAlert';
+export type { AlertRecord } from './bindings/AlertRecord';
+export type { AlertStatus } from './bindings/AlertStatus';
+export type { BackendEvent } from './bindings/BackendEvent';
+export type { BatteryInfo } from './bindings/BatteryInfo';
+export type { BlockDeviceIo } from './bindings/BlockDeviceIo';
+export type { CommandExit } from './bindings/CommandExit';
+export type { CommandSpec } from './bindings/CommandSpec';
+export type { CommandStatus } from './bindings/CommandStatus';
+export type { Comparison } from './bindings/Comparison';
+export type { CpuCoreInfo } from './bindings/CpuCoreInfo';
+export type { EnvVar } from './bindings/EnvVar';
+export type { EventEnvelope } from './bindings/EventEnvelope';
+export type { FileWriter } from './bindings/FileWriter';
+export type { GpuInfo } from './bindings/GpuInfo';
+export type { GpuSample } from './bindings/GpuSample';
+export type { GpuProcessInfo } from './bindings/GpuProcessInfo';
+export type { LoadAverage } from './bindings/LoadAverage';
+export type { LocalProcessInfo } from './bindings/LocalProcessInfo';
+export type { LogAlertRule } from './bindings/LogAlertRule';
+export type { LogEntry } from './bindings/LogEntry';
+export type { LogStream } from './bindings/LogStream';
+export type { MemoryInfo } from './bindings/MemoryInfo';
+export type { MetricAlertRule } from './bindings/MetricAlertRule';
+export type { MetricPoint } from './bindings/MetricPoint';
+export type { MetricsSample } from './bindings/MetricsSample';
+export type { MountSample } from './bindings/MountSample';
+export type { MountUsage } from './bindings/MountUsage';
+export type { NetworkInfo } from './bindings/NetworkInfo';
+export type { NetworkInterfaceInfo } from './bindings/NetworkInterfaceInfo';
+export type { OpenFile } from './bindings/OpenFile';
+export type { PendingProcessAction } from './bindings/PendingProcessAction';
+export type { PidRange } from './bindings/PidRange';
+export type { PowerInfo } from './bindings/PowerInfo';
+export type { PressureInfo } from './bindings/PressureInfo';
+export type { PressureLine } from './bindings/PressureLine';
+export type { PressureStat } from './bindings/PressureStat';
+export type { ProcessAction } from './bindings/ProcessAction';
+export type { ProcessDetail } from './bindings/ProcessDetail';
+export type { ProcessFilter } from './bindings/ProcessFilter';
+export type { ProcessIo } from './bindings/ProcessIo';
+export type { ProcessMemory } from './bindings/ProcessMemory';
+export type { ProcessSample } from './bindings/ProcessSample';
+export type { ProcessSeries } from './bindings/ProcessSeries';
+export type { ProcessSignal } from './bindings/ProcessSignal';
+export type { ProcessSocket } from './bindings/ProcessSocket';
+export type { ProcessTreeNode } from './bindings/ProcessTreeNode';
+export type { RaplZone } from './bindings/RaplZone';
+export type { Resolution as MetricResolution } from './bindings/Resolution';
+export type { SensorKind } from './bindings/SensorKind';
+export type { SensorReading } from './bindings/SensorReading';
+export type { SensorSample } from './bindings/SensorSample';
+export type { StorageInfo } from './bindings/StorageInfo';
+export type { SystemInfo } from './bindings/SystemInfo';
+export type { ThreadInfo } from './bindings/ThreadInfo';
+export type { WatchAction } from './bindings/WatchAction';
+export type { WatchCondition } from './bindings/WatchCondition';
+export type { WatchRule } from './bindings/WatchRule';
+export type { WatchTrigger } from './bindings/WatchTrigger';
+
+// Mirrors src-tauri/src/error.rs — the shape every rejected `invoke` carries.
+// AppError serializes by hand, so this one is not generated.
+export type AppErrorCode =
+  | 'io'
+  | 'encoding'
+  | 'parse'
+  | 'not_found'
+  | 'permission_denied'
+  | 'cancelled'
+  | 'nvml'
+  | 'invalid_argument'
+  | 'protected_process'
+  | 'unsupported';
+
+export interface AppError {
+  code: AppErrorCode;
   message: string;
-}
\ No newline at end of file
+}
+
+export function errorMessage(err: unknown): string {
+  if (err && typeof err === 'object' && 'message' in err) {
+    return String((err as AppError).message);
+  }
+  return String(err);
+}
+
+// Mirrors BACKEND_EVENT and EVENT_PROTOCOL_VERSION in src-tauri/src/models/events.rs.
+export const BACKEND_EVENT = 'backend_event';
+export const EVENT_PROTOCOL_VERSION = 8;